@@ -1,7 +1,12 @@
 #![no_std]
 
 use embedded_hal::digital::InputPin;
-use led_matrix_core::{JoystickPosition, HEIGHT, WIDTH};
+use led_matrix_core::{
+    correction::Gamma,
+    mapping::{Mapping, Wiring},
+    power::PowerLimit,
+    JoystickPosition,
+};
 use rp_pico::hal::{
     self,
     gpio::{
@@ -20,7 +25,7 @@ use smart_leds::{brightness, SmartLedsWrite};
 // Import the actual crate to handle the Ws2812 protocol:
 use ws2812_pio::Ws2812;
 
-pub struct LedMatrix {
+pub struct LedMatrix<const W: usize = 8, const H: usize = 8, M = Wiring> {
     ws: Ws2812<PIO0, SM0, CountDown<'static>, Pin<Gpio19, FunctionPio0, PullDown>>,
     delay: cortex_m::delay::Delay,
 
@@ -32,20 +37,40 @@ pub struct LedMatrix {
 
     switch: Pin<Gpio9, FunctionSio<SioInput>, PullUp>,
 
-    leds: [[(u8, u8, u8); WIDTH as usize]; HEIGHT as usize],
+    // indexed by [y][x], the mapping determines the order in which the
+    // LEDs are written to the chain
+    leds: [[(u8, u8, u8); W]; H],
+    mapping: M,
 
-    // Bring down the overall brightness of the strip to not blow
-    // the USB power supply: every LED draws ~60mA, RGB means 3 LEDs per
-    // ws2812 LED, for 3 LEDs that would be: 3 * 3 * 60mA, which is
-    // already 540mA for just 3 white LEDs!
-    //
-    // default: 50 (~ 20%)
+    // default: 255, the power limit protects the power supply
     brightness: u8,
+
+    // applied to every channel before the brightness
+    gamma: Gamma,
+
+    // Every frame is scaled down just enough to not blow the USB power
+    // supply: every LED draws up to ~60mA, so a full white 8x8 matrix would
+    // draw almost 4A.
+    power_limit: PowerLimit,
 }
 
 static mut TIMER: Option<Timer> = None;
 
-pub fn run<F: FnOnce(LedMatrix) + Send + 'static>(f: F) -> ! {
+pub fn run<const W: usize, const H: usize, F: FnOnce(LedMatrix<W, H>) + Send + 'static>(f: F) -> ! {
+    run_mapped(Wiring::DEFAULT, f)
+}
+
+/// Like [run], but with a custom mapping of the LED chain to positions on
+/// the matrix, e.g. for differently soldered or rotated panels.
+pub fn run_mapped<
+    const W: usize,
+    const H: usize,
+    M: Mapping<W, H>,
+    F: FnOnce(LedMatrix<W, H, M>) + Send + 'static,
+>(
+    mapping: M,
+    f: F,
+) -> ! {
     // This function corresponds closely to the initilization code of the
     // example from the rp_pico repository.
 
@@ -98,7 +123,7 @@ pub fn run<F: FnOnce(LedMatrix) + Send + 'static>(f: F) -> ! {
     let timer = Timer::new(pac.TIMER, &mut pac.RESETS, &clocks);
     let count_down = unsafe {
         TIMER = Some(timer);
-        TIMER.as_ref().unwrap().count_down()
+        (*core::ptr::addr_of!(TIMER)).as_ref().unwrap().count_down()
     };
 
     // Split the PIO state machine 0 into individual objects, so that
@@ -125,8 +150,11 @@ pub fn run<F: FnOnce(LedMatrix) + Send + 'static>(f: F) -> ! {
         joystick_right,
         joystick_pressed,
         switch,
-        leds: Default::default(),
-        brightness: 50, // default brightness of about 20%
+        leds: [[(0, 0, 0); W]; H],
+        mapping,
+        brightness: 255,
+        gamma: Gamma::LINEAR,
+        power_limit: PowerLimit::USB,
     };
 
     f(matrix);
@@ -136,16 +164,24 @@ pub fn run<F: FnOnce(LedMatrix) + Send + 'static>(f: F) -> ! {
     loop {}
 }
 
-impl led_matrix_core::LedMatrixCore for LedMatrix {
+impl<const W: usize, const H: usize, M: Mapping<W, H>> led_matrix_core::LedMatrixCore<W, H>
+    for LedMatrix<W, H, M>
+{
     fn apply(&mut self) {
+        let leds = &self.leds;
+        let mapping = &self.mapping;
+        let gamma = &self.gamma;
+        let limited_brightness = self.power_limit.limit_brightness(
+            leds.iter().flatten().map(|&led| gamma.correct(led)),
+            self.brightness,
+        );
         self.ws
             .write(brightness(
-                self.leds
-                    .iter()
-                    .rev()
-                    .flat_map(|row| row.iter())
-                    .map(|&c| c.into()),
-                self.brightness,
+                (0..W * H).map(|i| {
+                    let (x, y) = mapping.position(i);
+                    gamma.correct(leds[y][x]).into()
+                }),
+                limited_brightness,
             ))
             .unwrap();
     }
@@ -154,6 +190,14 @@ impl led_matrix_core::LedMatrixCore for LedMatrix {
         self.brightness = brightness
     }
 
+    fn set_gamma(&mut self, gamma: Gamma) {
+        self.gamma = gamma
+    }
+
+    fn set_power_limit(&mut self, limit: PowerLimit) {
+        self.power_limit = limit
+    }
+
     fn sleep_ms(&mut self, duration: u32) {
         self.delay.delay_ms(duration)
     }
@@ -187,19 +231,19 @@ impl led_matrix_core::LedMatrixCore for LedMatrix {
     }
 }
 
-impl core::ops::Index<(usize, usize)> for LedMatrix {
+impl<const W: usize, const H: usize, M> core::ops::Index<(usize, usize)> for LedMatrix<W, H, M> {
     type Output = (u8, u8, u8);
 
     fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
-        assert!((0..HEIGHT as usize).contains(&x));
-        assert!((0..WIDTH as usize).contains(&y));
-        &self.leds[HEIGHT as usize - y - 1][x]
+        assert!((0..W).contains(&x));
+        assert!((0..H).contains(&y));
+        &self.leds[y][x]
     }
 }
-impl core::ops::IndexMut<(usize, usize)> for LedMatrix {
+impl<const W: usize, const H: usize, M> core::ops::IndexMut<(usize, usize)> for LedMatrix<W, H, M> {
     fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
-        assert!((0..HEIGHT as usize).contains(&x));
-        assert!((0..WIDTH as usize).contains(&y));
-        &mut self.leds[HEIGHT as usize - y - 1][x]
+        assert!((0..W).contains(&x));
+        assert!((0..H).contains(&y));
+        &mut self.leds[y][x]
     }
 }
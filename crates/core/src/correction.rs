@@ -0,0 +1,265 @@
+//! Correction of colors on their way to the LEDs.
+//!
+//! WS2812 LEDs are driven with pulse width modulation, so the light they emit
+//! is proportional to the channel values. Our eyes don't perceive light
+//! linearly though: stepping from 1 to 2 is a huge difference, while stepping
+//! from 250 to 251 is invisible. A [Gamma] curve compensates for this by
+//! mapping every channel value through a lookup table (LUT) before it is sent
+//! to the LEDs.
+//!
+//! The brightness is applied after the gamma correction, see
+//! [scale_brightness].
+//!
+//! Computer screens already apply a similar curve (the sRGB transfer
+//! function). The emulators use [Gamma::emulate] to display what the LEDs
+//! would actually emit, so colors and fades look the same in the emulator
+//! and on the real board.
+
+/// A lookup table which maps every channel value to the value that is sent
+/// to the LEDs.
+///
+/// # Examples
+///
+/// ```
+/// use led_matrix_core::correction::Gamma;
+///
+/// assert_eq!(Gamma::LINEAR.correct((10, 128, 255)), (10, 128, 255));
+/// assert_eq!(Gamma::GAMMA_2_2.correct((10, 128, 255)), (0, 56, 255));
+///
+/// // a curve with a custom exponent, computed at runtime
+/// let gamma = Gamma::from_fn(|v| ((v as f32 / 255.0).powf(2.5) * 255.0 + 0.5) as u8);
+/// assert_eq!(gamma.lookup(128), 46);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Gamma {
+    lut: [u8; 256],
+}
+
+impl Gamma {
+    /// No correction at all, every value is sent to the LEDs as it is.
+    pub const LINEAR: Self = {
+        let mut lut = [0; 256];
+        let mut i = 0;
+        while i < 256 {
+            lut[i] = i as u8;
+            i += 1;
+        }
+        Self { lut }
+    };
+
+    /// A gamma of 2.2, the approximate inverse of the sRGB transfer function.
+    /// Colors look about the same on the LEDs as on a computer screen.
+    pub const GAMMA_2_2: Self = Self::from_lut([
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 2, 2, 2, 2, 2,
+        2, 2, 3, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 6, 6, 6, 6, 7, 7, 7, 8, 8, 8, 9, 9, 9, 10, 10,
+        11, 11, 11, 12, 12, 13, 13, 13, 14, 14, 15, 15, 16, 16, 17, 17, 18, 18, 19, 19, 20, 20, 21,
+        22, 22, 23, 23, 24, 25, 25, 26, 26, 27, 28, 28, 29, 30, 30, 31, 32, 33, 33, 34, 35, 35, 36,
+        37, 38, 39, 39, 40, 41, 42, 43, 43, 44, 45, 46, 47, 48, 49, 49, 50, 51, 52, 53, 54, 55, 56,
+        57, 58, 59, 60, 61, 62, 63, 64, 65, 66, 67, 68, 69, 70, 71, 73, 74, 75, 76, 77, 78, 79, 81,
+        82, 83, 84, 85, 87, 88, 89, 90, 91, 93, 94, 95, 97, 98, 99, 100, 102, 103, 105, 106, 107,
+        109, 110, 111, 113, 114, 116, 117, 119, 120, 121, 123, 124, 126, 127, 129, 130, 132, 133,
+        135, 137, 138, 140, 141, 143, 145, 146, 148, 149, 151, 153, 154, 156, 158, 159, 161, 163,
+        165, 166, 168, 170, 172, 173, 175, 177, 179, 181, 182, 184, 186, 188, 190, 192, 194, 196,
+        197, 199, 201, 203, 205, 207, 209, 211, 213, 215, 217, 219, 221, 223, 225, 227, 229, 231,
+        234, 236, 238, 240, 242, 244, 246, 248, 251, 253, 255,
+    ]);
+
+    /// A gamma of 2.8, a popular choice for WS2812 LEDs. It is a little
+    /// stronger than [GAMMA_2_2](Self::GAMMA_2_2), which gives more
+    /// saturated colors.
+    pub const GAMMA_2_8: Self = Self::from_lut([
+        0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1,
+        1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 2, 2, 2, 2, 2, 2, 2, 2, 3, 3, 3, 3, 3, 3, 3, 4, 4, 4, 4,
+        4, 5, 5, 5, 5, 6, 6, 6, 6, 7, 7, 7, 7, 8, 8, 8, 9, 9, 9, 10, 10, 10, 11, 11, 11, 12, 12,
+        13, 13, 13, 14, 14, 15, 15, 16, 16, 17, 17, 18, 18, 19, 19, 20, 20, 21, 21, 22, 22, 23, 24,
+        24, 25, 25, 26, 27, 27, 28, 29, 29, 30, 31, 32, 32, 33, 34, 35, 35, 36, 37, 38, 39, 39, 40,
+        41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 50, 51, 52, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63,
+        64, 66, 67, 68, 69, 70, 72, 73, 74, 75, 77, 78, 79, 81, 82, 83, 85, 86, 87, 89, 90, 92, 93,
+        95, 96, 98, 99, 101, 102, 104, 105, 107, 109, 110, 112, 114, 115, 117, 119, 120, 122, 124,
+        126, 127, 129, 131, 133, 135, 137, 138, 140, 142, 144, 146, 148, 150, 152, 154, 156, 158,
+        160, 162, 164, 167, 169, 171, 173, 175, 177, 180, 182, 184, 186, 189, 191, 193, 196, 198,
+        200, 203, 205, 208, 210, 213, 215, 218, 220, 223, 225, 228, 231, 233, 236, 239, 241, 244,
+        247, 249, 252, 255,
+    ]);
+
+    /// The CIE 1931 lightness curve, which models how bright we perceive a
+    /// light source to be. Use it to get perceptually even fades, see
+    /// [perceptual_brightness].
+    pub const CIE_1931: Self = Self::from_lut([
+        0, 0, 0, 0, 0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 2, 2, 2, 2, 2, 2, 2, 2, 2, 3, 3, 3, 3, 3, 3, 3,
+        3, 4, 4, 4, 4, 4, 4, 5, 5, 5, 5, 5, 6, 6, 6, 6, 6, 7, 7, 7, 7, 8, 8, 8, 8, 9, 9, 9, 10, 10,
+        10, 10, 11, 11, 11, 12, 12, 12, 13, 13, 13, 14, 14, 15, 15, 15, 16, 16, 17, 17, 17, 18, 18,
+        19, 19, 20, 20, 21, 21, 22, 22, 23, 23, 24, 24, 25, 25, 26, 26, 27, 28, 28, 29, 29, 30, 31,
+        31, 32, 32, 33, 34, 34, 35, 36, 37, 37, 38, 39, 39, 40, 41, 42, 43, 43, 44, 45, 46, 47, 47,
+        48, 49, 50, 51, 52, 53, 54, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63, 64, 65, 66, 67, 68, 70,
+        71, 72, 73, 74, 75, 76, 77, 79, 80, 81, 82, 83, 85, 86, 87, 88, 90, 91, 92, 94, 95, 96, 98,
+        99, 100, 102, 103, 105, 106, 108, 109, 110, 112, 113, 115, 116, 118, 120, 121, 123, 124,
+        126, 128, 129, 131, 132, 134, 136, 138, 139, 141, 143, 145, 146, 148, 150, 152, 154, 155,
+        157, 159, 161, 163, 165, 167, 169, 171, 173, 175, 177, 179, 181, 183, 185, 187, 189, 191,
+        193, 196, 198, 200, 202, 204, 207, 209, 211, 214, 216, 218, 220, 223, 225, 228, 230, 232,
+        235, 237, 240, 242, 245, 247, 250, 252, 255,
+    ]);
+
+    pub const fn from_lut(lut: [u8; 256]) -> Self {
+        Self { lut }
+    }
+
+    /// Compute the lookup table by calling `f` with every channel value.
+    pub fn from_fn(mut f: impl FnMut(u8) -> u8) -> Self {
+        Self {
+            lut: core::array::from_fn(|i| f(i as u8)),
+        }
+    }
+
+    /// The value sent to the LEDs for a single channel value.
+    pub const fn lookup(&self, value: u8) -> u8 {
+        self.lut[value as usize]
+    }
+
+    /// Correct all three channels of a color.
+    pub const fn correct(&self, (r, g, b): (u8, u8, u8)) -> (u8, u8, u8) {
+        (self.lookup(r), self.lookup(g), self.lookup(b))
+    }
+
+    /// The color a screen has to display to look like an LED showing `color`
+    /// with this correction and the given brightness applied.
+    pub const fn emulate(&self, color: (u8, u8, u8), brightness: u8) -> (u8, u8, u8) {
+        let (r, g, b) = scale_brightness(self.correct(color), brightness);
+        (SRGB[r as usize], SRGB[g as usize], SRGB[b as usize])
+    }
+}
+
+impl Default for Gamma {
+    fn default() -> Self {
+        Self::LINEAR
+    }
+}
+
+/// Scale a color by the brightness passed to `set_brightness`.
+///
+/// This is exactly what `smart_leds::brightness` does on the hardware, so
+/// the emulators can simulate it.
+pub const fn scale_brightness((r, g, b): (u8, u8, u8), brightness: u8) -> (u8, u8, u8) {
+    const fn scale(value: u8, brightness: u8) -> u8 {
+        (value as u16 * (brightness as u16 + 1) / 256) as u8
+    }
+    (
+        scale(r, brightness),
+        scale(g, brightness),
+        scale(b, brightness),
+    )
+}
+
+/// Map a perceived brightness level to the value to pass to `set_brightness`.
+///
+/// The brightness of the LEDs is proportional to the emitted light, so fading
+/// it linearly looks uneven: most of the visible change happens at low
+/// values. Fading the level passed to this function instead looks smooth.
+pub const fn perceptual_brightness(level: u8) -> u8 {
+    Gamma::CIE_1931.lookup(level)
+}
+
+/// The sRGB transfer function, which encodes linear light for a screen.
+const SRGB: [u8; 256] = [
+    0, 13, 22, 28, 34, 38, 42, 46, 50, 53, 56, 59, 61, 64, 66, 69, 71, 73, 75, 77, 79, 81, 83, 85,
+    86, 88, 90, 92, 93, 95, 96, 98, 99, 101, 102, 104, 105, 106, 108, 109, 110, 112, 113, 114, 115,
+    117, 118, 119, 120, 121, 122, 124, 125, 126, 127, 128, 129, 130, 131, 132, 133, 134, 135, 136,
+    137, 138, 139, 140, 141, 142, 143, 144, 145, 146, 147, 148, 148, 149, 150, 151, 152, 153, 154,
+    155, 155, 156, 157, 158, 159, 159, 160, 161, 162, 163, 163, 164, 165, 166, 167, 167, 168, 169,
+    170, 170, 171, 172, 173, 173, 174, 175, 175, 176, 177, 178, 178, 179, 180, 180, 181, 182, 182,
+    183, 184, 185, 185, 186, 187, 187, 188, 189, 189, 190, 190, 191, 192, 192, 193, 194, 194, 195,
+    196, 196, 197, 197, 198, 199, 199, 200, 200, 201, 202, 202, 203, 203, 204, 205, 205, 206, 206,
+    207, 208, 208, 209, 209, 210, 210, 211, 212, 212, 213, 213, 214, 214, 215, 215, 216, 216, 217,
+    218, 218, 219, 219, 220, 220, 221, 221, 222, 222, 223, 223, 224, 224, 225, 226, 226, 227, 227,
+    228, 228, 229, 229, 230, 230, 231, 231, 232, 232, 233, 233, 234, 234, 235, 235, 236, 236, 237,
+    237, 238, 238, 238, 239, 239, 240, 240, 241, 241, 242, 242, 243, 243, 244, 244, 245, 245, 246,
+    246, 246, 247, 247, 248, 248, 249, 249, 250, 250, 251, 251, 251, 252, 252, 253, 253, 254, 254,
+    255, 255,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CURVES: [Gamma; 4] = [
+        Gamma::LINEAR,
+        Gamma::GAMMA_2_2,
+        Gamma::GAMMA_2_8,
+        Gamma::CIE_1931,
+    ];
+
+    #[test]
+    fn curves_keep_black_and_full_brightness() {
+        for gamma in CURVES {
+            assert_eq!(gamma.lookup(0), 0);
+            assert_eq!(gamma.lookup(255), 255);
+            assert_eq!(gamma.correct((0, 255, 0)), (0, 255, 0));
+        }
+        assert_eq!((SRGB[0], SRGB[255]), (0, 255));
+        assert_eq!(perceptual_brightness(0), 0);
+        assert_eq!(perceptual_brightness(255), 255);
+    }
+
+    #[test]
+    fn curves_are_monotonic() {
+        for gamma in CURVES {
+            assert!(gamma.lut.windows(2).all(|pair| pair[0] <= pair[1]));
+        }
+        assert!(SRGB.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+
+    #[test]
+    fn gamma_darkens_the_midtones() {
+        for gamma in &CURVES[1..] {
+            assert!((0..=255).all(|v| gamma.lookup(v) <= v));
+            assert!(gamma.lookup(128) < 64);
+        }
+        assert!(Gamma::GAMMA_2_8.lookup(128) < Gamma::GAMMA_2_2.lookup(128));
+    }
+
+    #[test]
+    fn from_fn() {
+        assert_eq!(Gamma::from_fn(|v| v), Gamma::LINEAR);
+        let inverted = Gamma::from_fn(|v| 255 - v);
+        assert_eq!(inverted.correct((0, 55, 255)), (255, 200, 0));
+    }
+
+    #[test]
+    fn scale_brightness_keeps_full_brightness() {
+        for v in 0..=255 {
+            assert_eq!(
+                scale_brightness((v, v / 2, 255 - v), 255),
+                (v, v / 2, 255 - v)
+            );
+            assert_eq!(scale_brightness((v, v, v), 0), (0, 0, 0));
+        }
+        assert_eq!(scale_brightness((255, 128, 1), 127), (127, 64, 0));
+        // more brightness is never darker
+        for v in [1, 100, 255] {
+            let scaled: [u8; 256] =
+                core::array::from_fn(|b| scale_brightness((v, 0, 0), b as u8).0);
+            assert!(scaled.windows(2).all(|pair| pair[0] <= pair[1]));
+        }
+    }
+
+    #[test]
+    fn emulate() {
+        assert_eq!(Gamma::LINEAR.emulate((0, 255, 0), 255), (0, 255, 0));
+        assert_eq!(Gamma::GAMMA_2_2.emulate((255, 255, 255), 0), (0, 0, 0));
+        // a screen has to show linear light brighter than its value
+        assert_eq!(Gamma::LINEAR.emulate((128, 0, 0), 255), (SRGB[128], 0, 0));
+        assert!(SRGB[128] > 128);
+        // the gamma correction and the screen's transfer function roughly
+        // cancel out
+        let (r, g, b) = Gamma::GAMMA_2_2.emulate((128, 64, 200), 255);
+        assert!(r.abs_diff(128) <= 3 && g.abs_diff(64) <= 3 && b.abs_diff(200) <= 3);
+        // brightness is applied after the correction
+        assert_eq!(
+            Gamma::GAMMA_2_2.emulate((200, 0, 0), 100),
+            Gamma::LINEAR.emulate(
+                scale_brightness(Gamma::GAMMA_2_2.correct((200, 0, 0)), 100),
+                255
+            )
+        );
+    }
+}
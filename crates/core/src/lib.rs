@@ -1,17 +1,28 @@
 #![no_std]
 
+pub mod correction;
+pub mod mapping;
+pub mod power;
+
 /// A minimal specification of what an LED-matrix must be capable of.
 ///
 /// This trait is non-user facing. All user-facing conveniences should be
 /// defined on `led_matrix::LedMatrix` instead.
 ///
-pub trait LedMatrixCore:
+/// The size of the matrix is specified with the const generic parameters
+/// `W` (width) and `H` (height), which default to the 8x8 LED-matrix.
+///
+pub trait LedMatrixCore<const W: usize = 8, const H: usize = 8>:
     core::ops::Index<(usize, usize), Output = (u8, u8, u8)> + core::ops::IndexMut<(usize, usize)>
 {
     fn apply(&mut self);
 
     fn set_brightness(&mut self, brightness: u8);
 
+    fn set_gamma(&mut self, gamma: correction::Gamma);
+
+    fn set_power_limit(&mut self, limit: power::PowerLimit);
+
     fn sleep_ms(&mut self, duration: u32);
 
     fn get_sin(&self) -> fn(f32) -> f32;
@@ -33,7 +44,96 @@ pub enum JoystickPosition {
     Right,
 }
 
-// Remove these when enabling support for multiple matrices to discover all
-// places where code needs to change.
-pub const HEIGHT: u8 = 8;
-pub const WIDTH: u8 = 8;
+/// The orientation in which a panel is mounted, rotated clockwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Rotation {
+    #[default]
+    Deg0,
+    Deg90,
+    Deg180,
+    Deg270,
+}
+
+impl Rotation {
+    /// The size of the area covered by a panel of the given size when it's
+    /// mounted in this orientation.
+    pub const fn footprint(self, (width, height): (usize, usize)) -> (usize, usize) {
+        match self {
+            Rotation::Deg0 | Rotation::Deg180 => (width, height),
+            Rotation::Deg90 | Rotation::Deg270 => (height, width),
+        }
+    }
+
+    /// Map a position within the area covered by a rotated panel to the
+    /// coordinates of the panel itself.
+    ///
+    /// `size` is the size of the panel, not of the area it covers.
+    pub const fn to_panel(
+        self,
+        (x, y): (usize, usize),
+        (width, height): (usize, usize),
+    ) -> (usize, usize) {
+        match self {
+            Rotation::Deg0 => (x, y),
+            Rotation::Deg90 => (width - y - 1, x),
+            Rotation::Deg180 => (width - x - 1, height - y - 1),
+            Rotation::Deg270 => (y, height - x - 1),
+        }
+    }
+
+    /// The inverse of [to_panel](Self::to_panel), map the coordinates of a
+    /// rotated panel to a position within the area it covers.
+    pub const fn from_panel(
+        self,
+        (x, y): (usize, usize),
+        (width, height): (usize, usize),
+    ) -> (usize, usize) {
+        match self {
+            Rotation::Deg0 => (x, y),
+            Rotation::Deg90 => (y, width - x - 1),
+            Rotation::Deg180 => (width - x - 1, height - y - 1),
+            Rotation::Deg270 => (height - y - 1, x),
+        }
+    }
+}
+
+/// The placement of a panel on a larger canvas made of several panels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Tile {
+    /// Horizontal position of the lower left corner of the panel on the canvas.
+    pub x: usize,
+    /// Vertical position of the lower left corner of the panel on the canvas.
+    pub y: usize,
+    pub rotation: Rotation,
+}
+
+impl Tile {
+    pub const fn new(x: usize, y: usize, rotation: Rotation) -> Self {
+        Self { x, y, rotation }
+    }
+
+    /// Map a position on the canvas to the coordinates of the panel of the
+    /// given size, if the position is covered by this tile.
+    pub const fn to_panel(
+        &self,
+        (x, y): (usize, usize),
+        size: (usize, usize),
+    ) -> Option<(usize, usize)> {
+        let (width, height) = self.rotation.footprint(size);
+        if x < self.x || y < self.y || x >= self.x + width || y >= self.y + height {
+            return None;
+        }
+        Some(self.rotation.to_panel((x - self.x, y - self.y), size))
+    }
+
+    /// The inverse of [to_panel](Self::to_panel), map the coordinates of the
+    /// panel to a position on the canvas.
+    pub const fn from_panel(
+        &self,
+        position: (usize, usize),
+        size: (usize, usize),
+    ) -> (usize, usize) {
+        let (x, y) = self.rotation.from_panel(position, size);
+        (self.x + x, self.y + y)
+    }
+}
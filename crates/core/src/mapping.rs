@@ -0,0 +1,388 @@
+//! Mapping between positions on the matrix and the chain of LEDs.
+//!
+//! WS2812 LEDs are connected in a single chain, so the hardware has to know
+//! which position on the matrix every LED in the chain corresponds to. This
+//! depends on how the panel is soldered and mounted, which is described by a
+//! [Wiring]. Layouts of several chained panels are described by a
+//! [TiledWiring].
+
+use crate::{Rotation, Tile};
+
+/// Maps every LED in the chain to its position on a matrix of size `W` x `H`.
+pub trait Mapping<const W: usize, const H: usize> {
+    /// The position (x, y) of the LED at `index` in the chain.
+    fn position(&self, index: usize) -> (usize, usize);
+}
+
+/// The direction in which consecutive LEDs of the chain are laid out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Order {
+    /// The chain fills the matrix row by row.
+    #[default]
+    RowMajor,
+    /// The chain fills the matrix column by column.
+    ColumnMajor,
+}
+
+/// Whether a panel is mirrored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Mirror {
+    #[default]
+    None,
+    /// Left and right are swapped.
+    Horizontal,
+    /// Top and bottom are swapped.
+    Vertical,
+}
+
+/// Describes how the LEDs of a panel are chained together and how the panel
+/// is mounted.
+///
+/// Without rotation and mirroring, the chain starts in the lower left corner.
+/// For [Order::RowMajor], it fills the bottom row from left to right before
+/// continuing with the row above. For [Order::ColumnMajor], it fills the left
+/// column from bottom to top before continuing with the column to the right.
+///
+/// A position on the matrix is first rotated (clockwise) and then mirrored to
+/// get its position on the physical panel, which is then looked up in the
+/// chain.
+///
+/// # Examples
+///
+/// ```
+/// use led_matrix_core::mapping::Wiring;
+///
+/// // the wiring of the regular LED-matrix
+/// assert_eq!(Wiring::DEFAULT.index((2, 1), (8, 8)), 10);
+///
+/// // every other row runs from right to left
+/// let serpentine = Wiring::DEFAULT.serpentine();
+/// assert_eq!(serpentine.index((7, 1), (8, 8)), 8);
+/// assert_eq!(serpentine.position(8, (8, 8)), (7, 1));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Wiring {
+    pub order: Order,
+    /// Every other row (or column) of the chain runs in the opposite
+    /// direction.
+    pub serpentine: bool,
+    pub rotation: Rotation,
+    pub mirror: Mirror,
+}
+
+impl Wiring {
+    /// The wiring of the regular LED-matrix: row-major, starting in the
+    /// lower left corner.
+    pub const DEFAULT: Self = Self {
+        order: Order::RowMajor,
+        serpentine: false,
+        rotation: Rotation::Deg0,
+        mirror: Mirror::None,
+    };
+
+    /// Chain the LEDs column by column instead of row by row.
+    pub const fn column_major(self) -> Self {
+        Self {
+            order: Order::ColumnMajor,
+            ..self
+        }
+    }
+
+    /// Alternate the direction of every other row (or column).
+    pub const fn serpentine(self) -> Self {
+        Self {
+            serpentine: true,
+            ..self
+        }
+    }
+
+    /// Mount the panel rotated clockwise.
+    pub const fn rotated(self, rotation: Rotation) -> Self {
+        Self { rotation, ..self }
+    }
+
+    /// Mount the panel mirrored.
+    pub const fn mirrored(self, mirror: Mirror) -> Self {
+        Self { mirror, ..self }
+    }
+
+    /// The index in the chain of the LED at `position` on a matrix of the
+    /// given size.
+    pub const fn index(&self, position: (usize, usize), size: (usize, usize)) -> usize {
+        let panel_size = self.rotation.footprint(size);
+        let (x, y) = self.apply_mirror(self.rotation.to_panel(position, panel_size), panel_size);
+        let (line, along, line_length) = match self.order {
+            Order::RowMajor => (y, x, panel_size.0),
+            Order::ColumnMajor => (x, y, panel_size.1),
+        };
+        line * line_length + self.apply_serpentine(line, along, line_length)
+    }
+
+    /// The position of the LED at `index` in the chain on a matrix of the
+    /// given size. This is the inverse of [index](Self::index).
+    pub const fn position(&self, index: usize, size: (usize, usize)) -> (usize, usize) {
+        let panel_size = self.rotation.footprint(size);
+        let line_length = match self.order {
+            Order::RowMajor => panel_size.0,
+            Order::ColumnMajor => panel_size.1,
+        };
+        let line = index / line_length;
+        let along = self.apply_serpentine(line, index % line_length, line_length);
+        let position = match self.order {
+            Order::RowMajor => (along, line),
+            Order::ColumnMajor => (line, along),
+        };
+        self.rotation
+            .from_panel(self.apply_mirror(position, panel_size), panel_size)
+    }
+
+    /// Mirroring is its own inverse, so this works in both directions.
+    const fn apply_mirror(
+        &self,
+        (x, y): (usize, usize),
+        (width, height): (usize, usize),
+    ) -> (usize, usize) {
+        match self.mirror {
+            Mirror::None => (x, y),
+            Mirror::Horizontal => (width - x - 1, y),
+            Mirror::Vertical => (x, height - y - 1),
+        }
+    }
+
+    /// Reversing serpentine lines is its own inverse as well.
+    const fn apply_serpentine(&self, line: usize, along: usize, line_length: usize) -> usize {
+        if self.serpentine && line % 2 == 1 {
+            line_length - along - 1
+        } else {
+            along
+        }
+    }
+}
+
+impl<const W: usize, const H: usize> Mapping<W, H> for Wiring {
+    fn position(&self, index: usize) -> (usize, usize) {
+        self.position(index, (W, H))
+    }
+}
+
+/// Describes a chain of `N` panels of size `PW` x `PH`, which together cover
+/// a larger canvas.
+///
+/// The chain runs through all LEDs of the first panel before continuing with
+/// the next one. Every panel is wired the same way and placed on the canvas
+/// according to its tile.
+///
+/// The tiles must cover the canvas exactly, use [new](Self::new) to check
+/// this when the layout is created.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TiledWiring<const N: usize, const PW: usize = 8, const PH: usize = 8> {
+    pub tiles: [Tile; N],
+    pub panel: Wiring,
+}
+
+impl<const N: usize, const PW: usize, const PH: usize> TiledWiring<N, PW, PH> {
+    /// Describe a chain of panels covering a canvas of size `W` x `H`.
+    ///
+    /// Panics unless every tile is entirely on the canvas and the tiles cover
+    /// it exactly, without overlapping.
+    pub const fn new<const W: usize, const H: usize>(tiles: [Tile; N], panel: Wiring) -> Self {
+        assert!(N > 0, "a tiled wiring needs at least one panel");
+        assert!(
+            N * PW * PH == W * H,
+            "the panels have a different number of LEDs than the canvas"
+        );
+        let mut i = 0;
+        while i < N {
+            let (width, height) = tiles[i].rotation.footprint((PW, PH));
+            assert!(
+                tiles[i].x + width <= W && tiles[i].y + height <= H,
+                "tile is outside the canvas"
+            );
+            let mut j = 0;
+            while j < i {
+                let (other_width, other_height) = tiles[j].rotation.footprint((PW, PH));
+                assert!(
+                    tiles[i].x >= tiles[j].x + other_width
+                        || tiles[j].x >= tiles[i].x + width
+                        || tiles[i].y >= tiles[j].y + other_height
+                        || tiles[j].y >= tiles[i].y + height,
+                    "tiles overlap"
+                );
+                j += 1;
+            }
+            i += 1;
+        }
+        Self { tiles, panel }
+    }
+}
+
+impl<const W: usize, const H: usize, const N: usize, const PW: usize, const PH: usize> Mapping<W, H>
+    for TiledWiring<N, PW, PH>
+{
+    fn position(&self, index: usize) -> (usize, usize) {
+        let tile = &self.tiles[index / (PW * PH)];
+        let position = self.panel.position(index % (PW * PH), (PW, PH));
+        tile.from_panel(position, (PW, PH))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ROTATIONS: [Rotation; 4] = [
+        Rotation::Deg0,
+        Rotation::Deg90,
+        Rotation::Deg180,
+        Rotation::Deg270,
+    ];
+
+    fn wirings() -> impl Iterator<Item = Wiring> {
+        [Order::RowMajor, Order::ColumnMajor]
+            .into_iter()
+            .flat_map(|order| [false, true].map(|serpentine| (order, serpentine)))
+            .flat_map(|(order, serpentine)| ROTATIONS.map(|rotation| (order, serpentine, rotation)))
+            .flat_map(|(order, serpentine, rotation)| {
+                [Mirror::None, Mirror::Horizontal, Mirror::Vertical].map(|mirror| Wiring {
+                    order,
+                    serpentine,
+                    rotation,
+                    mirror,
+                })
+            })
+    }
+
+    /// Assert that `mapping` visits every position of a `W` x `H` matrix
+    /// exactly once.
+    fn assert_covers<const W: usize, const H: usize>(mapping: &impl Mapping<W, H>) {
+        let mut seen = [[false; W]; H];
+        for i in 0..W * H {
+            let (x, y) = mapping.position(i);
+            assert!(x < W && y < H, "LED {i} is outside the matrix: {x}, {y}");
+            assert!(!seen[y][x], "{x}, {y} is mapped twice");
+            seen[y][x] = true;
+        }
+    }
+
+    #[test]
+    fn index_and_position_are_inverse() {
+        for wiring in wirings() {
+            // square and non-square, with odd and even numbers of lines
+            for size in [(8, 8), (5, 3), (2, 7), (1, 4)] {
+                for i in 0..size.0 * size.1 {
+                    let position = wiring.position(i, size);
+                    assert!(position.0 < size.0 && position.1 < size.1);
+                    assert_eq!(wiring.index(position, size), i, "{wiring:?}, {size:?}");
+                }
+            }
+            assert_covers::<5, 3>(&wiring);
+        }
+    }
+
+    #[test]
+    fn wirings_start_in_the_expected_corner() {
+        let size = (4, 3);
+        assert_eq!(Wiring::DEFAULT.position(0, size), (0, 0));
+        assert_eq!(Wiring::DEFAULT.position(4, size), (0, 1));
+        assert_eq!(Wiring::DEFAULT.column_major().position(3, size), (1, 0));
+        assert_eq!(Wiring::DEFAULT.serpentine().position(4, size), (3, 1));
+        assert_eq!(
+            Wiring::DEFAULT
+                .column_major()
+                .serpentine()
+                .position(3, size),
+            (1, 2)
+        );
+        assert_eq!(
+            Wiring::DEFAULT
+                .mirrored(Mirror::Horizontal)
+                .position(0, size),
+            (3, 0)
+        );
+        assert_eq!(
+            Wiring::DEFAULT.mirrored(Mirror::Vertical).position(0, size),
+            (0, 2)
+        );
+        // the chain starts in the upper left corner of the matrix and runs
+        // down, its panel is 4 wide and 3 high
+        let rotated = Wiring::DEFAULT.rotated(Rotation::Deg90);
+        assert_eq!(rotated.position(0, (3, 4)), (0, 3));
+        assert_eq!(rotated.position(1, (3, 4)), (0, 2));
+        assert_eq!(rotated.position(4, (3, 4)), (1, 3));
+    }
+
+    #[test]
+    fn tiled_wiring_covers_the_canvas() {
+        for wiring in wirings() {
+            let tiles = [
+                Tile::new(0, 0, Rotation::Deg0),
+                Tile::new(3, 0, Rotation::Deg0),
+                Tile::new(3, 2, Rotation::Deg180),
+                Tile::new(0, 2, Rotation::Deg180),
+            ];
+            assert_covers::<6, 4>(&TiledWiring::<4, 3, 2>::new::<6, 4>(tiles, wiring));
+
+            let tiles = [
+                Tile::new(0, 0, Rotation::Deg90),
+                Tile::new(2, 0, Rotation::Deg270),
+                Tile::new(0, 3, Rotation::Deg90),
+                Tile::new(2, 3, Rotation::Deg270),
+            ];
+            assert_covers::<4, 6>(&TiledWiring::<4, 3, 2>::new::<4, 6>(tiles, wiring));
+        }
+    }
+
+    #[test]
+    fn tiled_wiring_runs_through_one_panel_after_the_other() {
+        let tiles = [
+            Tile::new(2, 0, Rotation::Deg0),
+            Tile::new(0, 0, Rotation::Deg180),
+        ];
+        let wiring = TiledWiring::<2, 2, 2>::new::<4, 2>(tiles, Wiring::DEFAULT);
+        let positions: [(usize, usize); 8] =
+            core::array::from_fn(|i| Mapping::<4, 2>::position(&wiring, i));
+        assert_eq!(
+            positions,
+            [
+                (2, 0),
+                (3, 0),
+                (2, 1),
+                (3, 1),
+                (1, 1),
+                (0, 1),
+                (1, 0),
+                (0, 0)
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "tile is outside the canvas")]
+    fn tiled_wiring_rejects_tiles_outside_the_canvas() {
+        let tiles = [
+            Tile::new(0, 0, Rotation::Deg0),
+            Tile::new(0, 8, Rotation::Deg0),
+        ];
+        TiledWiring::<2>::new::<16, 8>(tiles, Wiring::DEFAULT);
+    }
+
+    #[test]
+    #[should_panic(expected = "tiles overlap")]
+    fn tiled_wiring_rejects_overlapping_tiles() {
+        let tiles = [
+            Tile::new(0, 0, Rotation::Deg0),
+            Tile::new(4, 0, Rotation::Deg90),
+        ];
+        TiledWiring::<2>::new::<16, 8>(tiles, Wiring::DEFAULT);
+    }
+
+    #[test]
+    #[should_panic(expected = "different number of LEDs")]
+    fn tiled_wiring_rejects_uncovered_canvas() {
+        let tiles = [
+            Tile::new(0, 0, Rotation::Deg0),
+            Tile::new(8, 0, Rotation::Deg0),
+        ];
+        TiledWiring::<2>::new::<16, 16>(tiles, Wiring::DEFAULT);
+    }
+}
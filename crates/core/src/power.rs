@@ -0,0 +1,231 @@
+//! Estimation and limitation of the current drawn by the LEDs.
+//!
+//! Every WS2812 LED contains three small LEDs (red, green and blue), each of
+//! which draws up to about 20mA. A single white LED therefore draws about
+//! 60mA and a full white 8x8 matrix almost 4A, much more than a USB port can
+//! supply. A dim blue frame on the other hand draws very little.
+//!
+//! Instead of capping the brightness for every frame, a [PowerLimit]
+//! estimates the current of each frame and reduces the brightness just
+//! enough to stay within the budget.
+
+/// A budget for the current drawn by the LEDs, together with a simple model
+/// of how much current they draw.
+///
+/// # Examples
+///
+/// ```
+/// use led_matrix_core::power::PowerLimit;
+///
+/// let limit = PowerLimit::USB;
+///
+/// // a few dim LEDs stay within the budget
+/// let dim = [(0, 0, 40); 64];
+/// assert_eq!(limit.limit_brightness(dim, 255), 255);
+///
+/// // full white is scaled down until it fits
+/// let white = [(255, 255, 255); 64];
+/// let brightness = limit.limit_brightness(white, 255);
+/// assert_eq!(brightness, 28);
+/// assert!(limit.estimate_ma(white, brightness) <= 500);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PowerLimit {
+    /// The maximum current all LEDs together may draw, in milliamps.
+    pub budget_ma: u32,
+    /// The current drawn by a single color channel at full brightness, in
+    /// milliamps.
+    pub channel_ma: u32,
+    /// The current drawn by a single LED which is turned off, in milliamps.
+    pub idle_ma: u32,
+}
+
+impl PowerLimit {
+    /// The 500mA a USB 2.0 port is guaranteed to supply. This is the
+    /// default.
+    pub const USB: Self = Self::new(500);
+
+    /// Never reduce the brightness. Only use this with a power supply that
+    /// can handle all LEDs at full white.
+    pub const UNLIMITED: Self = Self::new(u32::MAX);
+
+    /// A budget of `budget_ma` milliamps for WS2812 LEDs.
+    pub const fn new(budget_ma: u32) -> Self {
+        Self {
+            budget_ma,
+            channel_ma: 20,
+            idle_ma: 1,
+        }
+    }
+
+    /// Estimate the current drawn by the LEDs showing the given colors at
+    /// the given brightness, in milliamps.
+    ///
+    /// The brightness is applied the same way as on the hardware.
+    pub fn estimate_ma(&self, leds: impl IntoIterator<Item = (u8, u8, u8)>, brightness: u8) -> u32 {
+        let (count, channels) = Self::sum(leds);
+        let variable =
+            channels * u64::from(self.channel_ma) * (u64::from(brightness) + 1) / (255 * 256);
+        let total = count * u64::from(self.idle_ma) + variable;
+        total.try_into().unwrap_or(u32::MAX)
+    }
+
+    /// The highest brightness up to `brightness` at which the LEDs showing
+    /// the given colors stay within the budget.
+    pub fn limit_brightness(
+        &self,
+        leds: impl IntoIterator<Item = (u8, u8, u8)>,
+        brightness: u8,
+    ) -> u8 {
+        let (count, channels) = Self::sum(leds);
+        let Some(available) =
+            u64::from(self.budget_ma).checked_sub(count * u64::from(self.idle_ma))
+        else {
+            return 0;
+        };
+        let full = channels * u64::from(self.channel_ma);
+        if full == 0 {
+            return brightness;
+        }
+        // solve estimate_ma for the brightness
+        let limit = (available * 255 * 256 / full).saturating_sub(1);
+        limit.min(brightness.into()) as u8
+    }
+
+    /// The number of LEDs and the sum of all their channels.
+    fn sum(leds: impl IntoIterator<Item = (u8, u8, u8)>) -> (u64, u64) {
+        leds.into_iter().fold((0, 0), |(count, sum), (r, g, b)| {
+            (count + 1, sum + u64::from(r) + u64::from(g) + u64::from(b))
+        })
+    }
+}
+
+impl Default for PowerLimit {
+    fn default() -> Self {
+        Self::USB
+    }
+}
+
+/// The text of the brightness overlay of the emulators: the brightness as
+/// set by the program and, if it's lower, the brightness after applying the
+/// power limit.
+///
+/// ```
+/// use led_matrix_core::power::BrightnessOverlay;
+///
+/// let overlay = BrightnessOverlay { brightness: 255, limited: 28 };
+/// assert_eq!(
+///     overlay.to_string(),
+///     "brightness: 255 (100%), limited to 28 (10%)"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BrightnessOverlay {
+    pub brightness: u8,
+    pub limited: u8,
+}
+
+impl core::fmt::Display for BrightnessOverlay {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let percent = |brightness: u8| u32::from(brightness) * 100 / 255;
+        let brightness = self.brightness;
+        write!(f, "brightness: {brightness} ({}%)", percent(brightness))?;
+        if self.limited < brightness {
+            let limited = self.limited;
+            write!(f, ", limited to {limited} ({}%)", percent(limited))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use std::string::ToString;
+
+    const WHITE: [(u8, u8, u8); 64] = [(255, 255, 255); 64];
+
+    #[test]
+    fn estimate() {
+        let limit = PowerLimit::USB;
+        // every LED draws its idle current, even when it's off
+        assert_eq!(limit.estimate_ma([(0, 0, 0); 64], 255), 64);
+        assert_eq!(limit.estimate_ma([(255, 0, 0)], 255), 21);
+        assert_eq!(limit.estimate_ma(WHITE, 255), 64 + 64 * 60);
+        assert_eq!(limit.estimate_ma(WHITE, 0), 64 + 15);
+        assert_eq!(limit.estimate_ma([], 255), 0);
+    }
+
+    #[test]
+    fn below_the_budget() {
+        // 64 + 64 * 3 * 20 * 20 / 255 = 365mA
+        let dim = [(20, 20, 20); 64];
+        assert_eq!(PowerLimit::USB.estimate_ma(dim, 255), 365);
+        assert_eq!(PowerLimit::USB.limit_brightness(dim, 255), 255);
+        assert_eq!(PowerLimit::USB.limit_brightness(dim, 100), 100);
+        // nothing to limit
+        assert_eq!(PowerLimit::USB.limit_brightness([(0, 0, 0); 64], 200), 200);
+    }
+
+    #[test]
+    fn at_the_budget() {
+        let limit = PowerLimit::new(64 + 64 * 60);
+        assert_eq!(limit.estimate_ma(WHITE, 255), limit.budget_ma);
+        assert_eq!(limit.limit_brightness(WHITE, 255), 255);
+        // a single milliamp less is too little
+        let limit = PowerLimit::new(64 + 64 * 60 - 1);
+        assert_eq!(limit.limit_brightness(WHITE, 255), 254);
+    }
+
+    #[test]
+    fn above_the_budget() {
+        let limit = PowerLimit::USB;
+        let brightness = limit.limit_brightness(WHITE, 255);
+        assert_eq!(brightness, 28);
+        // the highest brightness that fits
+        assert!(limit.estimate_ma(WHITE, brightness) <= 500);
+        assert!(limit.estimate_ma(WHITE, brightness + 1) > 500);
+        // a lower brightness is kept
+        assert_eq!(limit.limit_brightness(WHITE, 20), 20);
+    }
+
+    #[test]
+    fn budget_below_the_idle_draw() {
+        // 64 LEDs draw 64mA while they are off
+        let limit = PowerLimit::new(63);
+        assert_eq!(limit.limit_brightness(WHITE, 255), 0);
+        assert_eq!(limit.limit_brightness([(0, 0, 0); 64], 255), 0);
+        // exactly the idle draw leaves nothing for the colors
+        let limit = PowerLimit::new(64);
+        assert_eq!(limit.limit_brightness(WHITE, 255), 0);
+        assert_eq!(limit.limit_brightness([(0, 0, 0); 64], 255), 255);
+    }
+
+    #[test]
+    fn unlimited() {
+        let leds = [(255, 255, 255); 10_000];
+        assert_eq!(PowerLimit::UNLIMITED.limit_brightness(leds, 255), 255);
+    }
+
+    #[test]
+    fn brightness_overlay() {
+        let overlay = |brightness, limited| {
+            BrightnessOverlay {
+                brightness,
+                limited,
+            }
+            .to_string()
+        };
+        assert_eq!(overlay(255, 255), "brightness: 255 (100%)");
+        assert_eq!(overlay(128, 128), "brightness: 128 (50%)");
+        // only a lower brightness is shown as limited
+        assert_eq!(overlay(100, 200), "brightness: 100 (39%)");
+        assert_eq!(
+            overlay(200, 23),
+            "brightness: 200 (78%), limited to 23 (9%)"
+        );
+        assert_eq!(overlay(1, 0), "brightness: 1 (0%), limited to 0 (0%)");
+    }
+}
@@ -1,13 +1,23 @@
 use std::sync::mpsc::{Receiver, Sender};
 
-use eframe::egui::{self, Color32, Key, Pos2, Rect, Rounding, Shape};
-use led_matrix_core::{HEIGHT, WIDTH};
+use eframe::egui::{self, vec2, Align2, Color32, FontId, Key, Pos2, Rect, Rounding, Shape, Stroke};
+use led_matrix_core::{power::BrightnessOverlay, Tile};
 
-use crate::{Event, EventKey, EventKind, LedGrid};
+use crate::{Event, EventKey, EventKind, Frame, LedGrid};
 
-pub fn run(sender: Sender<Event>, receiver: Receiver<LedGrid>) {
+pub fn run<const W: usize, const H: usize>(
+    tiles: Vec<Tile>,
+    sender: Sender<Event>,
+    receiver: Receiver<Frame<W, H>>,
+) {
     let app = LedMatrixApp {
-        leds: Default::default(),
+        panels: vec![[[(0, 0, 0); W]; H]; tiles.len()],
+        brightness: BrightnessOverlay {
+            brightness: 255,
+            limited: 255,
+        },
+        show_brightness: false,
+        tiles,
         sender,
         receiver,
     };
@@ -20,13 +30,18 @@ pub fn run(sender: Sender<Event>, receiver: Receiver<LedGrid>) {
     .unwrap();
 }
 
-struct LedMatrixApp {
-    leds: LedGrid,
+struct LedMatrixApp<const W: usize, const H: usize> {
+    panels: Vec<LedGrid<W, H>>,
+    // brightness of the most recently applied panel, as set by the program
+    // and after applying the power limit
+    brightness: BrightnessOverlay,
+    show_brightness: bool,
+    tiles: Vec<Tile>,
     sender: Sender<Event>,
-    receiver: Receiver<LedGrid>,
+    receiver: Receiver<Frame<W, H>>,
 }
 
-impl eframe::App for LedMatrixApp {
+impl<const W: usize, const H: usize> eframe::App for LedMatrixApp<W, H> {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         ctx.request_repaint();
 
@@ -46,6 +61,10 @@ impl eframe::App for LedMatrixApp {
                     EventKey::S
                 } else if ctx.input(|i| i.key_pressed(Key::Q)) {
                     std::process::exit(0)
+                } else if ctx.input(|i| i.key_pressed(Key::B)) {
+                    // handled by the GUI, the program doesn't need to know
+                    self.show_brightness = !self.show_brightness;
+                    break 'rising_edge;
                 } else {
                     break 'rising_edge;
                 };
@@ -76,30 +95,61 @@ impl eframe::App for LedMatrixApp {
                 self.sender.send(event).unwrap();
             }
 
-            // drain queue to get the most recent frame
-            while let Ok(new_leds) = self.receiver.try_recv() {
-                self.leds = new_leds;
+            // drain queue to get the most recent frame of every panel
+            while let Ok(frame) = self.receiver.try_recv() {
+                self.panels[frame.panel] = frame.leds;
+                self.brightness = BrightnessOverlay {
+                    brightness: frame.brightness,
+                    limited: frame.limited_brightness,
+                };
             }
 
             ui.heading("control with arrow keys (joystick), space (joystick press) and enter (switch). Quit with Q.");
+            ui.label("Toggle the brightness overlay with B.");
 
             let painter = ui.painter();
             let padding_top = 80.0;
             let r = painter.clip_rect().with_min_y(padding_top);
-            let led_size = r.width().min(r.height()) / 8.0;
-            for x in 0..WIDTH as usize {
-                for y in 0..HEIGHT as usize {
-                    let (r, g, b) = self.leds[y][x];
-                    let color = Color32::from_rgb(r, g, b);
-                    let x = x as f32 * led_size;
-                    let y = y as f32 * led_size + padding_top;
-                    painter.add(Shape::rect_filled(
-                        Rect::from_two_pos(Pos2::new(x, y), Pos2::new(x + led_size, y + led_size)),
-                        Rounding::ZERO,
-                        color,
-                    ));
+
+            // size of the canvas covered by all panels together
+            let (width, height) = self.tiles.iter().fold((0, 0), |(width, height), tile| {
+                let (w, h) = tile.rotation.footprint((W, H));
+                (width.max(tile.x + w), height.max(tile.y + h))
+            });
+            let led_size = (r.width() / width as f32).min(r.height() / height as f32);
+            let led_rect = |x: usize, y: usize| {
+                // the canvas has its origin in the lower left corner
+                let x = x as f32 * led_size;
+                let y = (height - y - 1) as f32 * led_size + padding_top;
+                Rect::from_two_pos(Pos2::new(x, y), Pos2::new(x + led_size, y + led_size))
+            };
+
+            for (tile, leds) in self.tiles.iter().zip(&self.panels) {
+                let (w, h) = tile.rotation.footprint((W, H));
+                for x in tile.x..tile.x + w {
+                    for y in tile.y..tile.y + h {
+                        let (px, py) = tile.to_panel((x, y), (W, H)).unwrap();
+                        let (r, g, b) = leds[H - py - 1][px];
+                        let color = Color32::from_rgb(r, g, b);
+                        painter.add(Shape::rect_filled(led_rect(x, y), Rounding::ZERO, color));
+                    }
+                }
+                if self.tiles.len() > 1 {
+                    // outline the panels to make the layout visible
+                    let outline = led_rect(tile.x, tile.y + h - 1).union(led_rect(tile.x + w - 1, tile.y));
+                    painter.rect_stroke(outline, Rounding::ZERO, Stroke::new(1.0, Color32::DARK_GRAY));
                 }
             }
+
+            if self.show_brightness {
+                let text = self.brightness.to_string();
+                let galley = painter.layout_no_wrap(text, FontId::proportional(16.0), Color32::WHITE);
+                // in the upper right corner of the canvas
+                let corner = led_rect(width - 1, height - 1).right_top() + vec2(-8.0, 8.0);
+                let rect = Align2::RIGHT_TOP.anchor_size(corner, galley.size());
+                painter.rect_filled(rect.expand(4.0), Rounding::same(4.0), Color32::from_black_alpha(180));
+                painter.galley(rect.min, galley, Color32::WHITE);
+            }
         });
     }
 }
@@ -1,46 +1,83 @@
 use std::sync::mpsc::{self, Receiver, Sender};
 
-use led_matrix_core::{JoystickPosition, HEIGHT, WIDTH};
+use led_matrix_core::{correction::Gamma, power::PowerLimit, JoystickPosition, Tile};
 use serde::{Deserialize, Serialize};
 
 mod gui;
 
-pub type LedGrid = [[(u8, u8, u8); WIDTH as usize]; HEIGHT as usize];
+pub type LedGrid<const W: usize = 8, const H: usize = 8> = [[(u8, u8, u8); W]; H];
 
-pub struct LedMatrix {
-    sender: Sender<LedGrid>,
+/// What's sent to the GUI when a panel is applied. The LEDs are already
+/// corrected and scaled to look like the real LED-matrix.
+struct Frame<const W: usize, const H: usize> {
+    panel: usize,
+    brightness: u8,
+    // the brightness after applying the power limit
+    limited_brightness: u8,
+    leds: LedGrid<W, H>,
+}
+
+pub struct LedMatrix<const W: usize = 8, const H: usize = 8> {
+    panel: usize,
+    sender: Sender<Frame<W, H>>,
     receiver: Receiver<Event>,
 
     joystick_position: JoystickPosition,
     joystick_pressed: bool,
     switch: bool,
 
-    leds: [[(u8, u8, u8); WIDTH as usize]; HEIGHT as usize],
+    // simulated when the LEDs are sent to the GUI
+    brightness: u8,
+    gamma: Gamma,
+    power_limit: PowerLimit,
+
+    leds: LedGrid<W, H>,
+}
+
+pub fn run<const W: usize, const H: usize, F: FnOnce(LedMatrix<W, H>) + Send + 'static>(f: F) -> ! {
+    run_tiled([Tile::default()], |[matrix]| f(matrix))
 }
 
-pub fn run<F: FnOnce(LedMatrix) + Send + 'static>(f: F) -> ! {
+/// Emulate several panels, which are displayed together according to the
+/// given tiles.
+///
+/// Only the first panel receives input events.
+pub fn run_tiled<
+    const W: usize,
+    const H: usize,
+    const N: usize,
+    F: FnOnce([LedMatrix<W, H>; N]) + Send + 'static,
+>(
+    tiles: [Tile; N],
+    f: F,
+) -> ! {
     let (event_sender, event_receiver) = mpsc::channel();
     let (led_grid_sender, led_grid_receiver) = mpsc::channel();
 
-    let matrix = LedMatrix {
-        sender: led_grid_sender,
-        receiver: event_receiver,
+    let mut event_receiver = Some(event_receiver);
+    let matrices = core::array::from_fn(|panel| LedMatrix {
+        panel,
+        sender: led_grid_sender.clone(),
+        receiver: event_receiver.take().unwrap_or_else(|| mpsc::channel().1),
         joystick_position: Default::default(),
         joystick_pressed: false,
         switch: Default::default(),
-        leds: Default::default(),
-    };
+        brightness: 255,
+        gamma: Gamma::LINEAR,
+        power_limit: PowerLimit::USB,
+        leds: [[(0, 0, 0); W]; H],
+    });
 
-    std::thread::spawn(move || f(matrix));
+    std::thread::spawn(move || f(matrices));
 
-    gui::run(event_sender, led_grid_receiver);
+    gui::run(tiles.to_vec(), event_sender, led_grid_receiver);
 
     // necessary to make the run function non-terminating
     #[allow(clippy::empty_loop)]
     loop {}
 }
 
-impl LedMatrix {
+impl<const W: usize, const H: usize> LedMatrix<W, H> {
     fn poll_event(&mut self) {
         while let Ok(event) = self.receiver.try_recv() {
             use EventKey as K;
@@ -63,12 +100,37 @@ impl LedMatrix {
     }
 }
 
-impl led_matrix_core::LedMatrixCore for LedMatrix {
+impl<const W: usize, const H: usize> led_matrix_core::LedMatrixCore<W, H> for LedMatrix<W, H> {
     fn apply(&mut self) {
-        self.sender.send(self.leds).unwrap();
+        let limited_brightness = self.power_limit.limit_brightness(
+            self.leds
+                .iter()
+                .flatten()
+                .map(|&led| self.gamma.correct(led)),
+            self.brightness,
+        );
+        let frame = Frame {
+            panel: self.panel,
+            brightness: self.brightness,
+            limited_brightness,
+            leds: self
+                .leds
+                .map(|row| row.map(|led| self.gamma.emulate(led, limited_brightness))),
+        };
+        self.sender.send(frame).unwrap();
     }
 
-    fn set_brightness(&mut self, _brightness: u8) {}
+    fn set_brightness(&mut self, brightness: u8) {
+        self.brightness = brightness
+    }
+
+    fn set_gamma(&mut self, gamma: Gamma) {
+        self.gamma = gamma
+    }
+
+    fn set_power_limit(&mut self, limit: PowerLimit) {
+        self.power_limit = limit
+    }
 
     fn sleep_ms(&mut self, duration: u32) {
         self.poll_event();
@@ -94,20 +156,20 @@ impl led_matrix_core::LedMatrixCore for LedMatrix {
     }
 }
 
-impl core::ops::Index<(usize, usize)> for LedMatrix {
+impl<const W: usize, const H: usize> core::ops::Index<(usize, usize)> for LedMatrix<W, H> {
     type Output = (u8, u8, u8);
 
     fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
-        assert!((0..HEIGHT as usize).contains(&x));
-        assert!((0..WIDTH as usize).contains(&y));
-        &self.leds[HEIGHT as usize - y - 1][x]
+        assert!((0..W).contains(&x));
+        assert!((0..H).contains(&y));
+        &self.leds[H - y - 1][x]
     }
 }
-impl core::ops::IndexMut<(usize, usize)> for LedMatrix {
+impl<const W: usize, const H: usize> core::ops::IndexMut<(usize, usize)> for LedMatrix<W, H> {
     fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
-        assert!((0..HEIGHT as usize).contains(&x));
-        assert!((0..WIDTH as usize).contains(&y));
-        &mut self.leds[HEIGHT as usize - y - 1][x]
+        assert!((0..W).contains(&x));
+        assert!((0..H).contains(&y));
+        &mut self.leds[H - y - 1][x]
     }
 }
 
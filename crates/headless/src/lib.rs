@@ -0,0 +1,425 @@
+//! A headless, in-memory LED-matrix for automated tests.
+//!
+//! Nothing is rendered anywhere. Instead, every frame passed to `apply` is
+//! recorded, time only advances when the program calls `sleep_ms` and inputs
+//! are played back from a script. This makes it possible to run application
+//! code in `cargo test` or CI and inspect what it drew afterwards.
+//!
+//! Frames can be compared against checked-in snapshot files with
+//! [assert_frame_eq], see the module [snapshot] for details.
+
+use std::{
+    collections::VecDeque,
+    panic::{self, AssertUnwindSafe},
+};
+
+use led_matrix_core::{correction::Gamma, power::PowerLimit, JoystickPosition};
+
+pub mod snapshot;
+
+pub type LedGrid<const W: usize = 8, const H: usize = 8> = [[(u8, u8, u8); W]; H];
+
+/// How long (in virtual milliseconds) [run] lets a program execute before
+/// exiting, unless overridden with the environment variable of this name.
+pub const TIME_LIMIT_VAR: &str = "LED_MATRIX_HEADLESS_TIME_LIMIT";
+
+const DEFAULT_TIME_LIMIT: u64 = 60_000;
+
+/// An input event that can be scheduled with [LedMatrix::schedule].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Input {
+    Joystick(JoystickPosition),
+    JoystickPressed(bool),
+    Switch(bool),
+}
+
+/// A frame that was passed to `apply`, together with the virtual time at
+/// which that happened.
+///
+/// Index a frame with a tuple (x, y), just like the LED-matrix itself.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Frame<const W: usize = 8, const H: usize = 8> {
+    pub time_ms: u64,
+    pub brightness: u8,
+    pub leds: LedGrid<W, H>,
+}
+
+impl<const W: usize, const H: usize> AsRef<LedGrid<W, H>> for Frame<W, H> {
+    fn as_ref(&self) -> &LedGrid<W, H> {
+        &self.leds
+    }
+}
+
+impl<const W: usize, const H: usize> core::ops::Index<(usize, usize)> for Frame<W, H> {
+    type Output = (u8, u8, u8);
+
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        assert!((0..W).contains(&x));
+        assert!((0..H).contains(&y));
+        &self.leds[H - y - 1][x]
+    }
+}
+
+pub struct LedMatrix<const W: usize = 8, const H: usize = 8> {
+    now_ms: u64,
+    time_limit_ms: Option<u64>,
+    script: VecDeque<(u64, Input)>,
+    frames: Vec<Frame<W, H>>,
+
+    joystick_position: JoystickPosition,
+    joystick_pressed: bool,
+    switch: bool,
+
+    brightness: u8,
+    gamma: Gamma,
+    power_limit: PowerLimit,
+
+    leds: LedGrid<W, H>,
+}
+
+/// Runs the program without any display until it returns or the virtual
+/// clock reaches the time limit (see [TIME_LIMIT_VAR]), then exits the
+/// process.
+pub fn run<const W: usize, const H: usize, F: FnOnce(LedMatrix<W, H>) + Send + 'static>(f: F) -> ! {
+    let time_limit = std::env::var(TIME_LIMIT_VAR)
+        .ok()
+        .and_then(|limit| limit.parse().ok())
+        .unwrap_or(DEFAULT_TIME_LIMIT);
+
+    let mut matrix = LedMatrix::new();
+    matrix.time_limit_ms = Some(time_limit);
+    catch_time_limit(|| f(matrix));
+
+    std::process::exit(0)
+}
+
+/// Payload used to unwind out of a program once its time limit is reached.
+struct TimeLimitReached;
+
+fn catch_time_limit(f: impl FnOnce()) {
+    if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(f)) {
+        if !payload.is::<TimeLimitReached>() {
+            panic::resume_unwind(payload)
+        }
+    }
+}
+
+impl<const W: usize, const H: usize> Default for LedMatrix<W, H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const W: usize, const H: usize> LedMatrix<W, H> {
+    pub fn new() -> Self {
+        Self {
+            now_ms: 0,
+            time_limit_ms: None,
+            script: VecDeque::new(),
+            frames: Vec::new(),
+            joystick_position: JoystickPosition::Center,
+            joystick_pressed: false,
+            switch: false,
+            brightness: 255,
+            gamma: Gamma::LINEAR,
+            power_limit: PowerLimit::USB,
+            leds: [[(0, 0, 0); W]; H],
+        }
+    }
+
+    /// Run a program until it returns or `duration_ms` of virtual time have
+    /// passed, whichever comes first.
+    ///
+    /// This is how you test programs that contain an endless loop. Sleeping
+    /// past the time limit stops the program, the matrix can be inspected
+    /// afterwards.
+    ///
+    /// ```
+    /// use led_matrix_core::LedMatrixCore;
+    ///
+    /// let mut matrix = led_matrix_headless::LedMatrix::<8, 8>::new();
+    /// matrix.run_for(1_000, |matrix| loop {
+    ///     matrix.apply();
+    ///     matrix.sleep_ms(100);
+    /// });
+    /// assert_eq!(matrix.frames().len(), 10);
+    /// ```
+    pub fn run_for<F: FnOnce(&mut Self)>(&mut self, duration_ms: u64, f: F) {
+        let previous_limit = self.time_limit_ms.replace(self.now_ms + duration_ms);
+        catch_time_limit(|| f(self));
+        self.time_limit_ms = previous_limit;
+    }
+
+    /// Schedule an input event at a point in virtual time.
+    ///
+    /// The event takes effect as soon as the virtual clock reaches `at_ms`.
+    /// Events scheduled for the same time are applied in the order they were
+    /// scheduled.
+    pub fn schedule(&mut self, at_ms: u64, input: Input) -> &mut Self {
+        let i = self.script.partition_point(|&(t, _)| t <= at_ms);
+        self.script.insert(i, (at_ms, input));
+        self.process_script();
+        self
+    }
+
+    /// The current virtual time in milliseconds.
+    pub fn now_ms(&self) -> u64 {
+        self.now_ms
+    }
+
+    /// All frames that were passed to `apply` so far, oldest first.
+    pub fn frames(&self) -> &[Frame<W, H>] {
+        &self.frames
+    }
+
+    /// The most recently applied frame, if any.
+    pub fn last_frame(&self) -> Option<&Frame<W, H>> {
+        self.frames.last()
+    }
+
+    /// Forget all recorded frames, e.g. after some setup code.
+    pub fn clear_frames(&mut self) {
+        self.frames.clear()
+    }
+
+    /// The brightness most recently set by the program.
+    pub fn brightness(&self) -> u8 {
+        self.brightness
+    }
+
+    /// The gamma correction most recently set by the program.
+    ///
+    /// It isn't applied to the recorded frames, they contain the colors
+    /// exactly as they were drawn.
+    pub fn gamma(&self) -> &Gamma {
+        &self.gamma
+    }
+
+    /// The power limit most recently set by the program.
+    pub fn power_limit(&self) -> &PowerLimit {
+        &self.power_limit
+    }
+
+    fn process_script(&mut self) {
+        while let Some(&(at_ms, input)) = self.script.front() {
+            if at_ms > self.now_ms {
+                break;
+            }
+            self.script.pop_front();
+            match input {
+                Input::Joystick(position) => self.joystick_position = position,
+                Input::JoystickPressed(pressed) => self.joystick_pressed = pressed,
+                Input::Switch(switch) => self.switch = switch,
+            }
+        }
+    }
+}
+
+impl<const W: usize, const H: usize> led_matrix_core::LedMatrixCore<W, H> for LedMatrix<W, H> {
+    fn apply(&mut self) {
+        self.frames.push(Frame {
+            time_ms: self.now_ms,
+            brightness: self.brightness,
+            leds: self.leds,
+        });
+    }
+
+    fn set_brightness(&mut self, brightness: u8) {
+        self.brightness = brightness
+    }
+
+    fn set_gamma(&mut self, gamma: Gamma) {
+        self.gamma = gamma
+    }
+
+    fn set_power_limit(&mut self, limit: PowerLimit) {
+        self.power_limit = limit
+    }
+
+    fn sleep_ms(&mut self, duration: u32) {
+        self.now_ms += u64::from(duration);
+        if let Some(limit) = self.time_limit_ms {
+            if self.now_ms >= limit {
+                self.now_ms = limit;
+                self.process_script();
+                panic::resume_unwind(Box::new(TimeLimitReached));
+            }
+        }
+        self.process_script();
+    }
+
+    fn get_sin(&self) -> fn(f32) -> f32 {
+        f32::sin
+    }
+
+    fn joystick_position(&mut self) -> JoystickPosition {
+        self.joystick_position
+    }
+
+    fn switch(&mut self) -> bool {
+        self.switch
+    }
+
+    fn joystick_pressed(&mut self) -> bool {
+        self.joystick_pressed
+    }
+}
+
+impl<const W: usize, const H: usize> AsRef<LedGrid<W, H>> for LedMatrix<W, H> {
+    fn as_ref(&self) -> &LedGrid<W, H> {
+        &self.leds
+    }
+}
+
+impl<const W: usize, const H: usize> core::ops::Index<(usize, usize)> for LedMatrix<W, H> {
+    type Output = (u8, u8, u8);
+
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        assert!((0..W).contains(&x));
+        assert!((0..H).contains(&y));
+        &self.leds[H - y - 1][x]
+    }
+}
+impl<const W: usize, const H: usize> core::ops::IndexMut<(usize, usize)> for LedMatrix<W, H> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
+        assert!((0..W).contains(&x));
+        assert!((0..H).contains(&y));
+        &mut self.leds[H - y - 1][x]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use led_matrix_core::LedMatrixCore;
+
+    use super::*;
+
+    #[test]
+    fn sleeping_advances_the_clock() {
+        let mut matrix = LedMatrix::<8, 8>::new();
+        assert_eq!(matrix.now_ms(), 0);
+        matrix.sleep_ms(30);
+        matrix.sleep_ms(12);
+        assert_eq!(matrix.now_ms(), 42);
+    }
+
+    #[test]
+    fn frames_are_recorded_with_time_and_brightness() {
+        let mut matrix = LedMatrix::<4, 2>::new();
+        matrix[(0, 0)] = (255, 0, 0);
+        matrix.apply();
+        matrix.sleep_ms(100);
+        matrix.set_brightness(7);
+        matrix[(3, 1)] = (0, 0, 255);
+        matrix.apply();
+
+        let frames = matrix.frames();
+        assert_eq!(frames.len(), 2);
+        assert_eq!((frames[0].time_ms, frames[0].brightness), (0, 255));
+        assert_eq!((frames[1].time_ms, frames[1].brightness), (100, 7));
+        // frames are copies, later drawing doesn't change them
+        assert_eq!(frames[0][(3, 1)], (0, 0, 0));
+        assert_eq!(frames[1][(3, 1)], (0, 0, 255));
+        // the top row is stored first
+        assert_eq!(frames[1].leds[0][3], (0, 0, 255));
+        assert_eq!(frames[1].leds[1][0], (255, 0, 0));
+        assert_eq!(matrix.last_frame(), Some(&frames[1]));
+
+        matrix.clear_frames();
+        assert!(matrix.frames().is_empty());
+        assert_eq!(matrix.last_frame(), None);
+    }
+
+    #[test]
+    fn run_for_stops_endless_loops_at_the_time_limit() {
+        let mut matrix = LedMatrix::<8, 8>::new();
+        matrix.run_for(1_000, |matrix| loop {
+            matrix.apply();
+            matrix.sleep_ms(300);
+        });
+        assert_eq!(matrix.now_ms(), 1_000);
+        let times: Vec<u64> = matrix.frames().iter().map(|f| f.time_ms).collect();
+        assert_eq!(times, [0, 300, 600, 900]);
+
+        // the limit is relative to the current time and is lifted afterwards
+        matrix.run_for(500, |matrix| loop {
+            matrix.sleep_ms(200);
+        });
+        assert_eq!(matrix.now_ms(), 1_500);
+        matrix.sleep_ms(10_000);
+        assert_eq!(matrix.now_ms(), 11_500);
+    }
+
+    #[test]
+    fn run_for_returns_when_the_program_does() {
+        let mut matrix = LedMatrix::<8, 8>::new();
+        matrix.run_for(1_000, |matrix| matrix.sleep_ms(250));
+        assert_eq!(matrix.now_ms(), 250);
+    }
+
+    #[test]
+    fn nested_run_for_restores_the_outer_limit() {
+        let mut matrix = LedMatrix::<8, 8>::new();
+        matrix.run_for(1_000, |matrix| {
+            matrix.run_for(100, |matrix| loop {
+                matrix.sleep_ms(30);
+            });
+            assert_eq!(matrix.now_ms(), 100);
+            loop {
+                matrix.sleep_ms(400);
+            }
+        });
+        assert_eq!(matrix.now_ms(), 1_000);
+    }
+
+    #[test]
+    #[should_panic(expected = "boom")]
+    fn run_for_propagates_other_panics() {
+        let mut matrix = LedMatrix::<8, 8>::new();
+        matrix.run_for(1_000, |_| panic!("boom"));
+    }
+
+    #[test]
+    fn scheduled_inputs_take_effect_in_virtual_time() {
+        let mut matrix = LedMatrix::<8, 8>::new();
+        matrix
+            .schedule(100, Input::Joystick(JoystickPosition::Up))
+            .schedule(100, Input::Joystick(JoystickPosition::Left))
+            .schedule(50, Input::Switch(true))
+            .schedule(200, Input::JoystickPressed(true));
+
+        assert!(!matrix.switch());
+        matrix.sleep_ms(49);
+        assert!(!matrix.switch());
+        matrix.sleep_ms(1);
+        assert!(matrix.switch());
+        assert_eq!(matrix.joystick_position(), JoystickPosition::Center);
+
+        // events at the same time are applied in the order they were scheduled
+        matrix.sleep_ms(50);
+        assert_eq!(matrix.joystick_position(), JoystickPosition::Left);
+        assert!(!matrix.joystick_pressed());
+
+        // sleeping past several events applies all of them
+        matrix.sleep_ms(1_000);
+        assert!(matrix.joystick_pressed());
+    }
+
+    #[test]
+    fn inputs_scheduled_in_the_past_apply_immediately() {
+        let mut matrix = LedMatrix::<8, 8>::new();
+        matrix.sleep_ms(500);
+        matrix.schedule(100, Input::Switch(true));
+        assert!(matrix.switch());
+    }
+
+    #[test]
+    fn inputs_at_the_time_limit_are_applied() {
+        let mut matrix = LedMatrix::<8, 8>::new();
+        matrix.schedule(1_000, Input::Switch(true));
+        matrix.run_for(1_000, |matrix| loop {
+            matrix.sleep_ms(700);
+        });
+        assert!(matrix.switch());
+    }
+}
@@ -0,0 +1,424 @@
+//! Compare frames against checked-in snapshot files.
+//!
+//! Use the macro [assert_frame_eq](crate::assert_frame_eq) in your tests.
+//! Snapshots are stored either as a readable text file or as a PPM image,
+//! depending on the file extension (`.ppm` for PPM, anything else is text).
+//!
+//! The text format draws one symbol per LED, top row first, followed by a
+//! legend mapping each symbol to its color:
+//!
+//! ```txt
+//! ..a..a..
+//! ...aa...
+//! ..####..
+//! .#.##.#.
+//! ########
+//! #.####.#
+//! #.#..#.#
+//! ...##...
+//!
+//! . 000000
+//! # ffffff
+//! a ff0000
+//! ```
+//!
+//! There are symbols for 70 different colors. Frames with more colors, e.g.
+//! a rainbow on a large matrix, can only be stored as PPM.
+//!
+//! Set the environment variable [UPDATE_VAR] to `1` to write the current
+//! frame to the snapshot file instead of comparing against it. This also
+//! creates snapshots that don't exist yet.
+
+use std::{fmt::Write, fs, path::Path};
+
+use crate::LedGrid;
+
+/// Setting this environment variable to `1` updates snapshots instead of
+/// comparing against them.
+pub const UPDATE_VAR: &str = "LED_MATRIX_UPDATE_SNAPSHOTS";
+
+/// Symbols used for colors in the text format. Black and white always get
+/// `.` and `#`, other colors get the remaining ones in order of appearance.
+const SYMBOLS: &[u8] = b".#abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789@$%&*+";
+
+/// Assert that the current LED values of a matrix or a recorded frame match
+/// a snapshot file.
+///
+/// The path is relative to the directory of the crate's `Cargo.toml`. On
+/// mismatch, the test panics with both images side by side and a list of all
+/// LEDs that differ.
+///
+/// ```no_run
+/// use led_matrix_core::LedMatrixCore;
+/// use led_matrix_headless::{assert_frame_eq, LedMatrix};
+///
+/// let mut matrix = LedMatrix::<8, 8>::new();
+/// matrix[(3, 4)] = (255, 0, 0);
+/// matrix.apply();
+///
+/// assert_frame_eq!(matrix, "tests/snapshots/red_dot.txt");
+/// assert_frame_eq!(matrix.frames()[0], "tests/snapshots/red_dot.ppm");
+/// ```
+#[macro_export]
+macro_rules! assert_frame_eq {
+    ($frame:expr, $path:expr $(,)?) => {
+        $crate::snapshot::assert_snapshot(
+            ::core::convert::AsRef::as_ref(&$frame),
+            &::std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join($path),
+        )
+    };
+}
+
+/// The function behind [assert_frame_eq](crate::assert_frame_eq), which
+/// takes an absolute path.
+#[track_caller]
+pub fn assert_snapshot<const W: usize, const H: usize>(actual: &LedGrid<W, H>, path: &Path) {
+    let is_ppm = path.extension().is_some_and(|ext| ext == "ppm");
+
+    if std::env::var(UPDATE_VAR).is_ok_and(|v| v == "1") {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).unwrap();
+        }
+        let content = if is_ppm {
+            to_ppm(actual)
+        } else {
+            to_text(actual)
+                .unwrap_or_else(|e| panic!("failed to write snapshot {}: {e}", path.display()))
+                .into_bytes()
+        };
+        fs::write(path, content)
+            .unwrap_or_else(|e| panic!("failed to write snapshot {}: {e}", path.display()));
+        return;
+    }
+
+    let content = match fs::read(path) {
+        Ok(content) => content,
+        Err(e) => panic!(
+            "failed to read snapshot {}: {e}\n\
+             run with {UPDATE_VAR}=1 to create it",
+            path.display()
+        ),
+    };
+    let parsed = if is_ppm {
+        from_ppm(&content)
+    } else {
+        from_text(&String::from_utf8_lossy(&content))
+    };
+    let expected = parsed.unwrap_or_else(|e| panic!("invalid snapshot {}: {e}", path.display()));
+
+    if expected != *actual {
+        panic!(
+            "frame does not match snapshot {}\n\n{}\nrun with {UPDATE_VAR}=1 to update it",
+            path.display(),
+            diff(&expected, actual)
+        );
+    }
+}
+
+fn hex((r, g, b): (u8, u8, u8)) -> String {
+    format!("{r:02x}{g:02x}{b:02x}")
+}
+
+/// Colors and their symbols in the text format.
+type Palette = Vec<((u8, u8, u8), u8)>;
+
+/// Assign a symbol to every color in the given grids, or return the number
+/// of colors if there aren't enough symbols for all of them.
+fn palette<const W: usize, const H: usize>(grids: &[&LedGrid<W, H>]) -> Result<Palette, usize> {
+    let mut palette = vec![((0, 0, 0), b'.'), ((255, 255, 255), b'#')];
+    for &color in grids.iter().flat_map(|grid| grid.iter().flatten()) {
+        if !palette.iter().any(|&(c, _)| c == color) {
+            palette.push((color, 0));
+        }
+    }
+    if palette.len() > SYMBOLS.len() {
+        return Err(palette.len());
+    }
+    for (entry, &symbol) in palette.iter_mut().zip(SYMBOLS) {
+        entry.1 = symbol;
+    }
+    Ok(palette)
+}
+
+fn symbol(palette: &[((u8, u8, u8), u8)], color: (u8, u8, u8)) -> char {
+    palette.iter().find(|&&(c, _)| c == color).unwrap().1 as char
+}
+
+fn to_text<const W: usize, const H: usize>(grid: &LedGrid<W, H>) -> Result<String, String> {
+    let palette = palette(&[grid]).map_err(|count| {
+        format!(
+            "{count} colors don't fit into the text format, which has {} symbols, \
+             use a .ppm snapshot instead",
+            SYMBOLS.len()
+        )
+    })?;
+    let mut text = String::new();
+    for row in grid {
+        text.extend(row.iter().map(|&c| symbol(&palette, c)));
+        text.push('\n');
+    }
+    text.push('\n');
+    for &(color, s) in &palette {
+        if grid.iter().flatten().any(|&c| c == color) {
+            writeln!(text, "{} {}", s as char, hex(color)).unwrap();
+        }
+    }
+    Ok(text)
+}
+
+fn from_text<const W: usize, const H: usize>(text: &str) -> Result<LedGrid<W, H>, String> {
+    let mut lines = text.lines();
+    let rows: Vec<&str> = lines
+        .by_ref()
+        .take_while(|l| !l.trim().is_empty())
+        .collect();
+    if rows.len() != H {
+        return Err(format!("expected {H} rows, found {}", rows.len()));
+    }
+
+    let mut legend = Vec::new();
+    for line in lines.filter(|l| !l.trim().is_empty()) {
+        let (symbol, color) = line
+            .trim()
+            .split_once(' ')
+            .ok_or_else(|| format!("invalid legend entry: {line:?}"))?;
+        let color = u32::from_str_radix(color.trim(), 16)
+            .ok()
+            .filter(|_| color.trim().len() == 6)
+            .ok_or_else(|| format!("invalid color in legend: {line:?}"))?;
+        let [_, r, g, b] = color.to_be_bytes();
+        if legend.iter().any(|(s, _)| s == symbol) {
+            return Err(format!("symbol {symbol:?} appears twice in the legend"));
+        }
+        legend.push((symbol.to_string(), (r, g, b)));
+    }
+
+    let mut grid = [[(0, 0, 0); W]; H];
+    for (row, line) in grid.iter_mut().zip(rows) {
+        let symbols: Vec<char> = line.trim_end().chars().collect();
+        if symbols.len() != W {
+            return Err(format!("expected {W} symbols per row: {line:?}"));
+        }
+        for (led, s) in row.iter_mut().zip(symbols) {
+            *led = legend
+                .iter()
+                .find(|(symbol, _)| symbol.chars().eq([s]))
+                .ok_or_else(|| format!("symbol {s:?} is missing from the legend"))?
+                .1;
+        }
+    }
+    Ok(grid)
+}
+
+fn to_ppm<const W: usize, const H: usize>(grid: &LedGrid<W, H>) -> Vec<u8> {
+    let mut ppm = format!("P3\n{W} {H}\n255\n");
+    for row in grid {
+        let row: Vec<String> = row.iter().map(|(r, g, b)| format!("{r} {g} {b}")).collect();
+        ppm.push_str(&row.join("  "));
+        ppm.push('\n');
+    }
+    ppm.into_bytes()
+}
+
+/// Parse a PPM image, both the plain (P3) and the raw (P6) variant.
+fn from_ppm<const W: usize, const H: usize>(ppm: &[u8]) -> Result<LedGrid<W, H>, String> {
+    let mut pos = 0;
+    let mut header = Vec::new();
+    while header.len() < 4 {
+        while pos < ppm.len() && (ppm[pos].is_ascii_whitespace() || ppm[pos] == b'#') {
+            if ppm[pos] == b'#' {
+                while pos < ppm.len() && ppm[pos] != b'\n' {
+                    pos += 1;
+                }
+            } else {
+                pos += 1;
+            }
+        }
+        let start = pos;
+        while pos < ppm.len() && !ppm[pos].is_ascii_whitespace() {
+            pos += 1;
+        }
+        if start == pos {
+            return Err("incomplete PPM header".into());
+        }
+        header.push(String::from_utf8_lossy(&ppm[start..pos]).into_owned());
+    }
+    let magic = header[0].as_str();
+    let size: Vec<usize> = header[1..].iter().filter_map(|n| n.parse().ok()).collect();
+    if size != [W, H, 255] {
+        return Err(format!("expected a {W}x{H} image with 8-bit colors"));
+    }
+
+    let samples: Vec<u8> = match magic {
+        "P3" => String::from_utf8_lossy(&ppm[pos..])
+            .split_ascii_whitespace()
+            .map(|n| n.parse().map_err(|_| format!("invalid sample: {n:?}")))
+            .collect::<Result<_, _>>()?,
+        // exactly one whitespace byte separates the header from the data
+        "P6" => ppm.get(pos + 1..).unwrap_or_default().to_vec(),
+        _ => return Err(format!("unsupported PPM variant: {magic}")),
+    };
+    if samples.len() != W * H * 3 {
+        return Err("wrong number of color samples".into());
+    }
+
+    let mut grid = [[(0, 0, 0); W]; H];
+    for (led, rgb) in grid.iter_mut().flatten().zip(samples.chunks(3)) {
+        *led = (rgb[0], rgb[1], rgb[2]);
+    }
+    Ok(grid)
+}
+
+/// Render two grids side by side, followed by a list of differences.
+fn diff<const W: usize, const H: usize>(
+    expected: &LedGrid<W, H>,
+    actual: &LedGrid<W, H>,
+) -> String {
+    let mut text = String::new();
+    match palette(&[expected, actual]) {
+        Ok(palette) => {
+            writeln!(text, "{:<W$}   actual", "expected").unwrap();
+            for (expected, actual) in expected.iter().zip(actual) {
+                let expected: String = expected.iter().map(|&c| symbol(&palette, c)).collect();
+                let actual: String = actual.iter().map(|&c| symbol(&palette, c)).collect();
+                let marker = if expected == actual { "" } else { " <" };
+                writeln!(text, "{expected:<W$}   {actual}{marker}").unwrap();
+            }
+            text.push('\n');
+            for &(color, s) in &palette {
+                let used = |grid: &LedGrid<W, H>| grid.iter().flatten().any(|&c| c == color);
+                if used(expected) || used(actual) {
+                    writeln!(text, "{} {}", s as char, hex(color)).unwrap();
+                }
+            }
+        }
+        Err(count) => writeln!(
+            text,
+            "the frames have too many colors ({count}) to draw them"
+        )
+        .unwrap(),
+    }
+
+    text.push_str("\ndifferences (x, y: expected -> actual):\n");
+    for (i, (expected, actual)) in expected.iter().zip(actual).enumerate() {
+        let y = H - i - 1;
+        for (x, (&e, &a)) in expected.iter().zip(actual).enumerate() {
+            if e != a {
+                writeln!(text, "  {x}, {y}: {} -> {}", hex(e), hex(a)).unwrap();
+            }
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEART: &str = "\
+..a..a..
+.aaaaaa.
+.aaaaaa.
+..aaaa..
+...aa...
+........
+########
+.b.b.b.b
+
+. 000000
+# ffffff
+a ff0000
+b 0a0b0c
+";
+
+    /// A grid with a different color for every LED.
+    fn colorful<const W: usize, const H: usize>() -> LedGrid<W, H> {
+        let mut grid = [[(0, 0, 0); W]; H];
+        for (i, led) in grid.iter_mut().flatten().enumerate() {
+            *led = (i as u8, 100, 200);
+        }
+        grid
+    }
+
+    #[test]
+    fn text_round_trip() {
+        let grid: LedGrid<8, 8> = from_text(HEART).unwrap();
+        assert_eq!(grid[0][2], (255, 0, 0));
+        assert_eq!(grid[6][0], (255, 255, 255));
+        assert_eq!(grid[7][1], (10, 11, 12));
+        assert_eq!(to_text(&grid).unwrap(), HEART);
+    }
+
+    #[test]
+    fn text_with_as_many_colors_as_symbols() {
+        // black and white are part of the grid, too
+        let mut grid: LedGrid<10, 7> = colorful();
+        grid[0][0] = (0, 0, 0);
+        grid[0][1] = (255, 255, 255);
+        let text = to_text(&grid).unwrap();
+        assert_eq!(from_text::<10, 7>(&text), Ok(grid));
+    }
+
+    #[test]
+    fn text_with_too_many_colors_is_an_error() {
+        let grid: LedGrid<16, 16> = colorful();
+        let error = to_text(&grid).unwrap_err();
+        assert!(error.contains("258 colors"), "{error}");
+    }
+
+    #[test]
+    fn text_errors() {
+        let short_row = HEART.replacen("..a..a..", "..a..a.", 1);
+        assert!(from_text::<8, 8>(&short_row).is_err());
+        let missing = HEART.replace("b 0a0b0c\n", "");
+        assert_eq!(
+            from_text::<8, 8>(&missing),
+            Err("symbol 'b' is missing from the legend".into())
+        );
+        let twice = format!("{HEART}a 00ff00\n");
+        assert!(from_text::<8, 8>(&twice).is_err());
+        let invalid = HEART.replace("0a0b0c", "0a0b0");
+        assert!(from_text::<8, 8>(&invalid).is_err());
+        assert!(from_text::<8, 7>(HEART).is_err());
+    }
+
+    #[test]
+    fn plain_ppm_round_trip() {
+        let grid: LedGrid<16, 16> = colorful();
+        let ppm = to_ppm(&grid);
+        assert!(ppm.starts_with(b"P3\n16 16\n255\n"));
+        assert_eq!(from_ppm::<16, 16>(&ppm), Ok(grid));
+    }
+
+    #[test]
+    fn raw_ppm() {
+        let grid: LedGrid<3, 2> = colorful();
+        let mut ppm = b"P6\n# a comment\n3 2\n255\n".to_vec();
+        for &(r, g, b) in grid.iter().flatten() {
+            ppm.extend([r, g, b]);
+        }
+        assert_eq!(from_ppm::<3, 2>(&ppm), Ok(grid));
+        // samples that look like whitespace are data, not separators
+        *ppm.last_mut().unwrap() = b' ';
+        assert_eq!(from_ppm::<3, 2>(&ppm).unwrap()[1][2].2, b' ');
+    }
+
+    #[test]
+    fn ppm_errors() {
+        assert!(from_ppm::<3, 2>(b"P6\n3 2\n255\n\x00\x01").is_err());
+        assert!(from_ppm::<3, 2>(b"P3\n2 3\n255\n").is_err());
+        assert!(from_ppm::<3, 2>(b"P3\n3 2\n65535\n").is_err());
+        assert!(from_ppm::<3, 2>(b"P5\n3 2\n255\n").is_err());
+        assert!(from_ppm::<3, 2>(b"P3\n3").is_err());
+    }
+
+    #[test]
+    fn diff_with_too_many_colors() {
+        let expected: LedGrid<16, 16> = colorful();
+        let mut actual = expected;
+        actual[0][0] = (1, 2, 3);
+        let diff = diff(&expected, &actual);
+        assert!(diff.contains("too many colors"), "{diff}");
+        assert!(diff.contains("0, 15: 0064c8 -> 010203"), "{diff}");
+    }
+}
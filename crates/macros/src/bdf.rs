@@ -0,0 +1,318 @@
+//! A parser for fonts in the Glyph Bitmap Distribution Format (BDF).
+
+/// A font read from a BDF file.
+pub struct Font {
+    /// The height of a line, from the lowest to the highest pixel.
+    pub height: usize,
+    /// The number of rows below the baseline.
+    pub descent: usize,
+    /// The glyph to draw for unsupported characters, if the font has one.
+    pub default_char: Option<char>,
+    pub glyphs: Vec<Glyph>,
+}
+
+pub struct Glyph {
+    pub char: char,
+    /// The horizontal distance to the next glyph.
+    pub advance: usize,
+    /// The lit pixels, `y` counted from the lowest row of the line.
+    pub pixels: Vec<(usize, usize)>,
+}
+
+/// The size of a bounding box and the offset of its lower left corner from
+/// the origin on the baseline.
+#[derive(Clone, Copy)]
+struct BoundingBox {
+    width: usize,
+    height: usize,
+    x: i64,
+    y: i64,
+}
+
+/// The vertical extent of a line of text.
+#[derive(Clone, Copy)]
+struct Line {
+    height: usize,
+    descent: usize,
+}
+
+pub fn parse(source: &str) -> Result<Font, String> {
+    let mut lines = source
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()));
+    if !matches!(lines.next(), Some((_, line)) if line.starts_with("STARTFONT")) {
+        return Err("not a BDF font, STARTFONT is missing".into());
+    }
+
+    let mut bounding_box = None;
+    let (mut ascent, mut descent, mut default_char) = (None, None, None);
+    let mut glyphs = Vec::new();
+    let mut metrics = None;
+
+    while let Some((number, text)) = lines.next() {
+        let (keyword, args) = text.split_once(' ').unwrap_or((text, ""));
+        let error = |message: String| format!("line {number}: {message}");
+        match keyword {
+            "FONTBOUNDINGBOX" => bounding_box = Some(parse_bounding_box(args).map_err(error)?),
+            "FONT_ASCENT" => ascent = Some(parse_number(args).map_err(error)?),
+            "FONT_DESCENT" => descent = Some(parse_number(args).map_err(error)?),
+            "DEFAULT_CHAR" => {
+                let code = parse_number(args).map_err(error)?;
+                default_char = u32::try_from(code).ok().and_then(char::from_u32);
+            }
+            "STARTCHAR" => {
+                // the properties always precede the glyphs
+                let line = match metrics {
+                    Some(line) => line,
+                    None => {
+                        let bounding_box = bounding_box
+                            .ok_or_else(|| error("FONTBOUNDINGBOX is missing".into()))?;
+                        let descent = descent.unwrap_or(-bounding_box.y).max(0);
+                        let ascent = ascent
+                            .unwrap_or(bounding_box.height as i64 + bounding_box.y)
+                            .max(0);
+                        *metrics.insert(Line {
+                            height: (ascent + descent) as usize,
+                            descent: descent as usize,
+                        })
+                    }
+                };
+                if let Some(glyph) = parse_glyph(&mut lines, line)? {
+                    if glyphs.iter().any(|other: &Glyph| other.char == glyph.char) {
+                        return Err(error(format!("{:?} is encoded twice", glyph.char)));
+                    }
+                    glyphs.push(glyph);
+                }
+            }
+            "ENDFONT" => break,
+            _ => {}
+        }
+    }
+
+    let line = metrics.ok_or("the font has no glyphs")?;
+    Ok(Font {
+        height: line.height,
+        descent: line.descent,
+        default_char,
+        glyphs,
+    })
+}
+
+/// Parse a glyph after its STARTCHAR line. Glyphs without a Unicode code
+/// point are skipped.
+fn parse_glyph<'a>(
+    lines: &mut impl Iterator<Item = (usize, &'a str)>,
+    line: Line,
+) -> Result<Option<Glyph>, String> {
+    let (mut code, mut advance, mut bounding_box) = (None, None, None);
+    while let Some((number, text)) = lines.next() {
+        let (keyword, args) = text.split_once(' ').unwrap_or((text, ""));
+        let error = |message: String| format!("line {number}: {message}");
+        let first_arg = args.split_whitespace().next().unwrap_or("");
+        match keyword {
+            // -1 if the glyph has no code point
+            "ENCODING" => code = Some(parse_number(first_arg).map_err(error)?),
+            "DWIDTH" => advance = Some(parse_number(first_arg).map_err(error)?.max(0) as usize),
+            "BBX" => bounding_box = Some(parse_bounding_box(args).map_err(error)?),
+            "BITMAP" => {
+                let code = code.ok_or_else(|| error("ENCODING is missing".into()))?;
+                let advance = advance.ok_or_else(|| error("DWIDTH is missing".into()))?;
+                let bounding_box = bounding_box.ok_or_else(|| error("BBX is missing".into()))?;
+
+                let mut pixels = Vec::new();
+                for row in 0..bounding_box.height {
+                    let (number, text) = lines.next().ok_or("unexpected end of file")?;
+                    let error = || format!("line {number}: invalid bitmap row");
+                    // the first pixel is the highest bit, rows are padded to whole bytes
+                    let bits = text.len() * 4;
+                    if bits > 128 || bits < bounding_box.width {
+                        return Err(error());
+                    }
+                    let row_bits = u128::from_str_radix(text, 16).map_err(|_| error())?;
+                    for column in 0..bounding_box.width {
+                        if row_bits & (1 << (bits - 1 - column)) != 0 {
+                            let x = bounding_box.x + column as i64;
+                            let y = bounding_box.y + (bounding_box.height - 1 - row) as i64;
+                            pixels.push((x, y + line.descent as i64));
+                        }
+                    }
+                }
+                // pixels outside of the line can't be drawn
+                let pixels = pixels
+                    .into_iter()
+                    .filter_map(|(x, y)| Some((usize::try_from(x).ok()?, usize::try_from(y).ok()?)))
+                    .filter(|&(_, y)| y < line.height)
+                    .collect();
+
+                let c = u32::try_from(code).ok().and_then(char::from_u32);
+                return Ok(c.map(|char| Glyph {
+                    char,
+                    advance,
+                    pixels,
+                }));
+            }
+            _ => {}
+        }
+    }
+    Err("unexpected end of file".into())
+}
+
+fn parse_bounding_box(args: &str) -> Result<BoundingBox, String> {
+    let numbers = args
+        .split_whitespace()
+        .map(parse_number)
+        .collect::<Result<Vec<_>, _>>()?;
+    match numbers[..] {
+        [width, height, x, y] if width >= 0 && height >= 0 => Ok(BoundingBox {
+            width: width as usize,
+            height: height as usize,
+            x,
+            y,
+        }),
+        _ => Err(format!("invalid bounding box {args:?}")),
+    }
+}
+
+fn parse_number(text: &str) -> Result<i64, String> {
+    text.trim()
+        .parse()
+        .map_err(|_| format!("invalid number {text:?}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FONT: &str = "\
+STARTFONT 2.1
+FONT -test-3x5
+SIZE 5 75 75
+FONTBOUNDINGBOX 3 5 0 -1
+STARTPROPERTIES 3
+FONT_ASCENT 4
+FONT_DESCENT 1
+DEFAULT_CHAR 63
+ENDPROPERTIES
+CHARS 3
+STARTCHAR A
+ENCODING 65
+SWIDTH 500 0
+DWIDTH 4 0
+BBX 3 2 0 0
+BITMAP
+E0
+A0
+ENDCHAR
+STARTCHAR comma
+ENCODING 44
+DWIDTH 2 0
+BBX 2 2 -1 -1
+BITMAP
+40
+80
+ENDCHAR
+STARTCHAR unencoded
+ENCODING -1
+DWIDTH 3 0
+BBX 1 1 0 0
+BITMAP
+80
+ENDCHAR
+ENDFONT
+";
+
+    fn parse_err(source: &str) -> String {
+        match parse(source) {
+            Ok(_) => panic!("the font was accepted"),
+            Err(message) => message,
+        }
+    }
+
+    #[test]
+    fn font() {
+        let font = parse(FONT).unwrap();
+        assert_eq!((font.height, font.descent), (5, 1));
+        assert_eq!(font.default_char, Some('?'));
+
+        let chars: Vec<_> = font.glyphs.iter().map(|glyph| glyph.char).collect();
+        assert_eq!(chars, ['A', ',']);
+
+        // the lowest row of "A" is on the baseline, one row above the descent
+        let a = &font.glyphs[0];
+        assert_eq!(a.advance, 4);
+        assert_eq!(a.pixels, [(0, 2), (1, 2), (2, 2), (0, 1), (2, 1)]);
+    }
+
+    #[test]
+    fn negative_offsets() {
+        // the comma starts one column left of the origin and one row below the
+        // baseline, the pixel left of the origin is cut off
+        let font = parse(FONT).unwrap();
+        let comma = &font.glyphs[1];
+        assert_eq!(comma.advance, 2);
+        assert_eq!(comma.pixels, [(0, 1)]);
+    }
+
+    #[test]
+    fn descent_from_the_bounding_box() {
+        let source = FONT
+            .replace("FONT_ASCENT 4\n", "")
+            .replace("FONT_DESCENT 1\n", "");
+        let font = parse(&source).unwrap();
+        assert_eq!((font.height, font.descent), (5, 1));
+
+        let source = FONT.replace("FONT_DESCENT 1", "FONT_DESCENT 2");
+        let font = parse(&source).unwrap();
+        assert_eq!((font.height, font.descent), (6, 2));
+        assert_eq!(
+            font.glyphs[0].pixels,
+            [(0, 3), (1, 3), (2, 3), (0, 2), (2, 2)]
+        );
+    }
+
+    #[test]
+    fn without_default_char() {
+        let font = parse(&FONT.replace("DEFAULT_CHAR 63\n", "")).unwrap();
+        assert_eq!(font.default_char, None);
+        let font = parse(&FONT.replace("DEFAULT_CHAR 63", "DEFAULT_CHAR -1")).unwrap();
+        assert_eq!(font.default_char, None);
+    }
+
+    #[test]
+    fn malformed_rows() {
+        let source = FONT.replace("E0\nA0", "E0\nZZ");
+        assert_eq!(parse_err(&source), "line 18: invalid bitmap row");
+
+        // a row must have a bit for every column
+        let source = FONT.replace("BBX 3 2 0 0", "BBX 9 2 0 0");
+        assert_eq!(parse_err(&source), "line 17: invalid bitmap row");
+
+        let source = FONT.replace("BBX 3 2 0 0", "BBX 3 2 0");
+        assert_eq!(
+            parse_err(&source),
+            "line 15: invalid bounding box \"3 2 0\""
+        );
+
+        let end = FONT.find("E0\n").unwrap();
+        assert_eq!(parse_err(&FONT[..end]), "unexpected end of file");
+    }
+
+    #[test]
+    fn duplicate_encodings() {
+        let source = FONT.replace("ENCODING 44", "ENCODING 65");
+        assert_eq!(parse_err(&source), "line 20: 'A' is encoded twice");
+    }
+
+    #[test]
+    fn missing_properties() {
+        assert_eq!(
+            parse_err("FONT -test-3x5\n"),
+            "not a BDF font, STARTFONT is missing"
+        );
+        let source = FONT.replace("FONTBOUNDINGBOX 3 5 0 -1\n", "");
+        assert_eq!(parse_err(&source), "line 10: FONTBOUNDINGBOX is missing");
+        let source = FONT.replace("DWIDTH 4 0\n", "");
+        assert_eq!(parse_err(&source), "line 15: DWIDTH is missing");
+    }
+}
@@ -0,0 +1,142 @@
+//! Procedural macros of the crate `led-matrix`, which re-exports them.
+
+use std::path::PathBuf;
+
+mod bdf;
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, LitByteStr, LitStr};
+
+// documented where it's re-exported, in the crate `led-matrix`
+#[proc_macro]
+pub fn include_image(input: TokenStream) -> TokenStream {
+    let path = parse_macro_input!(input as LitStr);
+    match include_image_impl(&path) {
+        Ok(tokens) => tokens.into(),
+        Err(message) => syn::Error::new(path.span(), message)
+            .to_compile_error()
+            .into(),
+    }
+}
+
+// documented where it's re-exported, in the crate `led-matrix`
+#[proc_macro]
+pub fn include_font(input: TokenStream) -> TokenStream {
+    let path = parse_macro_input!(input as LitStr);
+    match include_font_impl(&path) {
+        Ok(tokens) => tokens.into(),
+        Err(message) => syn::Error::new(path.span(), message)
+            .to_compile_error()
+            .into(),
+    }
+}
+
+/// Resolve a path relative to the package invoking the macro, like the paths
+/// in Cargo.toml.
+fn resolve(path: &LitStr) -> Result<PathBuf, String> {
+    let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR")
+        .ok_or("CARGO_MANIFEST_DIR is not set, the macro must be invoked by cargo")?;
+    Ok(PathBuf::from(manifest_dir).join(path.value()))
+}
+
+fn include_image_impl(path: &LitStr) -> Result<proc_macro2::TokenStream, String> {
+    let full_path = resolve(path)?;
+
+    let image = image::open(&full_path)
+        .map_err(|error| format!("failed to load {}: {error}", full_path.display()))?
+        .into_rgba8();
+    let (width, height) = (image.width() as usize, image.height() as usize);
+
+    // The first row of the pixel data is the top row of the image. The alpha
+    // channel is only included if the image is actually transparent.
+    let opaque = image.pixels().all(|pixel| pixel[3] == 255);
+    let (constructor, data) = if opaque {
+        let data = image
+            .pixels()
+            .flat_map(|pixel| [pixel[0], pixel[1], pixel[2]])
+            .collect();
+        (quote!(from_rgb), data)
+    } else {
+        (quote!(from_rgba), image.into_raw())
+    };
+    let data = LitByteStr::new(&data, path.span());
+
+    let full_path = full_path
+        .to_str()
+        .ok_or_else(|| format!("path is not valid UTF-8: {}", full_path.display()))?;
+    Ok(quote! {
+        {
+            // makes cargo rebuild the invoking crate when the image changes
+            const _: &[u8] = include_bytes!(#full_path);
+            ::led_matrix::bitmap::Bitmap::#constructor(#width, #height, #data)
+        }
+    })
+}
+
+fn include_font_impl(path: &LitStr) -> Result<proc_macro2::TokenStream, String> {
+    let full_path = resolve(path)?;
+    let source = std::fs::read_to_string(&full_path)
+        .map_err(|error| format!("failed to load {}: {error}", full_path.display()))?;
+    let font = bdf::parse(&source)
+        .map_err(|error| format!("failed to load {}: {error}", full_path.display()))?;
+    // the columns of scrolling text are stored as bit masks
+    if font.height > 32 {
+        return Err(format!(
+            "the font is {} pixels high, at most 32 are supported",
+            font.height
+        ));
+    }
+
+    // The advance includes the empty column between two glyphs, which the
+    // crate adds itself.
+    let width = |glyph: &bdf::Glyph| {
+        let right = glyph.pixels.iter().map(|&(x, _)| x + 1).max().unwrap_or(0);
+        glyph.advance.saturating_sub(1).max(right)
+    };
+    let character = |pixels: &[(usize, usize)], width: usize| {
+        let (x, y): (Vec<_>, Vec<_>) = pixels.iter().copied().unzip();
+        quote!(::led_matrix::character::Character::new(&[#((#x, #y)),*], #width))
+    };
+
+    let chars = font.glyphs.iter().map(|glyph| glyph.char);
+    let characters = font
+        .glyphs
+        .iter()
+        .map(|glyph| character(&glyph.pixels, width(glyph)));
+    let replacement = match font
+        .glyphs
+        .iter()
+        .find(|glyph| Some(glyph.char) == font.default_char)
+    {
+        Some(glyph) => character(&glyph.pixels, width(glyph)),
+        None => {
+            // a hollow box from the baseline to the top of the line
+            let box_width = font.glyphs.iter().map(width).max().unwrap_or(1).max(1);
+            let (bottom, top) = (font.descent, font.height.max(font.descent + 1) - 1);
+            let pixels: Vec<_> = (bottom..=top)
+                .flat_map(|y| (0..box_width).map(move |x| (x, y)))
+                .filter(|&(x, y)| x == 0 || x == box_width - 1 || y == bottom || y == top)
+                .collect();
+            character(&pixels, box_width)
+        }
+    };
+    let height = font.height;
+
+    let full_path = full_path
+        .to_str()
+        .ok_or_else(|| format!("path is not valid UTF-8: {}", full_path.display()))?;
+    Ok(quote! {
+        {
+            // makes cargo rebuild the invoking crate when the font changes
+            const _: &[u8] = include_bytes!(#full_path);
+            fn lookup(value: char) -> ::core::option::Option<::led_matrix::character::Character> {
+                match value {
+                    #(#chars => ::core::option::Option::Some(#characters),)*
+                    _ => ::core::option::Option::None,
+                }
+            }
+            ::led_matrix::character::Font::new(lookup, #height, #replacement)
+        }
+    })
+}
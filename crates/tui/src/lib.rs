@@ -1,5 +1,7 @@
 use std::{
+    cell::RefCell,
     io::{stdout, Stdout},
+    rc::Rc,
     time::Duration,
 };
 
@@ -8,48 +10,102 @@ use crossterm::{
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
     ExecutableCommand,
 };
-use led_matrix_core::{JoystickPosition, HEIGHT, WIDTH};
+use led_matrix_core::{
+    correction::Gamma,
+    power::{BrightnessOverlay, PowerLimit},
+    JoystickPosition, Tile,
+};
 use ratatui::{
     backend::CrosstermBackend,
-    layout::{Position, Rect},
+    layout::{Alignment, Position, Rect},
     style::{Color, Stylize},
     text::Text,
     widgets::{Block, Paragraph},
     Terminal,
 };
 
-pub struct LedMatrix {
+pub struct LedMatrix<const W: usize = 8, const H: usize = 8> {
+    screen: Rc<RefCell<Screen<W, H>>>,
+    panel: usize,
+
+    // simulated when the LEDs are drawn
+    brightness: u8,
+    gamma: Gamma,
+    power_limit: PowerLimit,
+
+    leds: [[(u8, u8, u8); W]; H],
+}
+
+/// State shared by all panels, which are drawn on the same terminal.
+struct Screen<const W: usize, const H: usize> {
     terminal: Terminal<CrosstermBackend<Stdout>>,
+    tiles: Vec<Tile>,
+    panels: Vec<[[(u8, u8, u8); W]; H]>,
+    // brightness of the most recently applied panel, as set by the program
+    // and after applying the power limit
+    brightness: BrightnessOverlay,
+    show_brightness: bool,
+    // whether a panel was applied since the terminal was drawn
+    dirty: bool,
 
     joystick_position: JoystickPosition,
     joystick_pressed: bool,
     switch: bool,
+}
 
-    leds: [[(u8, u8, u8); WIDTH as usize]; HEIGHT as usize],
+pub fn run<const W: usize, const H: usize, F: FnOnce(LedMatrix<W, H>) + Send + 'static>(f: F) -> ! {
+    run_tiled([Tile::default()], |[matrix]| f(matrix))
 }
 
-pub fn run<F: FnOnce(LedMatrix) + Send + 'static>(f: F) -> ! {
+/// Emulate several panels, which are displayed together according to the
+/// given tiles.
+pub fn run_tiled<
+    const W: usize,
+    const H: usize,
+    const N: usize,
+    F: FnOnce([LedMatrix<W, H>; N]) + Send + 'static,
+>(
+    tiles: [Tile; N],
+    f: F,
+) -> ! {
     stdout().execute(EnterAlternateScreen).unwrap();
     enable_raw_mode().unwrap();
     let mut terminal = Terminal::new(CrosstermBackend::new(stdout())).unwrap();
     terminal.clear().unwrap();
 
-    let matrix = LedMatrix {
+    let screen = Rc::new(RefCell::new(Screen {
         terminal,
+        tiles: tiles.to_vec(),
+        panels: vec![[[(0, 0, 0); W]; H]; N],
+        brightness: BrightnessOverlay {
+            brightness: 255,
+            limited: 255,
+        },
+        show_brightness: false,
+        dirty: false,
         joystick_position: JoystickPosition::Center,
         joystick_pressed: false,
         switch: false,
-        leds: Default::default(),
-    };
+    }));
+
+    let matrices = core::array::from_fn(|panel| LedMatrix {
+        screen: Rc::clone(&screen),
+        panel,
+        brightness: 255,
+        gamma: Gamma::LINEAR,
+        power_limit: PowerLimit::USB,
+        leds: [[(0, 0, 0); W]; H],
+    });
+    drop(screen);
 
-    f(matrix);
+    f(matrices);
 
     // necessary to make the run function non-terminating
     #[allow(clippy::empty_loop)]
     loop {}
 }
 
-impl LedMatrix {
+impl<const W: usize, const H: usize> Screen<W, H> {
     // Process available events from crossterm and update internal state
     // accordingly. Do this frequently so quitting the app is snappy.
     fn poll_event(&mut self) {
@@ -65,6 +121,7 @@ impl LedMatrix {
                     KeyCode::Right => self.joystick_position = JoystickPosition::Right,
                     KeyCode::Char(' ') => self.joystick_pressed = true,
                     KeyCode::Enter => self.switch = !self.switch,
+                    KeyCode::Char('b' | 'B') => self.show_brightness = !self.show_brightness,
                     KeyCode::Char('r') => {
                         self.joystick_position = JoystickPosition::Center;
                         self.joystick_pressed = false;
@@ -74,64 +131,131 @@ impl LedMatrix {
             }
         }
     }
-}
 
-impl Drop for LedMatrix {
-    fn drop(&mut self) {
-        stdout().execute(LeaveAlternateScreen).unwrap();
-        disable_raw_mode().unwrap();
-    }
-}
+    fn draw(&mut self) {
+        self.dirty = false;
+        let Self {
+            tiles,
+            panels,
+            brightness,
+            show_brightness,
+            ..
+        } = self;
 
-impl led_matrix_core::LedMatrixCore for LedMatrix {
-    fn apply(&mut self) {
-        self.poll_event();
+        // size of the canvas covered by all panels together
+        let (width, height) = tiles.iter().fold((0, 0), |(width, height), tile| {
+            let (w, h) = tile.rotation.footprint((W, H));
+            (width.max(tile.x + w), height.max(tile.y + h))
+        });
 
         self.terminal
             .draw(|frame| {
                 let size = frame.size();
-                if !size.contains(Position::new(15, 8)) {
+                // Every pixel is twice as wide as it is high, to make it
+                // roughly square. The first two lines contain the help text.
+                let fits = |pixel_size: u16| {
+                    size.contains(Position::new(
+                        width as u16 * 2 * pixel_size - 1,
+                        height as u16 * pixel_size + 1,
+                    ))
+                };
+                if !fits(1) {
                     frame.render_widget(Paragraph::new("terminal is too small"), size);
                     return;
                 }
-                let pixel_size = match () {
-                    _ if size.contains(Position::new(79, 41)) => 5,
-                    _ if size.contains(Position::new(63, 33)) => 4,
-                    _ if size.contains(Position::new(47, 25)) => 3,
-                    _ if size.contains(Position::new(31, 17)) => 2,
-                    _ => 1,
-                };
+                let pixel_size = (1..=5).rev().find(|&p| fits(p)).unwrap_or(1);
 
                 let area = Rect::new(0, 0, size.width, 2);
                 frame.render_widget(
-                    Text::raw("joystick: move: arrows, press: space, release: space\nswitch: enter, quit: Q"),
+                    Text::raw("joystick: move: arrows, press: space, release: space\nswitch: enter, brightness: B, quit: Q"),
                     area,
                 );
-                for (i, row) in self.leds.iter().enumerate() {
-                    for (j, led) in row.iter().enumerate() {
-                        let area = Rect::new(
-                            j as u16 * 2 * pixel_size,
-                            i as u16 * pixel_size + 2, // + 2 bcs of help text
-                            2 * pixel_size,
-                            pixel_size,
-                        );
-                        let (r, g, b) = *led;
-                        let color = ((r as u32) << 16) | ((g as u32) << 8) | (b as u32);
-                        frame.render_widget(Block::new().bg(Color::from_u32(color)), area);
+                if *show_brightness {
+                    frame.render_widget(
+                        Paragraph::new(brightness.to_string()).alignment(Alignment::Right),
+                        Rect::new(0, 1, size.width, 1),
+                    );
+                }
+                for (tile, leds) in tiles.iter().zip(panels.iter()) {
+                    let (w, h) = tile.rotation.footprint((W, H));
+                    for x in tile.x..tile.x + w {
+                        for y in tile.y..tile.y + h {
+                            let (px, py) = tile.to_panel((x, y), (W, H)).unwrap();
+                            let area = Rect::new(
+                                x as u16 * 2 * pixel_size,
+                                // the canvas has its origin in the lower left
+                                // corner, + 2 bcs of help text
+                                (height - y - 1) as u16 * pixel_size + 2,
+                                2 * pixel_size,
+                                pixel_size,
+                            );
+                            let (r, g, b) = leds[H - py - 1][px];
+                            let color = ((r as u32) << 16) | ((g as u32) << 8) | (b as u32);
+                            frame.render_widget(Block::new().bg(Color::from_u32(color)), area);
+                        }
                     }
                 }
             })
             .unwrap();
+    }
+}
+
+impl<const W: usize, const H: usize> Drop for Screen<W, H> {
+    fn drop(&mut self) {
+        stdout().execute(LeaveAlternateScreen).unwrap();
+        disable_raw_mode().unwrap();
+    }
+}
 
-        self.poll_event();
+impl<const W: usize, const H: usize> led_matrix_core::LedMatrixCore<W, H> for LedMatrix<W, H> {
+    fn apply(&mut self) {
+        let mut screen = self.screen.borrow_mut();
+        screen.poll_event();
+        let limited_brightness = self.power_limit.limit_brightness(
+            self.leds
+                .iter()
+                .flatten()
+                .map(|&led| self.gamma.correct(led)),
+            self.brightness,
+        );
+        screen.panels[self.panel] = self
+            .leds
+            .map(|row| row.map(|led| self.gamma.emulate(led, limited_brightness)));
+        screen.brightness = BrightnessOverlay {
+            brightness: self.brightness,
+            limited: limited_brightness,
+        };
+        // The panels of a tiled matrix are applied one after another, so the
+        // terminal is only drawn once all of them are. Panels which are
+        // applied on their own are drawn when the program sleeps.
+        screen.dirty = true;
+        if self.panel == screen.panels.len() - 1 {
+            screen.draw();
+        }
+        screen.poll_event();
+    }
+
+    fn set_brightness(&mut self, brightness: u8) {
+        self.brightness = brightness
     }
 
-    fn set_brightness(&mut self, _brightness: u8) {}
+    fn set_gamma(&mut self, gamma: Gamma) {
+        self.gamma = gamma
+    }
+
+    fn set_power_limit(&mut self, limit: PowerLimit) {
+        self.power_limit = limit
+    }
 
     fn sleep_ms(&mut self, duration: u32) {
-        self.poll_event();
+        let mut screen = self.screen.borrow_mut();
+        if screen.dirty {
+            screen.draw();
+        }
+        screen.poll_event();
+        drop(screen);
         std::thread::sleep(std::time::Duration::from_millis(duration.into()));
-        self.poll_event();
+        self.screen.borrow_mut().poll_event();
     }
 
     fn get_sin(&self) -> fn(f32) -> f32 {
@@ -139,34 +263,37 @@ impl led_matrix_core::LedMatrixCore for LedMatrix {
     }
 
     fn joystick_position(&mut self) -> JoystickPosition {
-        self.poll_event();
-        self.joystick_position
+        let mut screen = self.screen.borrow_mut();
+        screen.poll_event();
+        screen.joystick_position
     }
 
     fn switch(&mut self) -> bool {
-        self.poll_event();
-        self.switch
+        let mut screen = self.screen.borrow_mut();
+        screen.poll_event();
+        screen.switch
     }
 
     fn joystick_pressed(&mut self) -> bool {
-        self.poll_event();
-        self.joystick_pressed
+        let mut screen = self.screen.borrow_mut();
+        screen.poll_event();
+        screen.joystick_pressed
     }
 }
 
-impl core::ops::Index<(usize, usize)> for LedMatrix {
+impl<const W: usize, const H: usize> core::ops::Index<(usize, usize)> for LedMatrix<W, H> {
     type Output = (u8, u8, u8);
 
     fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
-        assert!((0..HEIGHT as usize).contains(&x));
-        assert!((0..WIDTH as usize).contains(&y));
-        &self.leds[HEIGHT as usize - y - 1][x]
+        assert!((0..W).contains(&x));
+        assert!((0..H).contains(&y));
+        &self.leds[H - y - 1][x]
     }
 }
-impl core::ops::IndexMut<(usize, usize)> for LedMatrix {
+impl<const W: usize, const H: usize> core::ops::IndexMut<(usize, usize)> for LedMatrix<W, H> {
     fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
-        assert!((0..HEIGHT as usize).contains(&x));
-        assert!((0..WIDTH as usize).contains(&y));
-        &mut self.leds[HEIGHT as usize - y - 1][x]
+        assert!((0..W).contains(&x));
+        assert!((0..H).contains(&y));
+        &mut self.leds[H - y - 1][x]
     }
 }
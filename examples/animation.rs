@@ -0,0 +1,20 @@
+#![no_std]
+#![cfg_attr(target_os = "none", no_main)]
+
+#[cfg(target_os = "none")]
+use panic_halt as _;
+
+use led_matrix::animation::Gif;
+
+static SPINNER: &[u8] = include_bytes!("../bitmaps/spinner.gif");
+
+#[cfg_attr(target_os = "none", rp_pico::entry)]
+fn main() -> ! {
+    led_matrix::run(app);
+}
+
+fn app(matrix: &mut dyn led_matrix::LedMatrix) {
+    let spinner = Gif::parse(SPINNER).unwrap();
+    // the spinner repeats forever
+    spinner.play(matrix);
+}
@@ -4,7 +4,10 @@
 #[cfg(target_os = "none")]
 use panic_halt as _;
 
-use led_matrix::billboard::{vertical, Billboard};
+use led_matrix::{
+    billboard::{vertical, Billboard},
+    marquee::{Direction, Marquee, Repeat},
+};
 
 static ZIG_ZAG: Billboard = &vertical([
     *b"#       ",
@@ -21,14 +24,6 @@ static ZIG_ZAG: Billboard = &vertical([
     *b"   #    ",
     *b"  #     ",
     *b" #      ",
-    *b"#       ",
-    *b" #      ",
-    *b"  #     ",
-    *b"   #    ",
-    *b"    #   ",
-    *b"     #  ",
-    *b"      # ",
-    *b"       #",
 ]);
 
 #[cfg_attr(target_os = "none", rp_pico::entry)]
@@ -37,11 +32,12 @@ fn main() -> ! {
 }
 
 fn app(matrix: &mut dyn led_matrix::LedMatrix) {
-    loop {
-        for offset in 0..ZIG_ZAG.len() {
-            matrix.draw_vertical_billboard_frame(ZIG_ZAG, offset);
-            matrix.apply();
-            matrix.sleep_ms(100);
-        }
-    }
+    // the zig zag continues seamlessly at its start
+    let mut marquee = Marquee::new(ZIG_ZAG.len(), 8)
+        .with_speed(100)
+        .with_repeat(Repeat::Loop)
+        .with_direction(Direction::Up);
+    marquee.run(matrix, |marquee, matrix| {
+        marquee.draw_vertical_billboard(matrix, ZIG_ZAG)
+    });
 }
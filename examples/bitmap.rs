@@ -4,7 +4,9 @@
 #[cfg(target_os = "none")]
 use panic_halt as _;
 
-static SOME_BITMAPS: &[&[u8]] = &[
+use led_matrix::bitmap::{Bitmap, Transparency, BIG_IMG};
+
+static SOME_BITMAPS: &[&Bitmap] = &[
     led_matrix::bitmap::APPLE,
     led_matrix::bitmap::BAT,
     led_matrix::bitmap::BIRD,
@@ -31,5 +33,13 @@ fn app(matrix: &mut dyn led_matrix::LedMatrix) {
             matrix.apply();
             matrix.sleep_ms(1_000)
         }
+
+        // pan diagonally across an image which is larger than the matrix
+        let max_offset = BIG_IMG.width().min(BIG_IMG.height()) as i32 - 8;
+        for offset in (0..=max_offset).chain((0..max_offset).rev()) {
+            matrix.draw_bitmap_at(BIG_IMG, (-offset, -offset), Transparency::Opaque);
+            matrix.apply();
+            matrix.sleep_ms(150)
+        }
     }
 }
@@ -6,7 +6,7 @@ use panic_halt as _;
 
 use core::convert::From;
 
-use led_matrix::all_led_coordinates;
+use led_matrix::{all_led_coordinates, color::Color};
 
 #[cfg_attr(target_os = "none", rp_pico::entry)]
 fn main() -> ! {
@@ -38,12 +38,8 @@ fn app(matrix: &mut dyn led_matrix::LedMatrix) {
             let sin_01 = (sin_11 + 1.0) * 0.5;
 
             let hue = 360.0 * sin_01;
-            let sat = 1.0;
-            let val = 1.0;
 
-            let rgb = hsv2rgb_u8(hue, sat, val);
-
-            matrix[(x, y)] = rgb;
+            matrix[(x, y)] = Color::from_hsv(hue, 1.0, 1.0).into();
         }
         matrix.apply();
         matrix.sleep_ms(16);
@@ -56,35 +52,3 @@ fn app(matrix: &mut dyn led_matrix::LedMatrix) {
         }
     }
 }
-
-fn hsv2rgb(hue: f32, sat: f32, val: f32) -> (f32, f32, f32) {
-    let c = val * sat;
-    let v = (hue / 60.0) % 2.0 - 1.0;
-    let v = if v < 0.0 { -v } else { v };
-    let x = c * (1.0 - v);
-    let m = val - c;
-    let (r, g, b) = if hue < 60.0 {
-        (c, x, 0.0)
-    } else if hue < 120.0 {
-        (x, c, 0.0)
-    } else if hue < 180.0 {
-        (0.0, c, x)
-    } else if hue < 240.0 {
-        (0.0, x, c)
-    } else if hue < 300.0 {
-        (x, 0.0, c)
-    } else {
-        (c, 0.0, x)
-    };
-    (r + m, g + m, b + m)
-}
-
-fn hsv2rgb_u8(h: f32, s: f32, v: f32) -> (u8, u8, u8) {
-    let r = hsv2rgb(h, s, v);
-
-    (
-        (r.0 * 255.0) as u8,
-        (r.1 * 255.0) as u8,
-        (r.2 * 255.0) as u8,
-    )
-}
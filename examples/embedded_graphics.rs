@@ -0,0 +1,48 @@
+#![no_std]
+#![cfg_attr(target_os = "none", no_main)]
+
+#[cfg(target_os = "none")]
+use panic_halt as _;
+
+use embedded_graphics::{
+    mono_font::{ascii::FONT_5X8, MonoTextStyle},
+    pixelcolor::Rgb888,
+    prelude::*,
+    primitives::{Circle, PrimitiveStyle},
+    text::{Baseline, Text},
+};
+use led_matrix::graphics::Canvas;
+
+#[cfg_attr(target_os = "none", rp_pico::entry)]
+fn main() -> ! {
+    led_matrix::run(app);
+}
+
+fn app(matrix: &mut dyn led_matrix::LedMatrix) {
+    let mut canvas = Canvas::new(matrix);
+
+    let message = " Hello, embedded-graphics! ";
+    let style = MonoTextStyle::new(&FONT_5X8, Rgb888::CYAN);
+    let width = (message.len() * 5) as i32;
+
+    loop {
+        for offset in 0..width {
+            canvas.clear(Rgb888::BLACK).unwrap();
+            Text::with_baseline(message, Point::new(-offset, 0), style, Baseline::Top)
+                .draw(&mut canvas)
+                .unwrap();
+            canvas.matrix().apply();
+            canvas.matrix().sleep_ms(60);
+        }
+
+        for diameter in (1..=8).chain((1..8).rev()) {
+            canvas.clear(Rgb888::BLACK).unwrap();
+            Circle::with_center(Point::new(4, 4), diameter)
+                .into_styled(PrimitiveStyle::with_fill(Rgb888::MAGENTA))
+                .draw(&mut canvas)
+                .unwrap();
+            canvas.matrix().apply();
+            canvas.matrix().sleep_ms(100);
+        }
+    }
+}
@@ -4,8 +4,10 @@
 use led_matrix::{
     billboard::{horizontal, Billboard},
     bitmap,
-    character::convert_str,
-    color, JoystickPosition,
+    character::Style,
+    color, convert_str,
+    marquee::Marquee,
+    JoystickPosition,
 };
 #[cfg(target_os = "none")]
 use panic_halt as _;
@@ -60,21 +62,20 @@ fn app(matrix: &mut dyn led_matrix::LedMatrix) {
         }
 
         // run some text across the screen
-        let (billboard, length) = &convert_str(*b" REWRITE IT IN RUST ");
-        for offset in 0..*length {
-            matrix.draw_text_billboard_frame(billboard, offset);
-            matrix.apply();
-            matrix.sleep_ms(50);
-        }
+        let (text, length) = &convert_str!("REWRITE IT IN RUST");
+        let style = Style::color(color::WHITE);
+        Marquee::new(*length, 8).run(matrix, |marquee, matrix| {
+            marquee.draw_text(matrix, text, &style)
+        });
         if matrix.switch() {
             continue 'main_loop;
         }
 
         // draw a custom-drawn billboard
-        for offset in 0..SHRUG_KAOMOJI.len() {
-            matrix.draw_horizontal_billboard_frame(SHRUG_KAOMOJI, offset);
-            matrix.apply();
-            matrix.sleep_ms(100);
-        }
+        Marquee::new(SHRUG_KAOMOJI.len(), 8)
+            .with_speed(100)
+            .run(matrix, |marquee, matrix| {
+                marquee.draw_horizontal_billboard(matrix, SHRUG_KAOMOJI)
+            });
     }
 }
@@ -0,0 +1,43 @@
+#![no_std]
+#![cfg_attr(target_os = "none", no_main)]
+
+#[cfg(target_os = "none")]
+use panic_halt as _;
+
+use led_matrix::{
+    bitmap::Transparency,
+    include_image,
+    sprite::{Animation, AnimationFrame, SpriteSheet},
+};
+
+// four frames of 8x8 pixels next to each other, with a transparent background
+static WALKER: SpriteSheet = SpriteSheet::grid(include_image!("bitmaps/walker.png"), (8, 8))
+    .with_transparency(Transparency::Alpha);
+
+static WALK: Animation = Animation::looping(&[
+    AnimationFrame::new(0, 150),
+    AnimationFrame::new(1, 100),
+    AnimationFrame::new(2, 150),
+    AnimationFrame::new(3, 100),
+]);
+
+const FRAME_MS: u32 = 50;
+
+#[cfg_attr(target_os = "none", rp_pico::entry)]
+fn main() -> ! {
+    led_matrix::run(app);
+}
+
+fn app(matrix: &mut dyn led_matrix::LedMatrix) {
+    let mut walk = WALK.start();
+    loop {
+        // walk in from the left and out on the right, one step every 4 frames
+        for step in -32i32..32 {
+            matrix.fill((0, 0, 30));
+            matrix.draw_sprite(&WALKER, walk.sprite(), (step.div_euclid(4), 0));
+            matrix.apply();
+            matrix.sleep_ms(FRAME_MS);
+            walk.advance(FRAME_MS);
+        }
+    }
+}
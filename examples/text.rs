@@ -4,7 +4,13 @@
 #[cfg(target_os = "none")]
 use panic_halt as _;
 
-use led_matrix::{bitmap, character::convert_str};
+use led_matrix::{
+    bitmap,
+    character::{self, rainbow, Font, Style, FONT_5X7},
+    color, include_font, text,
+};
+
+static DIGITS: Font = include_font!("fonts/digits-3x8.bdf");
 
 #[cfg_attr(target_os = "none", rp_pico::entry)]
 fn main() -> ! {
@@ -12,15 +18,39 @@ fn main() -> ! {
 }
 
 fn app(matrix: &mut dyn led_matrix::LedMatrix) {
-    let (billboard, length) = &convert_str(*b" REWRITE IT IN RUST ");
-
+    let mut score = 0u32;
     loop {
-        for offset in 0..*length {
-            matrix.draw_text_billboard_frame(billboard, offset);
+        for frame in character::scroll(text!("REWRITE IT IN RUST").chars(), &FONT_5X7) {
+            frame.draw_styled(matrix, &Style::shader(&rainbow));
+            matrix.apply();
+            matrix.sleep_ms(50);
+        }
+
+        // text which is only known at runtime
+        let mut x = 8;
+        loop {
+            matrix.clear();
+            let width = matrix.draw_text_fmt(
+                (x, 0),
+                format_args!("SCORE: {score}"),
+                &FONT_5X7,
+                color::GREEN,
+            );
             matrix.apply();
             matrix.sleep_ms(50);
+            if x + width as i32 <= 0 {
+                break;
+            }
+            x -= 1;
         }
 
+        // two digits of an imported font fit on the matrix
+        matrix.clear();
+        let args = format_args!("{:02}", score % 100);
+        matrix.draw_text_fmt((0, 0), args, &DIGITS, color::GREEN);
+        matrix.apply();
+        matrix.sleep_ms(2_000);
+
         for _ in 0..3 {
             matrix.draw_bitmap(bitmap::CRAB);
             matrix.apply();
@@ -29,5 +59,6 @@ fn app(matrix: &mut dyn led_matrix::LedMatrix) {
             matrix.apply();
             matrix.sleep_ms(500);
         }
+        score += 7;
     }
 }
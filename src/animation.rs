@@ -0,0 +1,742 @@
+//! Playback of animated GIFs.
+//!
+//! Parse a GIF file with [Gif::parse] and play it with [Gif::play]:
+//!
+//! ```
+//! use led_matrix::animation::Gif;
+//!
+//! # fn app(matrix: &mut dyn led_matrix::LedMatrix) {
+//! let gif = Gif::parse(include_bytes!("../bitmaps/spinner.gif")).unwrap();
+//! gif.play(matrix);
+//! # }
+//! ```
+//!
+//! Frames are decoded one at a time, directly onto the LED-matrix. Decoding
+//! doesn't allocate and needs about 12KB of stack, independent of the size
+//! of the GIF.
+//!
+//! GIF format: <https://www.w3.org/Graphics/GIF/spec-gif89a.txt>
+
+use crate::LedMatrix;
+
+/// A parsed GIF, which borrows the bytes of the file.
+///
+/// Like [draw_bitmap](crate::LedMatrix::draw_bitmap), the upper left corner
+/// of the GIF is drawn in the upper left corner of the matrix. Parts of the
+/// GIF which don't fit are clipped.
+#[derive(Debug, Clone, Copy)]
+pub struct Gif<'a> {
+    width: usize,
+    height: usize,
+    global_palette: &'a [u8],
+    loop_count: LoopCount,
+    // offset of the first block after the header and the global color table
+    blocks: usize,
+    bytes: &'a [u8],
+}
+
+/// How often an animation repeats, as specified by the Netscape application
+/// extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LoopCount {
+    /// The animation is played once, because it doesn't specify a loop count.
+    Once,
+    /// The animation is played once and then repeated this many times.
+    Repeat(u16),
+    /// The animation repeats forever.
+    Forever,
+}
+
+/// The reason why a GIF couldn't be parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GifError {
+    /// The data doesn't start with the signature "GIF87a" or "GIF89a".
+    NotAGif,
+    /// The data ends before the GIF is complete.
+    Truncated,
+    /// A block has an unknown type, given as its first byte.
+    InvalidBlock(u8),
+    /// The width or height of the GIF or of a frame is zero.
+    InvalidDimensions,
+    /// A frame has neither a local nor a global color table.
+    MissingColorTable,
+    /// The minimum code size of the compressed data isn't in `2..=8`.
+    InvalidCodeSize(u8),
+    /// The compressed data contains an invalid code.
+    InvalidData,
+    /// A pixel refers to a color which isn't in the color table.
+    InvalidColorIndex(u8),
+    /// The GIF doesn't contain any frames.
+    NoFrames,
+}
+
+impl core::fmt::Display for GifError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::NotAGif => write!(f, "not a GIF file"),
+            Self::Truncated => write!(f, "GIF file is truncated"),
+            Self::InvalidBlock(byte) => write!(f, "invalid block type: {byte:#04x}"),
+            Self::InvalidDimensions => write!(f, "invalid width or height"),
+            Self::MissingColorTable => write!(f, "frame without color table"),
+            Self::InvalidCodeSize(size) => write!(f, "invalid minimum code size: {size}"),
+            Self::InvalidData => write!(f, "invalid compressed data"),
+            Self::InvalidColorIndex(i) => write!(f, "color index {i} is not in the color table"),
+            Self::NoFrames => write!(f, "GIF without frames"),
+        }
+    }
+}
+
+impl<'a> Gif<'a> {
+    /// Parse the contents of a GIF file.
+    ///
+    /// All frames are decoded once to validate them, so drawing them
+    /// afterwards can't fail.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, GifError> {
+        if !matches!(bytes.get(..6), Some(b"GIF87a" | b"GIF89a")) {
+            return Err(GifError::NotAGif);
+        }
+        let width = read_u16(bytes, 6)? as usize;
+        let height = read_u16(bytes, 8)? as usize;
+        if width == 0 || height == 0 {
+            return Err(GifError::InvalidDimensions);
+        }
+        let flags = *bytes.get(10).ok_or(GifError::Truncated)?;
+        let global_palette = if flags & 0x80 != 0 {
+            slice(bytes, 13, palette_size(flags))?
+        } else {
+            &[]
+        };
+
+        let mut gif = Self {
+            width,
+            height,
+            global_palette,
+            loop_count: LoopCount::Once,
+            blocks: 13 + global_palette.len(),
+            bytes,
+        };
+
+        let mut pos = gif.blocks;
+        let mut frames = 0;
+        while let Some((frame, next)) = gif.next_frame(pos)? {
+            frame.validate()?;
+            pos = next;
+            frames += 1;
+        }
+        // playing a GIF without frames forever would never sleep
+        if frames == 0 {
+            return Err(GifError::NoFrames);
+        }
+        Ok(gif)
+    }
+
+    /// The width of the logical screen, which contains all frames.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// The height of the logical screen, which contains all frames.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// How often the animation repeats.
+    pub fn loop_count(&self) -> LoopCount {
+        self.loop_count
+    }
+
+    /// An iterator over the frames of the animation, in the order they are
+    /// displayed.
+    pub fn frames(&self) -> Frames<'a> {
+        Frames {
+            gif: *self,
+            pos: self.blocks,
+        }
+    }
+
+    /// Play the animation on the LED-matrix, as often as its
+    /// [loop count](Self::loop_count) specifies. If it repeats forever, this
+    /// function never returns.
+    ///
+    /// Every frame is displayed for its delay, which is waited for with
+    /// [sleep_ms](crate::LedMatrix::sleep_ms). The LED-matrix is cleared at
+    /// the start of every repetition.
+    pub fn play<const W: usize, const H: usize>(&self, matrix: &mut dyn LedMatrix<W, H>) {
+        let mut remaining = match self.loop_count {
+            LoopCount::Once => Some(1),
+            LoopCount::Repeat(count) => Some(u32::from(count) + 1),
+            LoopCount::Forever => None,
+        };
+        while remaining != Some(0) {
+            matrix.clear();
+            for frame in self.frames() {
+                let previous = match frame.disposal() {
+                    Disposal::Previous => Some(snapshot(matrix)),
+                    _ => None,
+                };
+                frame.draw(matrix);
+                matrix.apply();
+                matrix.sleep_ms(frame.delay_ms());
+                match (frame.disposal(), previous) {
+                    (Disposal::Background, _) => frame.clear(matrix),
+                    (Disposal::Previous, Some(previous)) => restore(matrix, &previous),
+                    _ => {}
+                }
+            }
+            remaining = remaining.map(|remaining| remaining - 1);
+        }
+    }
+
+    /// Parse the blocks starting at `pos` up to and including the next
+    /// frame. Returns the frame and the position after it, or `None` at the
+    /// end of the file.
+    fn next_frame(&mut self, mut pos: usize) -> Result<Option<(Frame<'a>, usize)>, GifError> {
+        let bytes = self.bytes;
+        let mut control = GraphicControl::default();
+        loop {
+            match bytes.get(pos).copied() {
+                // trailer, tolerate a missing one
+                Some(0x3b) | None => return Ok(None),
+                // extension
+                Some(0x21) => {
+                    let label = *bytes.get(pos + 1).ok_or(GifError::Truncated)?;
+                    let data = pos + 2;
+                    pos = skip_sub_blocks(bytes, data)?;
+                    match label {
+                        0xf9 => control = GraphicControl::parse(slice(bytes, data, 6)?),
+                        0xff => {
+                            if let Some(count) = parse_loop_count(bytes, data) {
+                                self.loop_count = count;
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                // image descriptor
+                Some(0x2c) => {
+                    let descriptor = slice(bytes, pos + 1, 9)?;
+                    let field = |i: usize| u16::from_le_bytes([descriptor[i], descriptor[i + 1]]);
+                    let flags = descriptor[8];
+                    let (width, height) = (field(4) as usize, field(6) as usize);
+                    if width == 0 || height == 0 {
+                        return Err(GifError::InvalidDimensions);
+                    }
+                    pos += 10;
+                    let palette = if flags & 0x80 != 0 {
+                        let palette = slice(bytes, pos, palette_size(flags))?;
+                        pos += palette.len();
+                        palette
+                    } else if !self.global_palette.is_empty() {
+                        self.global_palette
+                    } else {
+                        return Err(GifError::MissingColorTable);
+                    };
+                    let min_code_size = *bytes.get(pos).ok_or(GifError::Truncated)?;
+                    if !(2..=8).contains(&min_code_size) {
+                        return Err(GifError::InvalidCodeSize(min_code_size));
+                    }
+                    let data = pos + 1;
+                    pos = skip_sub_blocks(bytes, data)?;
+                    let frame = Frame {
+                        left: field(0) as usize,
+                        top: field(2) as usize,
+                        width,
+                        height,
+                        interlaced: flags & 0x40 != 0,
+                        palette,
+                        control,
+                        min_code_size,
+                        bytes,
+                        data,
+                    };
+                    return Ok(Some((frame, pos)));
+                }
+                Some(byte) => return Err(GifError::InvalidBlock(byte)),
+            }
+        }
+    }
+}
+
+/// An iterator over the frames of a [Gif], see [Gif::frames].
+#[derive(Debug, Clone)]
+pub struct Frames<'a> {
+    gif: Gif<'a>,
+    pos: usize,
+}
+
+impl<'a> Iterator for Frames<'a> {
+    type Item = Frame<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // the GIF was validated while parsing it
+        let (frame, pos) = self.gif.next_frame(self.pos).ok()??;
+        self.pos = pos;
+        Some(frame)
+    }
+}
+
+/// What happens to a frame after it has been displayed, before the next
+/// frame is drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Disposal {
+    /// The frame stays and the next frame is drawn on top of it.
+    Keep,
+    /// The area of the frame is cleared.
+    Background,
+    /// The area of the frame is restored to what it was before the frame was
+    /// drawn.
+    Previous,
+}
+
+/// A single frame of a [Gif].
+///
+/// To play the frames manually, draw a frame with [draw](Self::draw), wait
+/// for its [delay](Self::delay_ms) and dispose of it according to its
+/// [disposal](Self::disposal) method. [Gif::play] does exactly that.
+#[derive(Debug, Clone, Copy)]
+pub struct Frame<'a> {
+    // position and size on the logical screen, the origin is in the upper
+    // left corner
+    left: usize,
+    top: usize,
+    width: usize,
+    height: usize,
+    interlaced: bool,
+    palette: &'a [u8],
+    control: GraphicControl,
+    min_code_size: u8,
+    bytes: &'a [u8],
+    // offset of the first sub-block of compressed data
+    data: usize,
+}
+
+impl Frame<'_> {
+    /// How long the frame is displayed, in milliseconds.
+    ///
+    /// Like web browsers, delays of 0 and 10ms are treated as 100ms, because
+    /// many GIFs rely on that.
+    pub fn delay_ms(&self) -> u32 {
+        match self.control.delay {
+            0 | 1 => 100,
+            delay => u32::from(delay) * 10,
+        }
+    }
+
+    /// What happens to the frame after it has been displayed.
+    pub fn disposal(&self) -> Disposal {
+        self.control.disposal
+    }
+
+    /// Draw the frame onto the LED-matrix. Transparent pixels are left
+    /// untouched.
+    pub fn draw<const W: usize, const H: usize>(&self, matrix: &mut dyn LedMatrix<W, H>) {
+        let transparent = self.control.transparent;
+        // the frame was validated while parsing the GIF
+        let _ = self.decode(&mut |(x, y), index| {
+            if Some(index) == transparent {
+                return;
+            }
+            if let Some(position) = to_matrix::<W, H>((x, y)) {
+                let i = index as usize * 3;
+                matrix[position] = (self.palette[i], self.palette[i + 1], self.palette[i + 2]);
+            }
+        });
+    }
+
+    /// Turn off all LEDs in the area of the frame, which is how
+    /// [Disposal::Background] is implemented.
+    pub fn clear<const W: usize, const H: usize>(&self, matrix: &mut dyn LedMatrix<W, H>) {
+        for y in self.top..self.top + self.height {
+            for x in self.left..self.left + self.width {
+                if let Some(position) = to_matrix::<W, H>((x, y)) {
+                    matrix[position] = (0, 0, 0);
+                }
+            }
+        }
+    }
+
+    fn validate(&self) -> Result<(), GifError> {
+        let colors = self.palette.len() / 3;
+        let mut invalid = None;
+        self.decode(&mut |_, index| {
+            if index as usize >= colors && Some(index) != self.control.transparent {
+                invalid = Some(index);
+            }
+        })?;
+        match invalid {
+            Some(index) => Err(GifError::InvalidColorIndex(index)),
+            None => Ok(()),
+        }
+    }
+
+    /// Decode the compressed data and pass every pixel to `pixel`, as its
+    /// position on the logical screen and its color index.
+    ///
+    /// The pixels aren't passed in order.
+    fn decode(&self, pixel: &mut dyn FnMut((usize, usize), u8)) -> Result<(), GifError> {
+        let count = self.width * self.height;
+        let mut emit = |i: usize, index: u8| {
+            if i < count {
+                let row = match self.interlaced {
+                    true => interlaced_row(i / self.width, self.height),
+                    false => i / self.width,
+                };
+                pixel((self.left + i % self.width, self.top + row), index);
+            }
+        };
+
+        let mut codes = CodeReader::new(self.bytes, self.data);
+        let clear = 1u16 << self.min_code_size;
+        let end = clear + 1;
+        // every code is a string of color indices, which is its prefix (a
+        // code) followed by its suffix (a color index)
+        let mut prefix = [0u16; 4096];
+        let mut suffix = [0u8; 4096];
+        for (i, suffix) in suffix.iter_mut().enumerate().take(clear as usize) {
+            *suffix = i as u8;
+        }
+        let (mut code_size, mut next) = (self.min_code_size + 1, end + 1);
+        let mut previous = None;
+        let mut pos = 0;
+
+        // the string of a code is written back to front, so it has to be
+        // walked to find its length first
+        let walk = |mut code: u16, prefix: &[u16; 4096]| {
+            let mut len = 1;
+            while code > end {
+                code = prefix[code as usize];
+                len += 1;
+            }
+            (len, code as u8)
+        };
+
+        while pos < count {
+            // tolerate missing end codes
+            let Some(code) = codes.read(code_size) else {
+                break;
+            };
+            if code == clear {
+                (code_size, next, previous) = (self.min_code_size + 1, end + 1, None);
+                continue;
+            }
+            if code == end {
+                break;
+            }
+            if code > next || (code == next && previous.is_none()) {
+                return Err(GifError::InvalidData);
+            }
+            if let Some(previous) = previous {
+                // the table is full until the next clear code
+                if next < 4096 {
+                    let first = match code {
+                        code if code == next => walk(previous, &prefix).1,
+                        code => walk(code, &prefix).1,
+                    };
+                    prefix[next as usize] = previous;
+                    suffix[next as usize] = first;
+                    next += 1;
+                    if next == 1 << code_size && code_size < 12 {
+                        code_size += 1;
+                    }
+                }
+            }
+            let (len, _) = walk(code, &prefix);
+            let mut current = code;
+            for i in (pos..pos + len).rev() {
+                emit(i, suffix[current as usize]);
+                current = prefix[current as usize];
+            }
+            pos += len;
+            previous = Some(code);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct GraphicControl {
+    disposal: Disposal,
+    // in hundredths of a second
+    delay: u16,
+    transparent: Option<u8>,
+}
+
+impl Default for GraphicControl {
+    fn default() -> Self {
+        Self {
+            disposal: Disposal::Keep,
+            delay: 0,
+            transparent: None,
+        }
+    }
+}
+
+impl GraphicControl {
+    /// Parse the graphic control extension, starting with its block size.
+    fn parse(block: &[u8]) -> Self {
+        let flags = block[1];
+        Self {
+            disposal: match (flags >> 2) & 0b111 {
+                2 => Disposal::Background,
+                3 => Disposal::Previous,
+                // unspecified, do not dispose and reserved values
+                _ => Disposal::Keep,
+            },
+            delay: u16::from_le_bytes([block[2], block[3]]),
+            transparent: (flags & 1 != 0).then_some(block[4]),
+        }
+    }
+}
+
+/// Reads codes of variable size from a sequence of sub-blocks.
+struct CodeReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    // bytes left in the current sub-block
+    remaining: u8,
+    buffer: u32,
+    bits: u8,
+}
+
+impl<'a> CodeReader<'a> {
+    fn new(bytes: &'a [u8], pos: usize) -> Self {
+        Self {
+            bytes,
+            pos,
+            remaining: 0,
+            buffer: 0,
+            bits: 0,
+        }
+    }
+
+    /// Read the next code, or `None` at the end of the data.
+    fn read(&mut self, size: u8) -> Option<u16> {
+        while self.bits < size {
+            if self.remaining == 0 {
+                self.remaining = *self.bytes.get(self.pos)?;
+                self.pos += 1;
+                if self.remaining == 0 {
+                    return None;
+                }
+            }
+            self.buffer |= u32::from(*self.bytes.get(self.pos)?) << self.bits;
+            self.pos += 1;
+            self.remaining -= 1;
+            self.bits += 8;
+        }
+        let code = (self.buffer & ((1 << size) - 1)) as u16;
+        self.buffer >>= size;
+        self.bits -= size;
+        Some(code)
+    }
+}
+
+/// The loop count of a Netscape application extension, or `None` for other
+/// application extensions.
+fn parse_loop_count(bytes: &[u8], data: usize) -> Option<LoopCount> {
+    // the block size and identifier are followed by a sub-block with the ID 1
+    // and the count
+    let (identifier, count) = bytes.get(data..data + 16)?.split_at(12);
+    match (identifier, count) {
+        (b"\x0bNETSCAPE2.0" | b"\x0bANIMEXTS1.0", &[3, 1, low, high]) => {
+            Some(match u16::from_le_bytes([low, high]) {
+                0 => LoopCount::Forever,
+                count => LoopCount::Repeat(count),
+            })
+        }
+        _ => None,
+    }
+}
+
+/// The row of the `i`-th stored row of an interlaced image, which stores every 8th
+/// row first, then the rows in between and so on.
+fn interlaced_row(mut i: usize, height: usize) -> usize {
+    for (start, step) in [(0, 8), (4, 8), (2, 4), (1, 2)] {
+        let rows = height.saturating_sub(start).div_ceil(step);
+        if i < rows {
+            return start + i * step;
+        }
+        i -= rows;
+    }
+    i
+}
+
+/// The position on the matrix of a position on the logical screen, whose
+/// origin is in the upper left corner.
+fn to_matrix<const W: usize, const H: usize>((x, y): (usize, usize)) -> Option<(usize, usize)> {
+    (x < W && y < H).then(|| (x, H - y - 1))
+}
+
+fn snapshot<const W: usize, const H: usize>(
+    matrix: &dyn LedMatrix<W, H>,
+) -> [[(u8, u8, u8); W]; H] {
+    core::array::from_fn(|y| core::array::from_fn(|x| matrix[(x, y)]))
+}
+
+fn restore<const W: usize, const H: usize>(
+    matrix: &mut dyn LedMatrix<W, H>,
+    previous: &[[(u8, u8, u8); W]; H],
+) {
+    for (y, row) in previous.iter().enumerate() {
+        for (x, &led) in row.iter().enumerate() {
+            matrix[(x, y)] = led;
+        }
+    }
+}
+
+/// The size in bytes of the color table described by the given flags.
+fn palette_size(flags: u8) -> usize {
+    3 << ((flags & 0b111) + 1)
+}
+
+/// Skip a sequence of sub-blocks and return the position after it.
+fn skip_sub_blocks(bytes: &[u8], mut pos: usize) -> Result<usize, GifError> {
+    loop {
+        let len = *bytes.get(pos).ok_or(GifError::Truncated)? as usize;
+        pos += 1 + len;
+        if len == 0 {
+            return Ok(pos);
+        }
+    }
+}
+
+fn read_u16(bytes: &[u8], offset: usize) -> Result<u16, GifError> {
+    let bytes = slice(bytes, offset, 2)?;
+    Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+fn slice(bytes: &[u8], start: usize, len: usize) -> Result<&[u8], GifError> {
+    bytes.get(start..start + len).ok_or(GifError::Truncated)
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use led_matrix_headless::LedMatrix as Headless;
+    use std::vec::Vec;
+
+    const K: (u8, u8, u8) = (0, 0, 0);
+    const R: (u8, u8, u8) = (255, 0, 0);
+    const G: (u8, u8, u8) = (0, 255, 0);
+    const B: (u8, u8, u8) = (0, 0, 255);
+
+    /// A 4x4 GIF with a single frame. The code size grows from 3 to 4 bits,
+    /// is reset by a clear code after 12 pixels and grows again.
+    const CLEAR_CODE: &[u8] = &[
+        0x47, 0x49, 0x46, 0x38, 0x39, 0x61, 0x04, 0x00, 0x04, 0x00, 0x81, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0xff, 0x00, 0x00, 0x00, 0xff, 0x00, 0x00, 0x00, //
+        0xff, 0x2c, 0x00, 0x00, 0x00, 0x00, 0x04, 0x00, 0x04, 0x00, 0x00, 0x02, //
+        0x07, 0x8c, 0x6f, 0xa2, 0x42, 0x53, 0xa0, 0x00, 0x00, 0x3b,
+    ];
+
+    /// A 4x4 GIF with three frames:
+    /// 1. red everywhere, shown for 100ms
+    /// 2. a 2x2 frame at (1, 1) with green on the diagonal and the
+    ///    transparent index 3 elsewhere, shown for 200ms and disposed by
+    ///    restoring the previous content
+    /// 3. a single blue pixel in the upper left corner, shown for 300ms
+    const DISPOSAL: &[u8] = &[
+        0x47, 0x49, 0x46, 0x38, 0x39, 0x61, 0x04, 0x00, 0x04, 0x00, 0x81, 0x00, //
+        0x00, 0x00, 0x00, 0x00, 0xff, 0x00, 0x00, 0x00, 0xff, 0x00, 0x00, 0x00, //
+        0xff, 0x21, 0xf9, 0x04, 0x04, 0x0a, 0x00, 0x00, 0x00, 0x2c, 0x00, 0x00, //
+        0x00, 0x00, 0x04, 0x00, 0x04, 0x00, 0x00, 0x02, 0x04, 0x8c, 0x8f, 0x19, //
+        0x05, 0x00, 0x21, 0xf9, 0x04, 0x0d, 0x14, 0x00, 0x03, 0x00, 0x2c, 0x01, //
+        0x00, 0x01, 0x00, 0x02, 0x00, 0x02, 0x00, 0x00, 0x02, 0x03, 0xd4, 0x26, //
+        0x05, 0x00, 0x21, 0xf9, 0x04, 0x04, 0x1e, 0x00, 0x00, 0x00, 0x2c, 0x00, //
+        0x00, 0x00, 0x00, 0x01, 0x00, 0x01, 0x00, 0x00, 0x02, 0x02, 0x5c, 0x01, //
+        0x00, 0x3b,
+    ];
+
+    #[test]
+    fn decodes_across_clear_codes() {
+        let gif = Gif::parse(CLEAR_CODE).unwrap();
+        assert_eq!((gif.width(), gif.height()), (4, 4));
+        assert_eq!(gif.loop_count(), LoopCount::Once);
+        assert_eq!(gif.frames().count(), 1);
+
+        let mut matrix = Headless::<4, 4>::new();
+        gif.frames().next().unwrap().draw(&mut matrix);
+        matrix.apply();
+        // the rows are listed from the top, like in the GIF
+        assert_eq!(
+            matrix.last_frame().unwrap().leds,
+            [[R, R, R, R], [R, R, R, R], [G, G, G, G], [B, G, R, K]]
+        );
+    }
+
+    #[test]
+    fn plays_frames_with_transparency_and_disposal() {
+        let gif = Gif::parse(DISPOSAL).unwrap();
+        let disposals: [Disposal; 3] = {
+            let mut frames = gif.frames();
+            core::array::from_fn(|_| frames.next().unwrap().disposal())
+        };
+        assert_eq!(
+            disposals,
+            [Disposal::Keep, Disposal::Previous, Disposal::Keep]
+        );
+
+        let mut matrix = Headless::<4, 4>::new();
+        gif.play(&mut matrix);
+        let frames = matrix.frames();
+        assert_eq!(frames.len(), 3);
+        assert_eq!(
+            frames.iter().map(|frame| frame.time_ms).collect::<Vec<_>>(),
+            [0, 100, 300]
+        );
+        assert_eq!(frames[0].leds, [[R; 4]; 4]);
+        // transparent pixels keep the red of the first frame
+        assert_eq!(
+            frames[1].leds,
+            [[R, R, R, R], [R, G, R, R], [R, R, G, R], [R, R, R, R]]
+        );
+        // the second frame is gone again
+        assert_eq!(
+            frames[2].leds,
+            [[B, R, R, R], [R, R, R, R], [R, R, R, R], [R, R, R, R]]
+        );
+        assert_eq!(matrix.now_ms(), 600);
+    }
+
+    #[test]
+    fn frames_are_clipped_to_the_matrix() {
+        let gif = Gif::parse(CLEAR_CODE).unwrap();
+        let mut matrix = Headless::<2, 3>::new();
+        gif.frames().next().unwrap().draw(&mut matrix);
+        matrix.apply();
+        assert_eq!(matrix.last_frame().unwrap().leds, [[R, R], [R, R], [G, G]]);
+    }
+
+    #[test]
+    fn gif_without_frames_is_rejected() {
+        // the header and global color table of the fixtures, but no image
+        let mut bytes = DISPOSAL[..25].to_vec();
+        // a looping animation, which would otherwise never return from play
+        bytes.extend_from_slice(b"\x21\xff\x0bNETSCAPE2.0\x03\x01\x00\x00\x00");
+        bytes.push(0x3b);
+        assert_eq!(Gif::parse(&bytes).unwrap_err(), GifError::NoFrames);
+    }
+
+    #[test]
+    fn invalid_gifs_are_rejected() {
+        assert_eq!(Gif::parse(b"GIF88a").unwrap_err(), GifError::NotAGif);
+        assert_eq!(
+            Gif::parse(&CLEAR_CODE[..20]).unwrap_err(),
+            GifError::Truncated
+        );
+        let mut bytes = CLEAR_CODE.to_vec();
+        bytes[25] = 0x42;
+        assert_eq!(
+            Gif::parse(&bytes).unwrap_err(),
+            GifError::InvalidBlock(0x42)
+        );
+        let mut bytes = CLEAR_CODE.to_vec();
+        bytes[35] = 9;
+        assert_eq!(
+            Gif::parse(&bytes).unwrap_err(),
+            GifError::InvalidCodeSize(9)
+        );
+    }
+}
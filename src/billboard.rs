@@ -6,24 +6,73 @@
 //! The [Billboard] type alias specifies how such strips of pixel art are
 //! reperesented for the purpose of this module. You can construct both
 //! [horizontal] and [vertical] billboards.
+//!
+//! A [ColorBillboard] is a horizontal billboard in color, which is put
+//! together at runtime from text, bitmaps and pixel art:
+//!
+//! ```
+//! use led_matrix::{
+//!     billboard::{ColorBillboard, Palette},
+//!     character::{Style, FONT_5X7},
+//!     color,
+//!     marquee::Marquee,
+//! };
+//!
+//! static HEART: Palette = Palette::new(&[('r', color::RED), ('p', color::PINK), ('.', color::BLACK)]);
+//!
+//! # fn app(matrix: &mut dyn led_matrix::LedMatrix) {
+//! let mut billboard = ColorBillboard::<8>::new();
+//! billboard.push_text("I ", &FONT_5X7, &Style::color(color::WHITE)).unwrap();
+//! billboard
+//!     .push_pixel_art(
+//!         [
+//!             ".rr.rr.",
+//!             "rpprrrr",
+//!             "rprrrrr",
+//!             "rrrrrrr",
+//!             ".rrrrr.",
+//!             "..rrr..",
+//!             "...r...",
+//!             ".......",
+//!         ],
+//!         &HEART,
+//!     )
+//!     .unwrap();
+//! billboard.push_text(" RUST", &FONT_5X7, &Style::color(color::ORANGE)).unwrap();
+//!
+//! Marquee::new(billboard.len(), 8).run(matrix, |marquee, matrix| {
+//!     marquee.draw_color_billboard(matrix, billboard.columns())
+//! });
+//! # }
+//! ```
+
+use crate::{
+    bitmap::Bitmap,
+    character::{Font, Pixel, Style},
+    color, LedMatrix,
+};
 
 /// Data structure for billboard-style pixel art.
 ///
 /// A "billboard" is represented as a slice of arrays, where each array has
-/// length 8, because 8 is the "width" of the strip of pixel art. For horizontal
-/// billboards like text, such an array represents a column of pixels. For
-/// vertical billboards, it's a row.
+/// length `N`, because `N` is the "width" of the strip of pixel art. For
+/// horizontal billboards like text, such an array represents a column of
+/// pixels and `N` must match the height of the matrix. For vertical
+/// billboards, it's a row and `N` must match the width of the matrix.
+///
+/// `N` defaults to 8, the size of the regular LED-matrix.
 ///
-pub type Billboard = &'static [[bool; 8]];
+pub type Billboard<const N: usize = 8> = &'static [[bool; N]];
 
 /// Construct a horizontal strip of pixel art.
 ///
-/// The strip must be 8 pixels high. After construction, you can
+/// The strip must be as high as the matrix. After construction, you can
 /// draw the billboard with [draw_horizontal_billboard_frame](crate::LedMatrix::draw_horizontal_billboard_frame).
 ///
 /// # Examples
 ///
 /// ```
+/// # use led_matrix::billboard::{horizontal, Billboard};
 /// static ZIG_ZAG: Billboard = &horizontal([
 ///     *b"#             #             #       ",
 ///     *b" #           # #           # #      ",
@@ -36,18 +85,24 @@ pub type Billboard = &'static [[bool; 8]];
 /// ]);
 /// ```
 ///
+/// # Panics
+///
+/// Panics if the pixel art contains a byte other than `#` and a space. In a
+/// `static` or `const`, this is a compile error.
+///
 pub const fn horizontal<const L: usize, const W: usize>(billboard: [[u8; L]; W]) -> [[bool; W]; L] {
     transpose(vertical(billboard))
 }
 
 /// Construct a vertical strip of pixel art.
 ///
-/// The strip must be 8 pixels wide. After construction, you can
+/// The strip must be as wide as the matrix. After construction, you can
 /// draw the billboard with [draw_vertical_billboard_frame](crate::LedMatrix::draw_vertical_billboard_frame).
 ///
 /// # Examples
 ///
 /// ```
+/// # use led_matrix::billboard::{vertical, Billboard};
 /// static ZIG_ZAG: Billboard = &vertical([
 ///     *b"#       ",
 ///     *b" #      ",
@@ -74,6 +129,11 @@ pub const fn horizontal<const L: usize, const W: usize>(billboard: [[u8; L]; W])
 /// ]);
 /// ```
 ///
+/// # Panics
+///
+/// Panics if the pixel art contains a byte other than `#` and a space. In a
+/// `static` or `const`, this is a compile error.
+///
 pub const fn vertical<const L: usize, const W: usize>(billboard: [[u8; W]; L]) -> [[bool; W]; L] {
     let mut res = [[false; W]; L];
     let mut i = 0;
@@ -83,7 +143,7 @@ pub const fn vertical<const L: usize, const W: usize>(billboard: [[u8; W]; L]) -
             res[i][j] = match billboard[i][j] {
                 b'#' => true,
                 b' ' => false,
-                _ => panic!("invalid pixel art byte"),
+                _ => panic!("pixel art may only contain '#' and ' '"),
             };
             j += 1;
         }
@@ -108,3 +168,423 @@ const fn transpose<const N: usize, const M: usize>(data: [[bool; M]; N]) -> [[bo
     }
     res
 }
+
+/// Maps the characters of pixel art to colors, e.g. `'r'` to red.
+#[derive(Debug, Clone, Copy)]
+pub struct Palette<'a> {
+    colors: &'a [(char, (u8, u8, u8))],
+}
+
+impl<'a> Palette<'a> {
+    pub const fn new(colors: &'a [(char, (u8, u8, u8))]) -> Self {
+        Self { colors }
+    }
+
+    /// The color of a character, or `None` if it's not in the palette.
+    pub fn color(&self, c: char) -> Option<(u8, u8, u8)> {
+        self.colors
+            .iter()
+            .find_map(|&(key, color)| (key == c).then_some(color))
+    }
+}
+
+/// The reason why something couldn't be added to a [ColorBillboard].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BillboardError {
+    /// There isn't enough space left for the given number of columns.
+    Full(usize),
+    /// The rows of pixel art don't have the same length.
+    UnevenRows,
+    /// A character of pixel art isn't in the palette.
+    UnknownColor(char),
+}
+
+impl core::fmt::Display for BillboardError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Full(columns) => write!(f, "no space left for {columns} columns"),
+            Self::UnevenRows => write!(f, "the rows of pixel art have different lengths"),
+            Self::UnknownColor(c) => write!(f, "{c:?} is not in the palette"),
+        }
+    }
+}
+
+/// A column of a [ColorBillboard], with the top pixel first.
+pub type ColorColumn<const H: usize = 8> = [(u8, u8, u8); H];
+
+/// A horizontal billboard in color, which is built at runtime.
+///
+/// The columns are stored in a buffer for up to `L` columns, which are `H`
+/// pixels high, so no allocation is needed. Like in a [Billboard], the
+/// first pixel of a column is the top one.
+///
+/// Draw it with [draw_frame](Self::draw_frame), or let a
+/// [Marquee](crate::marquee::Marquee) scroll its [columns](Self::columns).
+/// See the [module documentation](self) for an example.
+#[derive(Debug, Clone)]
+pub struct ColorBillboard<const H: usize = 8, const L: usize = 128> {
+    columns: [ColorColumn<H>; L],
+    len: usize,
+}
+
+impl<const H: usize, const L: usize> ColorBillboard<H, L> {
+    /// An empty billboard.
+    pub const fn new() -> Self {
+        Self {
+            columns: [[color::BLACK; H]; L],
+            len: 0,
+        }
+    }
+
+    /// The number of columns.
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The maximum number of columns.
+    pub const fn capacity(&self) -> usize {
+        L
+    }
+
+    /// The columns added so far, from left to right.
+    pub fn columns(&self) -> &[ColorColumn<H>] {
+        &self.columns[..self.len]
+    }
+
+    /// Remove all columns.
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+
+    /// Make space for `count` black columns and return them.
+    fn reserve(&mut self, count: usize) -> Result<&mut [ColorColumn<H>], BillboardError> {
+        if count > L - self.len {
+            return Err(BillboardError::Full(count));
+        }
+        let columns = &mut self.columns[self.len..self.len + count];
+        columns.fill([color::BLACK; H]);
+        self.len += count;
+        Ok(columns)
+    }
+
+    /// Add a single column, with the top pixel first.
+    pub fn push(&mut self, column: ColorColumn<H>) -> Result<(), BillboardError> {
+        self.reserve(1)?[0] = column;
+        Ok(())
+    }
+
+    /// Add black columns, e.g. as space between two parts.
+    pub fn push_blank(&mut self, count: usize) -> Result<(), BillboardError> {
+        self.reserve(count).map(|_| ())
+    }
+
+    /// Add a [horizontal] billboard, whose lit pixels have the given color.
+    pub fn push_billboard(
+        &mut self,
+        billboard: &[[bool; H]],
+        color: (u8, u8, u8),
+    ) -> Result<(), BillboardError> {
+        let columns = self.reserve(billboard.len())?;
+        for (column, pixels) in columns.iter_mut().zip(billboard) {
+            for (pixel, &lit) in column.iter_mut().zip(pixels) {
+                if lit {
+                    *pixel = color;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Add pixel art, given as its rows from top to bottom. Every character
+    /// is a pixel, whose color is looked up in the palette.
+    ///
+    /// Nothing is added if the rows have different lengths, or if a
+    /// character isn't in the palette.
+    pub fn push_pixel_art(
+        &mut self,
+        rows: [&str; H],
+        palette: &Palette,
+    ) -> Result<(), BillboardError> {
+        let width = rows.first().map_or(0, |row| row.chars().count());
+        for row in rows {
+            if row.chars().count() != width {
+                return Err(BillboardError::UnevenRows);
+            }
+            if let Some(c) = row.chars().find(|&c| palette.color(c).is_none()) {
+                return Err(BillboardError::UnknownColor(c));
+            }
+        }
+        let columns = self.reserve(width)?;
+        for (y, row) in rows.iter().enumerate() {
+            for (column, c) in columns.iter_mut().zip(row.chars()) {
+                // every character was checked above
+                column[y] = palette.color(c).unwrap_or(color::BLACK);
+            }
+        }
+        Ok(())
+    }
+
+    /// Add text, standing on the bottom row like text drawn at the bottom of
+    /// the matrix. Like with [draw_styled_text](crate::LedMatrix::draw_styled_text),
+    /// the background of the style fills the height of the font, including
+    /// the empty columns between characters.
+    ///
+    /// The columns of the text start at 0 for a [shader](Style::shader).
+    pub fn push_text(
+        &mut self,
+        text: &str,
+        font: &Font,
+        style: &Style,
+    ) -> Result<(), BillboardError> {
+        let columns = self.reserve(font.text_width(text.chars()))?;
+        if let Some(background) = style.background() {
+            for column in columns.iter_mut() {
+                let top = H.saturating_sub(font.height());
+                column[top..].fill(background);
+            }
+        }
+        let mut left = 0;
+        for (index, c) in text.chars().enumerate() {
+            let character = font.glyph_or_replacement(c);
+            for &(x, y) in character.coordinates {
+                // glyphs may not fit into the billboard
+                if let (Some(column), true) = (columns.get_mut(left + x), y < H) {
+                    let pixel = Pixel {
+                        x: left + x,
+                        y,
+                        index,
+                    };
+                    column[H - y - 1] = style.foreground(pixel);
+                }
+            }
+            left += character.width + 1;
+        }
+        Ok(())
+    }
+
+    /// Add a bitmap, with its lower edge on the bottom row. Rows which don't
+    /// fit are cut off at the top, and the alpha channel is ignored.
+    pub fn push_bitmap(&mut self, bitmap: &Bitmap) -> Result<(), BillboardError> {
+        let columns = self.reserve(bitmap.width())?;
+        for ((x, y), (red, green, blue, _)) in bitmap.pixels() {
+            if y < H {
+                columns[x][H - y - 1] = (red, green, blue);
+            }
+        }
+        Ok(())
+    }
+
+    /// Draw a frame at a specified offset, like
+    /// [draw_horizontal_billboard_frame](crate::LedMatrix::draw_horizontal_billboard_frame).
+    pub fn draw_frame<const W: usize>(&self, matrix: &mut dyn LedMatrix<W, H>, offset: usize) {
+        for x in 0..W {
+            let column = offset.checked_add(x).and_then(|x| self.columns().get(x));
+            for y in 0..H {
+                matrix[(x, y)] = column.map_or(color::BLACK, |column| column[H - y - 1]);
+            }
+        }
+    }
+}
+
+impl<const H: usize, const L: usize> Default for ColorBillboard<H, L> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::character::FONT_3X3;
+    use std::{format, string::String, vec::Vec};
+
+    static PALETTE: Palette = Palette::new(&[
+        ('.', color::BLACK),
+        ('w', color::WHITE),
+        ('r', color::RED),
+        ('b', color::BLUE),
+    ]);
+
+    fn symbol(led: (u8, u8, u8)) -> char {
+        PALETTE
+            .colors
+            .iter()
+            .find_map(|&(c, color)| (color == led).then_some(c))
+            .unwrap_or('?')
+    }
+
+    /// The rows of a billboard from top to bottom, as pixel art in [PALETTE].
+    fn rows<const H: usize, const L: usize>(billboard: &ColorBillboard<H, L>) -> Vec<String> {
+        (0..H)
+            .map(|y| billboard.columns().iter().map(|c| symbol(c[y])).collect())
+            .collect()
+    }
+
+    #[test]
+    fn push_columns() {
+        let mut billboard = ColorBillboard::<2, 8>::new();
+        assert!(billboard.is_empty());
+        assert_eq!(billboard.capacity(), 8);
+        billboard.push([color::RED, color::WHITE]).unwrap();
+        billboard.push_blank(2).unwrap();
+        billboard.push([color::BLUE, color::BLACK]).unwrap();
+        assert_eq!(billboard.len(), 4);
+        assert_eq!(rows(&billboard), ["r..b", "w..."]);
+
+        billboard.clear();
+        assert!(billboard.is_empty());
+        // cleared columns are black again
+        billboard.push_blank(1).unwrap();
+        assert_eq!(rows(&billboard), [".", "."]);
+    }
+
+    #[test]
+    fn push_pixel_art() {
+        let mut billboard = ColorBillboard::<3, 8>::new();
+        billboard
+            .push_pixel_art(["r.w", ".b.", "rrr"], &PALETTE)
+            .unwrap();
+        billboard.push_pixel_art(["w", "w", "."], &PALETTE).unwrap();
+        assert_eq!(rows(&billboard), ["r.ww", ".b.w", "rrr."]);
+    }
+
+    #[test]
+    fn push_billboard() {
+        static ART: [[bool; 3]; 4] = horizontal([*b"#  #", *b" # #", *b"  ##"]);
+        let mut billboard = ColorBillboard::<3, 8>::new();
+        billboard.push_billboard(&ART, color::RED).unwrap();
+        assert_eq!(rows(&billboard), ["r..r", ".r.r", "..rr"]);
+    }
+
+    #[test]
+    fn push_text() {
+        let mut billboard = ColorBillboard::<4, 16>::new();
+        let style = Style::color(color::WHITE).with_background(color::BLUE);
+        billboard.push_text("12", &FONT_3X3, &style).unwrap();
+        // the text stands on the bottom row, the background fills the height
+        // of the font and the column between the characters
+        assert_eq!(
+            rows(&billboard),
+            [".......", "wwbbwwb", "bwbbbwb", "wwwbbww"]
+        );
+
+        // the columns of a shader start at 0 for every text
+        let mut billboard = ColorBillboard::<4, 16>::new();
+        billboard.push_blank(1).unwrap();
+        let shader = |pixel: Pixel| match pixel.x {
+            0 => color::RED,
+            _ => color::WHITE,
+        };
+        billboard
+            .push_text("-", &FONT_3X3, &Style::shader(&shader))
+            .unwrap();
+        assert_eq!(rows(&billboard), ["....", "....", ".rww", "...."]);
+    }
+
+    #[test]
+    fn push_bitmap() {
+        #[rustfmt::skip]
+        static DATA: [u8; 24] = [
+            255, 0, 0, 0, 0, 0,
+            0, 0, 0, 255, 255, 255,
+            255, 0, 0, 255, 0, 0,
+            0, 0, 255, 0, 0, 0,
+        ];
+        // lower than the billboard
+        let mut billboard = ColorBillboard::<4, 8>::new();
+        let bitmap = Bitmap::from_rgb(2, 3, &DATA[6..]);
+        billboard.push_bitmap(&bitmap).unwrap();
+        assert_eq!(rows(&billboard), ["..", ".w", "rr", "b."]);
+
+        // higher than the billboard, the top row is cut off
+        let mut billboard = ColorBillboard::<3, 8>::new();
+        let bitmap = Bitmap::from_rgb(2, 4, &DATA);
+        billboard.push_bitmap(&bitmap).unwrap();
+        assert_eq!(rows(&billboard), [".w", "rr", "b."]);
+    }
+
+    #[test]
+    fn full() {
+        let mut billboard = ColorBillboard::<3, 4>::new();
+        billboard.push_blank(2).unwrap();
+        let style = Style::color(color::WHITE);
+        assert_eq!(
+            billboard.push_text("1", &FONT_3X3, &style),
+            Err(BillboardError::Full(3))
+        );
+        assert_eq!(
+            billboard.push_pixel_art(["www", "www", "www"], &PALETTE),
+            Err(BillboardError::Full(3))
+        );
+        assert_eq!(billboard.push_blank(3), Err(BillboardError::Full(3)));
+        // nothing was added
+        assert_eq!(billboard.len(), 2);
+
+        billboard.push_blank(1).unwrap();
+        billboard.push([color::RED; 3]).unwrap();
+        assert_eq!(
+            billboard.push([color::RED; 3]),
+            Err(BillboardError::Full(1))
+        );
+        assert_eq!(rows(&billboard), ["...r", "...r", "...r"]);
+        assert_eq!(
+            format!("{}", BillboardError::Full(1)),
+            "no space left for 1 columns"
+        );
+    }
+
+    #[test]
+    fn invalid_pixel_art() {
+        let mut billboard = ColorBillboard::<3, 8>::new();
+        assert_eq!(
+            billboard.push_pixel_art(["rr", "r", "rr"], &PALETTE),
+            Err(BillboardError::UnevenRows)
+        );
+        assert_eq!(
+            billboard.push_pixel_art(["rr", "rx", "ry"], &PALETTE),
+            Err(BillboardError::UnknownColor('x'))
+        );
+        assert!(billboard.is_empty());
+        assert_eq!(
+            format!("{}", BillboardError::UnevenRows),
+            "the rows of pixel art have different lengths"
+        );
+        assert_eq!(
+            format!("{}", BillboardError::UnknownColor('x')),
+            "'x' is not in the palette"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "pixel art may only contain '#' and ' '")]
+    fn invalid_billboard() {
+        vertical([*b"# ", *b" x"]);
+    }
+
+    #[test]
+    fn draw_frame() {
+        let mut billboard = ColorBillboard::<3, 8>::new();
+        billboard
+            .push_pixel_art(["r..b", ".w..", "..rr"], &PALETTE)
+            .unwrap();
+        let mut matrix = led_matrix_headless::LedMatrix::<3, 3>::new();
+        let mut frame = |offset| {
+            billboard.draw_frame(&mut matrix, offset);
+            matrix.apply();
+            let leds = matrix.last_frame().unwrap().leds;
+            leds.iter()
+                .map(|row| row.iter().map(|&led| symbol(led)).collect())
+                .collect::<Vec<String>>()
+        };
+        assert_eq!(frame(0), ["r..", ".w.", "..r"]);
+        assert_eq!(frame(2), [".b.", "...", "rr."]);
+        assert_eq!(frame(4), ["..."; 3]);
+        assert_eq!(frame(usize::MAX), ["..."; 3]);
+    }
+}
@@ -0,0 +1,1049 @@
+//! Decoding of bitmap files and a number of predefined bitmaps.
+//!
+//! Parse a bitmap file with [Bitmap::parse] and draw it with
+//! [draw_bitmap](crate::LedMatrix::draw_bitmap). The parser doesn't allocate
+//! and can be evaluated at compile time, so invalid bitmaps can be rejected
+//! before the program ever runs:
+//!
+//! ```
+//! use led_matrix::bitmap::Bitmap;
+//!
+//! static HEART: Bitmap = match Bitmap::parse(include_bytes!("../bitmaps/apple.bmp")) {
+//!     Ok(bitmap) => bitmap,
+//!     Err(_) => panic!("invalid bitmap"),
+//! };
+//!
+//! # fn app(matrix: &mut dyn led_matrix::LedMatrix) {
+//! matrix.draw_bitmap(&HEART);
+//! matrix.apply();
+//! # }
+//! ```
+//!
+//! Supported are uncompressed images with 1, 4, 8, 16, 24 and 32 bits per
+//! pixel as well as images compressed with BI_RLE4 and BI_RLE8. 16 and 32 bit
+//! images may specify their channels with bit masks (BI_BITFIELDS), which
+//! includes an alpha channel.
+//!
+//! bitmap format: <https://en.wikipedia.org/wiki/BMP_file_format>
+//!
+//! To embed an image in the firmware, prefer [include_image]. It decodes the
+//! image (PNG, GIF or BMP) while compiling and only embeds its pixels.
+
+use crate::{include_image, sprite::Region};
+
+/// A parsed bitmap, which borrows the bytes of the bitmap file or its raw
+/// pixels.
+///
+/// Like on the LED-matrix, the origin of the coordinates (x, y) of a bitmap
+/// is in its lower left corner.
+#[derive(Debug, Clone, Copy)]
+pub struct Bitmap<'a> {
+    width: usize,
+    height: usize,
+    // whether the first row in the file is the top row of the image
+    top_down: bool,
+    bits_per_pixel: u16,
+    compression: Compression,
+    palette: &'a [u8],
+    // 3 bytes for the oldest header version, 4 otherwise
+    palette_entry_size: usize,
+    palette_len: usize,
+    // red, green, blue and alpha
+    masks: [u32; 4],
+    data: &'a [u8],
+    // bytes per row including padding, only for uncompressed images
+    stride: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Compression {
+    None,
+    Rle8,
+    Rle4,
+}
+
+/// The reason why a bitmap couldn't be parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitmapError {
+    /// The data doesn't start with the signature "BM".
+    NotABitmap,
+    /// The data ends before the bitmap is complete.
+    Truncated,
+    /// The size of the header doesn't correspond to a supported version.
+    UnsupportedHeader(u32),
+    UnsupportedBitDepth(u16),
+    /// The compression method isn't supported or doesn't match the bit depth
+    /// and orientation of the image.
+    UnsupportedCompression(u32),
+    /// The width or height is zero or too large.
+    InvalidDimensions,
+    /// A pixel refers to a color which isn't in the color table.
+    InvalidColorIndex(u8),
+}
+
+impl core::fmt::Display for BitmapError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::NotABitmap => write!(f, "not a bitmap file"),
+            Self::Truncated => write!(f, "bitmap file is truncated"),
+            Self::UnsupportedHeader(size) => write!(f, "unsupported header size: {size}"),
+            Self::UnsupportedBitDepth(bpp) => write!(f, "unsupported color depth: {bpp} bits"),
+            Self::UnsupportedCompression(c) => write!(f, "unsupported compression: {c}"),
+            Self::InvalidDimensions => write!(f, "invalid width or height"),
+            Self::InvalidColorIndex(i) => write!(f, "color index {i} is not in the color table"),
+        }
+    }
+}
+
+/// Which pixels of a bitmap
+/// [draw_bitmap_at](crate::LedMatrix::draw_bitmap_at) treats as transparent.
+///
+/// Pixels skipped by a compressed bitmap are always transparent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Transparency {
+    /// Draw every pixel as it is and ignore the alpha channel.
+    #[default]
+    Opaque,
+    /// Blend every pixel with the LED below according to its alpha channel.
+    /// Pixels with an alpha of 0 are fully transparent.
+    Alpha,
+    /// Skip every pixel of the given color.
+    ColorKey((u8, u8, u8)),
+}
+
+/// The `?` operator isn't available in const functions.
+macro_rules! tri {
+    ($result:expr) => {
+        match $result {
+            Ok(value) => value,
+            Err(error) => return Err(error),
+        }
+    };
+}
+
+const FILE_HEADER_SIZE: usize = 14;
+
+impl<'a> Bitmap<'a> {
+    /// Parse the contents of a bitmap file.
+    ///
+    /// All pixels are validated, so drawing the bitmap afterwards can't fail.
+    pub const fn parse(bytes: &'a [u8]) -> Result<Self, BitmapError> {
+        if bytes.len() < 2 || bytes[0] != b'B' || bytes[1] != b'M' {
+            return Err(BitmapError::NotABitmap);
+        }
+        let data_offset = tri!(read_u32(bytes, 10)) as usize;
+        let header_size = tri!(read_u32(bytes, FILE_HEADER_SIZE));
+        let header = FILE_HEADER_SIZE + header_size as usize;
+        if bytes.len() < header {
+            return Err(BitmapError::Truncated);
+        }
+
+        let (width, height, top_down, bits_per_pixel, compression, palette_len) = match header_size
+        {
+            // BITMAPCOREHEADER
+            12 => {
+                let width = tri!(read_u16(bytes, 18)) as i64;
+                let height = tri!(read_u16(bytes, 20)) as i64;
+                (width, height, false, tri!(read_u16(bytes, 24)), 0, 0)
+            }
+            // BITMAPINFOHEADER and its extensions up to BITMAPV5HEADER
+            40 | 52 | 56 | 108 | 124 => {
+                let width = tri!(read_u32(bytes, 18)) as i32 as i64;
+                let height = tri!(read_u32(bytes, 22)) as i32 as i64;
+                (
+                    width,
+                    height.abs(),
+                    height < 0,
+                    tri!(read_u16(bytes, 28)),
+                    tri!(read_u32(bytes, 30)),
+                    tri!(read_u32(bytes, 46)),
+                )
+            }
+            _ => return Err(BitmapError::UnsupportedHeader(header_size)),
+        };
+        if width <= 0 || height <= 0 {
+            return Err(BitmapError::InvalidDimensions);
+        }
+        let (width, height) = (width as usize, height as usize);
+
+        match (header_size, bits_per_pixel) {
+            (_, 1 | 4 | 8 | 24) | (40.., 16 | 32) => {}
+            _ => return Err(BitmapError::UnsupportedBitDepth(bits_per_pixel)),
+        }
+
+        // masks of the red, green, blue and alpha channels, the ones of
+        // palettized images are unused
+        let mut masks = match bits_per_pixel {
+            16 => [0x7c00, 0x03e0, 0x001f, 0],
+            _ => [0xff0000, 0x00ff00, 0x0000ff, 0],
+        };
+        // the color table follows the header and the masks
+        let mut palette_start = header;
+        let compression = match (compression, bits_per_pixel) {
+            (0, _) => Compression::None,
+            (1, 8) if !top_down => Compression::Rle8,
+            (2, 4) if !top_down => Compression::Rle4,
+            // BI_BITFIELDS and BI_ALPHABITFIELDS
+            (3 | 6, 16 | 32) => {
+                let with_alpha = compression == 6 || header_size >= 56;
+                let count = if with_alpha { 4 } else { 3 };
+                // in newer headers, the masks are part of the header
+                let start = if header_size >= 52 {
+                    FILE_HEADER_SIZE + 40
+                } else {
+                    palette_start += 4 * count;
+                    header
+                };
+                let mut i = 0;
+                while i < count {
+                    masks[i] = tri!(read_u32(bytes, start + 4 * i));
+                    i += 1;
+                }
+                Compression::None
+            }
+            _ => return Err(BitmapError::UnsupportedCompression(compression)),
+        };
+
+        let (palette_entry_size, palette_len) = match (header_size, bits_per_pixel) {
+            (12, 1 | 4 | 8) => (3, 1 << bits_per_pixel),
+            (_, 1 | 4 | 8) => {
+                let max = 1 << bits_per_pixel;
+                match palette_len as usize {
+                    0 => (4, max),
+                    len if len > max => (4, max),
+                    len => (4, len),
+                }
+            }
+            _ => (4, 0),
+        };
+        let palette = tri!(slice(
+            bytes,
+            palette_start,
+            palette_len * palette_entry_size
+        ));
+
+        if data_offset > bytes.len() {
+            return Err(BitmapError::Truncated);
+        }
+        let data = bytes.split_at(data_offset).1;
+
+        // rows are padded to a multiple of 4 bytes
+        let stride = match width.checked_mul(bits_per_pixel as usize) {
+            Some(bits) => bits.div_ceil(32) * 4,
+            None => return Err(BitmapError::InvalidDimensions),
+        };
+        if let Compression::None = compression {
+            match stride.checked_mul(height) {
+                Some(size) if size <= data.len() => {}
+                Some(_) => return Err(BitmapError::Truncated),
+                None => return Err(BitmapError::InvalidDimensions),
+            }
+        }
+
+        let mut bitmap = Self {
+            width,
+            height,
+            top_down,
+            bits_per_pixel,
+            compression,
+            palette,
+            palette_entry_size,
+            palette_len,
+            masks,
+            data,
+            stride,
+        };
+        tri!(bitmap.validate());
+        Ok(bitmap)
+    }
+
+    /// A bitmap of raw pixels without any file header, three bytes (red,
+    /// green, blue) per pixel. The first row is the top row of the image and
+    /// rows aren't padded.
+    ///
+    /// This is what [include_image] generates for opaque images.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the width or height is zero or if the data doesn't contain
+    /// exactly `width * height` pixels.
+    pub const fn from_rgb(width: usize, height: usize, data: &'a [u8]) -> Self {
+        Self::from_raw(width, height, data, 24)
+    }
+
+    /// A bitmap of raw pixels with four bytes (red, green, blue, alpha) per
+    /// pixel, otherwise the same as [from_rgb](Self::from_rgb).
+    ///
+    /// # Panics
+    ///
+    /// Panics if the width or height is zero or if the data doesn't contain
+    /// exactly `width * height` pixels.
+    pub const fn from_rgba(width: usize, height: usize, data: &'a [u8]) -> Self {
+        Self::from_raw(width, height, data, 32)
+    }
+
+    const fn from_raw(width: usize, height: usize, data: &'a [u8], bits_per_pixel: u16) -> Self {
+        let stride = width * (bits_per_pixel as usize / 8);
+        assert!(width > 0 && height > 0, "bitmap is empty");
+        assert!(
+            stride * height == data.len(),
+            "pixel data doesn't match the size"
+        );
+        Self {
+            width,
+            height,
+            top_down: true,
+            bits_per_pixel,
+            compression: Compression::None,
+            palette: &[],
+            palette_entry_size: 4,
+            palette_len: 0,
+            // the pixels are read as little endian
+            masks: [
+                0x0000_00ff,
+                0x0000_ff00,
+                0x00ff_0000,
+                if bits_per_pixel == 32 { 0xff00_0000 } else { 0 },
+            ],
+            data,
+            stride,
+        }
+    }
+
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The color of a single pixel as `(red, green, blue, alpha)`.
+    ///
+    /// Returns `None` if the position is outside of the bitmap or if the
+    /// pixel was skipped by a compressed bitmap, which means it is
+    /// transparent.
+    pub fn get(&self, (x, y): (usize, usize)) -> Option<(u8, u8, u8, u8)> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        match self.compression {
+            Compression::None => Some(self.color(self.raw(x, y))),
+            Compression::Rle8 | Compression::Rle4 => self
+                .pixels()
+                .find_map(|(position, color)| (position == (x, y)).then_some(color)),
+        }
+    }
+
+    /// An iterator over all pixels of the bitmap, as the coordinates (x, y)
+    /// and the color `(red, green, blue, alpha)`.
+    ///
+    /// Pixels which are skipped by a compressed bitmap are left out.
+    pub fn pixels(&self) -> Pixels<'a> {
+        Pixels {
+            bitmap: *self,
+            state: match self.compression {
+                Compression::None => PixelsState::Uncompressed { index: 0 },
+                Compression::Rle8 | Compression::Rle4 => PixelsState::Rle(self.rle_decoder()),
+            },
+        }
+    }
+
+    /// Check that all color indices are in the color table and, for
+    /// compressed bitmaps, that the data can be decoded. 32 bit images
+    /// without masks only have an alpha channel if it's used by any pixel.
+    const fn validate(&mut self) -> Result<(), BitmapError> {
+        if let Compression::Rle8 | Compression::Rle4 = self.compression {
+            let mut decoder = self.rle_decoder();
+            while let Some((_, index)) = tri!(decoder.next_pixel()) {
+                tri!(self.check_index(index as u32));
+            }
+            return Ok(());
+        }
+        if self.bits_per_pixel > 8 && self.bits_per_pixel != 32 {
+            return Ok(());
+        }
+        let mut has_alpha = false;
+        let mut y = 0;
+        while y < self.height {
+            let mut x = 0;
+            while x < self.width {
+                let raw = self.raw(x, y);
+                if self.bits_per_pixel <= 8 {
+                    tri!(self.check_index(raw));
+                } else if raw >> 24 != 0 {
+                    has_alpha = true;
+                }
+                x += 1;
+            }
+            y += 1;
+        }
+        if self.bits_per_pixel == 32 && self.masks[3] == 0 && has_alpha {
+            self.masks[3] = 0xff000000;
+        }
+        Ok(())
+    }
+
+    const fn check_index(&self, index: u32) -> Result<(), BitmapError> {
+        if index as usize >= self.palette_len {
+            return Err(BitmapError::InvalidColorIndex(index as u8));
+        }
+        Ok(())
+    }
+
+    /// The raw value of a pixel of an uncompressed bitmap, either a color
+    /// index or the channels packed according to the masks.
+    const fn raw(&self, x: usize, y: usize) -> u32 {
+        let row = if self.top_down {
+            self.height - y - 1
+        } else {
+            y
+        };
+        let bits = self.bits_per_pixel as usize;
+        let start = row * self.stride + x * bits / 8;
+        let bytes = self.data;
+        match bits {
+            1 | 4 | 8 => {
+                let shift = 8 - bits - x * bits % 8;
+                (bytes[start] >> shift) as u32 & ((1 << bits) - 1)
+            }
+            16 => u16::from_le_bytes([bytes[start], bytes[start + 1]]) as u32,
+            24 => u32::from_le_bytes([bytes[start], bytes[start + 1], bytes[start + 2], 0]),
+            _ => u32::from_le_bytes([
+                bytes[start],
+                bytes[start + 1],
+                bytes[start + 2],
+                bytes[start + 3],
+            ]),
+        }
+    }
+
+    /// Convert a raw pixel value to `(red, green, blue, alpha)`.
+    const fn color(&self, raw: u32) -> (u8, u8, u8, u8) {
+        if self.bits_per_pixel <= 8 {
+            let i = raw as usize * self.palette_entry_size;
+            let palette = self.palette;
+            return (palette[i + 2], palette[i + 1], palette[i], 255);
+        }
+        let [r, g, b, a] = self.masks;
+        let alpha = if a == 0 { 255 } else { channel(raw, a) };
+        (channel(raw, r), channel(raw, g), channel(raw, b), alpha)
+    }
+
+    /// Call `pixel` for every pixel within the region, with its position
+    /// relative to the lower left corner of the region.
+    pub(crate) fn for_each_pixel_in(
+        &self,
+        region: Region,
+        mut pixel: impl FnMut((usize, usize), (u8, u8, u8, u8)),
+    ) {
+        let (right, top) = (region.x + region.width, region.y + region.height);
+        match self.compression {
+            Compression::None => {
+                for y in region.y..top.min(self.height) {
+                    for x in region.x..right.min(self.width) {
+                        pixel((x - region.x, y - region.y), self.color(self.raw(x, y)));
+                    }
+                }
+            }
+            // compressed pixels can only be decoded in order
+            Compression::Rle8 | Compression::Rle4 => {
+                for ((x, y), color) in self.pixels() {
+                    if (region.x..right).contains(&x) && (region.y..top).contains(&y) {
+                        pixel((x - region.x, y - region.y), color);
+                    }
+                }
+            }
+        }
+    }
+
+    const fn rle_decoder(&self) -> RleDecoder<'a> {
+        RleDecoder {
+            data: self.data,
+            pos: 0,
+            x: 0,
+            y: 0,
+            width: self.width,
+            height: self.height,
+            four_bit: matches!(self.compression, Compression::Rle4),
+            run: Run::Idle,
+        }
+    }
+}
+
+/// Extract a channel with the given mask and scale it to 8 bits.
+const fn channel(raw: u32, mask: u32) -> u8 {
+    if mask == 0 {
+        return 0;
+    }
+    let shift = mask.trailing_zeros();
+    let max = (mask >> shift) as u64;
+    let value = ((raw & mask) >> shift) as u64;
+    ((value * 255 + max / 2) / max) as u8
+}
+
+const fn read_u16(bytes: &[u8], offset: usize) -> Result<u16, BitmapError> {
+    match slice(bytes, offset, 2) {
+        Ok(&[a, b]) => Ok(u16::from_le_bytes([a, b])),
+        _ => Err(BitmapError::Truncated),
+    }
+}
+
+const fn read_u32(bytes: &[u8], offset: usize) -> Result<u32, BitmapError> {
+    match slice(bytes, offset, 4) {
+        Ok(&[a, b, c, d]) => Ok(u32::from_le_bytes([a, b, c, d])),
+        _ => Err(BitmapError::Truncated),
+    }
+}
+
+/// `&bytes[start..start + len]`, which isn't available in const functions.
+const fn slice(bytes: &[u8], start: usize, len: usize) -> Result<&[u8], BitmapError> {
+    if start > bytes.len() || len > bytes.len() - start {
+        return Err(BitmapError::Truncated);
+    }
+    Ok(bytes.split_at(start).1.split_at(len).0)
+}
+
+/// An iterator over the pixels of a [Bitmap], see [Bitmap::pixels].
+#[derive(Debug, Clone)]
+pub struct Pixels<'a> {
+    bitmap: Bitmap<'a>,
+    state: PixelsState<'a>,
+}
+
+#[derive(Debug, Clone)]
+enum PixelsState<'a> {
+    Uncompressed { index: usize },
+    Rle(RleDecoder<'a>),
+}
+
+impl Iterator for Pixels<'_> {
+    type Item = ((usize, usize), (u8, u8, u8, u8));
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bitmap = &self.bitmap;
+        let (position, raw) = match &mut self.state {
+            PixelsState::Uncompressed { index } => {
+                if *index >= bitmap.width * bitmap.height {
+                    return None;
+                }
+                let (x, y) = (*index % bitmap.width, *index / bitmap.width);
+                *index += 1;
+                ((x, y), bitmap.raw(x, y))
+            }
+            // the data was validated when the bitmap was parsed
+            PixelsState::Rle(decoder) => {
+                let (position, index) = decoder.next_pixel().ok()??;
+                (position, index as u32)
+            }
+        };
+        Some((position, bitmap.color(raw)))
+    }
+}
+
+/// The position of a pixel and its color index.
+type IndexedPixel = ((usize, usize), u8);
+
+/// Decodes BI_RLE4 and BI_RLE8 compressed data into color indices.
+#[derive(Debug, Clone, Copy)]
+struct RleDecoder<'a> {
+    data: &'a [u8],
+    pos: usize,
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+    four_bit: bool,
+    run: Run,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Run {
+    Idle,
+    /// A single value repeated `count` times. With 4 bits per pixel, the
+    /// value contains two indices which alternate.
+    Encoded {
+        value: u8,
+        count: usize,
+        i: usize,
+    },
+    /// `count` literal indices starting at `start`.
+    Absolute {
+        start: usize,
+        count: usize,
+        i: usize,
+    },
+    Done,
+}
+
+impl RleDecoder<'_> {
+    /// The next pixel which lies within the bitmap, together with its color
+    /// index.
+    const fn next_pixel(&mut self) -> Result<Option<IndexedPixel>, BitmapError> {
+        loop {
+            match self.run {
+                Run::Done => return Ok(None),
+                Run::Encoded { value, count, i } if i < count => {
+                    self.run = Run::Encoded {
+                        value,
+                        count,
+                        i: i + 1,
+                    };
+                    let index = match (self.four_bit, i % 2) {
+                        (false, _) => value,
+                        (true, 0) => value >> 4,
+                        (true, _) => value & 0x0f,
+                    };
+                    if let Some(position) = self.advance() {
+                        return Ok(Some((position, index)));
+                    }
+                }
+                Run::Absolute { start, count, i } if i < count => {
+                    self.run = Run::Absolute {
+                        start,
+                        count,
+                        i: i + 1,
+                    };
+                    let index = match (self.four_bit, i % 2) {
+                        (false, _) => self.data[start + i],
+                        (true, 0) => self.data[start + i / 2] >> 4,
+                        (true, _) => self.data[start + i / 2] & 0x0f,
+                    };
+                    if let Some(position) = self.advance() {
+                        return Ok(Some((position, index)));
+                    }
+                }
+                Run::Encoded { .. } => self.run = Run::Idle,
+                Run::Absolute { start, count, .. } => {
+                    // absolute runs are padded to a multiple of 2 bytes
+                    self.pos = start + self.absolute_len(count).next_multiple_of(2);
+                    self.run = Run::Idle;
+                }
+                Run::Idle => tri!(self.read_command()),
+            }
+        }
+    }
+
+    const fn read_command(&mut self) -> Result<(), BitmapError> {
+        // be lenient about a missing end of bitmap marker
+        let (first, second) = match slice(self.data, self.pos, 2) {
+            Ok(&[first, second]) => (first, second),
+            _ => {
+                self.run = Run::Done;
+                return Ok(());
+            }
+        };
+        self.pos += 2;
+        match (first, second) {
+            // end of line
+            (0, 0) => {
+                self.x = 0;
+                self.y += 1;
+            }
+            // end of bitmap
+            (0, 1) => self.run = Run::Done,
+            // delta
+            (0, 2) => {
+                let delta = tri!(slice(self.data, self.pos, 2));
+                self.pos += 2;
+                self.x += delta[0] as usize;
+                self.y += delta[1] as usize;
+            }
+            (0, count) => {
+                let count = count as usize;
+                tri!(slice(self.data, self.pos, self.absolute_len(count)));
+                self.run = Run::Absolute {
+                    start: self.pos,
+                    count,
+                    i: 0,
+                };
+            }
+            (count, value) => {
+                self.run = Run::Encoded {
+                    value,
+                    count: count as usize,
+                    i: 0,
+                };
+            }
+        }
+        if self.y >= self.height {
+            self.run = Run::Done;
+        }
+        Ok(())
+    }
+
+    /// The number of bytes of an absolute run without padding.
+    const fn absolute_len(&self, count: usize) -> usize {
+        if self.four_bit {
+            count.div_ceil(2)
+        } else {
+            count
+        }
+    }
+
+    /// Move to the next pixel, returning the current one if it's within the
+    /// bitmap. Runs which are too long for their line are clipped.
+    const fn advance(&mut self) -> Option<(usize, usize)> {
+        let position = (self.x, self.y);
+        self.x += 1;
+        if position.0 < self.width {
+            Some(position)
+        } else {
+            None
+        }
+    }
+}
+
+pub static APPLE: &Bitmap = &include_image!("bitmaps/apple.bmp");
+pub static BAT: &Bitmap = &include_image!("bitmaps/bat.bmp");
+pub static BIG_IMG: &Bitmap = &include_image!("bitmaps/big_img.bmp");
+pub static BIRD: &Bitmap = &include_image!("bitmaps/bird.bmp");
+pub static CHICKEN: &Bitmap = &include_image!("bitmaps/chicken.bmp");
+pub static CRAB: &Bitmap = &include_image!("bitmaps/crab.bmp");
+pub static DINO: &Bitmap = &include_image!("bitmaps/dino.bmp");
+pub static DRINK: &Bitmap = &include_image!("bitmaps/drink.bmp");
+pub static DUCK: &Bitmap = &include_image!("bitmaps/duck.bmp");
+pub static ERLENMEYER: &Bitmap = &include_image!("bitmaps/erlenmeyer.bmp");
+pub static FOX: &Bitmap = &include_image!("bitmaps/fox.bmp");
+pub static MC_CREEPER: &Bitmap = &include_image!("bitmaps/mc_creeper.bmp");
+pub static MC_PIG: &Bitmap = &include_image!("bitmaps/mc_pig.bmp");
+pub static MOB_1: &Bitmap = &include_image!("bitmaps/mob_1.bmp");
+pub static MOUSE: &Bitmap = &include_image!("bitmaps/mouse.bmp");
+pub static MUSHROOM: &Bitmap = &include_image!("bitmaps/mushroom.bmp");
+pub static PIKACHU: &Bitmap = &include_image!("bitmaps/pikachu.bmp");
+pub static RABBIT: &Bitmap = &include_image!("bitmaps/rabbit.bmp");
+pub static SKULL: &Bitmap = &include_image!("bitmaps/skull.bmp");
+pub static TEST_GRID: &Bitmap = &include_image!("bitmaps/test_grid.bmp");
+pub static WINE: &Bitmap = &include_image!("bitmaps/wine.bmp");
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use std::vec::Vec;
+
+    const BLACK: (u8, u8, u8, u8) = (0, 0, 0, 255);
+    const WHITE: (u8, u8, u8, u8) = (255, 255, 255, 255);
+    const RED: (u8, u8, u8, u8) = (255, 0, 0, 255);
+    const GREEN: (u8, u8, u8, u8) = (0, 255, 0, 255);
+    const BLUE: (u8, u8, u8, u8) = (0, 0, 255, 255);
+
+    /// A color table with black, white, red, green and blue, in this order.
+    const PALETTE: [u8; 20] = [
+        0, 0, 0, 0, 255, 255, 255, 0, 0, 0, 255, 0, 0, 255, 0, 0, 255, 0, 0, 0,
+    ];
+
+    /// The fields of a bitmap file which vary between the test images.
+    #[derive(Clone, Copy)]
+    struct Header {
+        size: u32,
+        width: i32,
+        height: i32,
+        bits_per_pixel: u16,
+        compression: u32,
+        palette_len: u32,
+    }
+
+    impl Header {
+        const fn info(width: i32, height: i32, bits_per_pixel: u16) -> Self {
+            Self {
+                size: 40,
+                width,
+                height,
+                bits_per_pixel,
+                compression: 0,
+                palette_len: 0,
+            }
+        }
+
+        /// Use the first `len` colors of [PALETTE].
+        const fn with_palette(self, len: u32) -> Self {
+            Self {
+                palette_len: len,
+                ..self
+            }
+        }
+    }
+
+    /// Assemble a bitmap file. The masks directly follow the first 40 bytes
+    /// of the header, which is where both BITMAPINFOHEADER with BI_BITFIELDS
+    /// and the newer headers expect them.
+    fn bmp(header: Header, masks: &[u32], palette: &[u8], data: &[u8]) -> Vec<u8> {
+        let mut info = Vec::new();
+        info.extend(header.size.to_le_bytes());
+        info.extend(header.width.to_le_bytes());
+        info.extend(header.height.to_le_bytes());
+        info.extend(1u16.to_le_bytes());
+        info.extend(header.bits_per_pixel.to_le_bytes());
+        info.extend(header.compression.to_le_bytes());
+        info.extend((data.len() as u32).to_le_bytes());
+        info.extend([0; 8]);
+        info.extend(header.palette_len.to_le_bytes());
+        info.extend([0; 4]);
+        for mask in masks {
+            info.extend(mask.to_le_bytes());
+        }
+        if info.len() < header.size as usize {
+            info.resize(header.size as usize, 0);
+        }
+        info.extend(palette);
+
+        let data_offset = 14 + info.len();
+        let mut file = Vec::new();
+        file.extend(b"BM");
+        file.extend(((data_offset + data.len()) as u32).to_le_bytes());
+        file.extend([0; 4]);
+        file.extend((data_offset as u32).to_le_bytes());
+        file.extend(info);
+        file.extend(data);
+        file
+    }
+
+    /// The color of a pixel, `None` if it was skipped.
+    type Pixel = Option<(u8, u8, u8, u8)>;
+
+    /// All pixels of a bitmap from the top row down.
+    fn pixels(bitmap: &Bitmap) -> Vec<Vec<Pixel>> {
+        (0..bitmap.height())
+            .rev()
+            .map(|y| (0..bitmap.width()).map(|x| bitmap.get((x, y))).collect())
+            .collect()
+    }
+
+    #[test]
+    fn one_bit() {
+        // rows are stored bottom up and padded to 4 bytes
+        let data = [0b1010_0000, 0, 0, 0, 0b0110_0000, 0, 0, 0];
+        let file = bmp(Header::info(3, 2, 1), &[], &PALETTE[..8], &data);
+        let bitmap = Bitmap::parse(&file).unwrap();
+        assert_eq!(
+            pixels(&bitmap),
+            [
+                [Some(BLACK), Some(WHITE), Some(WHITE)],
+                [Some(WHITE), Some(BLACK), Some(WHITE)],
+            ]
+        );
+    }
+
+    #[test]
+    fn four_bit() {
+        let header = Header::info(3, 2, 4).with_palette(3);
+        let data = [0x20, 0x10, 0, 0, 0x11, 0x20, 0, 0];
+        let file = bmp(header, &[], &PALETTE[..12], &data);
+        let bitmap = Bitmap::parse(&file).unwrap();
+        assert_eq!(
+            pixels(&bitmap),
+            [
+                [Some(WHITE), Some(WHITE), Some(RED)],
+                [Some(RED), Some(BLACK), Some(WHITE)],
+            ]
+        );
+    }
+
+    #[test]
+    fn eight_bit_bottom_up_and_top_down() {
+        let data = [3, 4, 0, 0, 2, 2, 0, 0];
+        let expected = [[Some(RED), Some(RED)], [Some(GREEN), Some(BLUE)]];
+        let file = bmp(Header::info(2, 2, 8).with_palette(5), &[], &PALETTE, &data);
+        assert_eq!(pixels(&Bitmap::parse(&file).unwrap()), expected);
+
+        // the same pixels with the top row first
+        let data = [2, 2, 0, 0, 3, 4, 0, 0];
+        let file = bmp(Header::info(2, -2, 8).with_palette(5), &[], &PALETTE, &data);
+        assert_eq!(pixels(&Bitmap::parse(&file).unwrap()), expected);
+    }
+
+    #[test]
+    fn sixteen_bit() {
+        let pixels_of = |file: &[u8]| {
+            let bitmap = Bitmap::parse(file).unwrap();
+            [(0, 1), (1, 1), (0, 0), (1, 0)].map(|position| bitmap.get(position).unwrap())
+        };
+
+        // 5 bits per channel without masks
+        let data = [0x00, 0x7c, 0xe0, 0x03, 0x1f, 0x00, 0x10, 0x42];
+        let file = bmp(Header::info(2, 2, 16), &[], &[], &data);
+        assert_eq!(pixels_of(&file), [BLUE, (132, 132, 132, 255), RED, GREEN]);
+
+        // 5 bits red and blue, 6 bits green
+        let header = Header {
+            compression: 3,
+            ..Header::info(2, 2, 16)
+        };
+        let data = [0x00, 0xf8, 0xe0, 0x07, 0x1f, 0x00, 0x10, 0x84];
+        let file = bmp(header, &[0xf800, 0x07e0, 0x001f], &[], &data);
+        assert_eq!(pixels_of(&file), [BLUE, (132, 130, 132, 255), RED, GREEN]);
+    }
+
+    #[test]
+    fn thirty_two_bit() {
+        // blue, green, red and an unused byte
+        let data = [0x33, 0x22, 0x11, 0, 0, 0, 0, 0];
+        let file = bmp(Header::info(2, 1, 32), &[], &[], &data);
+        let bitmap = Bitmap::parse(&file).unwrap();
+        assert_eq!(
+            pixels(&bitmap),
+            [[Some((0x11, 0x22, 0x33, 255)), Some(BLACK)]]
+        );
+
+        // as soon as one pixel uses the fourth byte, it's the alpha channel
+        let data = [0x33, 0x22, 0x11, 0x80, 0, 0, 0, 0];
+        let file = bmp(Header::info(2, 1, 32), &[], &[], &data);
+        let bitmap = Bitmap::parse(&file).unwrap();
+        assert_eq!(
+            pixels(&bitmap),
+            [[Some((0x11, 0x22, 0x33, 0x80)), Some((0, 0, 0, 0))]]
+        );
+    }
+
+    #[test]
+    fn v4_header_with_alpha_mask() {
+        let header = Header {
+            size: 108,
+            compression: 3,
+            ..Header::info(2, 1, 32)
+        };
+        // alpha in the lowest byte, unlike the default order
+        let masks = [0xff00_0000, 0x00ff_0000, 0x0000_ff00, 0x0000_00ff];
+        let data = [0x80, 0x33, 0x22, 0x11, 0xff, 0, 0, 0xff];
+        let file = bmp(header, &masks, &[], &data);
+        let bitmap = Bitmap::parse(&file).unwrap();
+        assert_eq!(
+            pixels(&bitmap),
+            [[Some((0x11, 0x22, 0x33, 0x80)), Some(RED)]]
+        );
+    }
+
+    #[test]
+    fn rle8() {
+        let header = Header {
+            compression: 1,
+            ..Header::info(4, 3, 8).with_palette(5)
+        };
+        #[rustfmt::skip]
+        let data = [
+            // four times white and the end of the line
+            4, 1, 0, 0,
+            // three literal pixels, padded to 2 bytes, and the end of the line
+            0, 3, 2, 3, 4, 0, 0, 0,
+            // skip two pixels, twice red, and the end of the bitmap
+            0, 2, 2, 0, 2, 2, 0, 1,
+        ];
+        let file = bmp(header, &[], &PALETTE, &data);
+        let bitmap = Bitmap::parse(&file).unwrap();
+        assert_eq!(
+            pixels(&bitmap),
+            [
+                [None, None, Some(RED), Some(RED)],
+                [Some(RED), Some(GREEN), Some(BLUE), None],
+                [Some(WHITE), Some(WHITE), Some(WHITE), Some(WHITE)],
+            ]
+        );
+        assert_eq!(bitmap.pixels().count(), 9);
+    }
+
+    #[test]
+    fn rle4() {
+        let header = Header {
+            compression: 2,
+            ..Header::info(4, 2, 4).with_palette(5)
+        };
+        #[rustfmt::skip]
+        let data = [
+            // alternating red and green, too long for the line
+            5, 0x23, 0, 0,
+            // three literal pixels, padded to 2 bytes
+            0, 3, 0x12, 0x40, 0, 1,
+        ];
+        let file = bmp(header, &[], &PALETTE, &data);
+        let bitmap = Bitmap::parse(&file).unwrap();
+        assert_eq!(
+            pixels(&bitmap),
+            [
+                [Some(WHITE), Some(RED), Some(BLUE), None],
+                [Some(RED), Some(GREEN), Some(RED), Some(GREEN)],
+            ]
+        );
+    }
+
+    #[test]
+    fn truncated() {
+        let data = [3, 4, 0, 0, 2, 2, 0, 0];
+        let file = bmp(Header::info(2, 2, 8).with_palette(5), &[], &PALETTE, &data);
+        for len in [1, 12, 30, 60, file.len() - 1] {
+            assert_eq!(
+                Bitmap::parse(&file[..len]).unwrap_err(),
+                match len {
+                    1 => BitmapError::NotABitmap,
+                    _ => BitmapError::Truncated,
+                },
+                "{len} bytes"
+            );
+        }
+
+        // an absolute run which ends early
+        let header = Header {
+            compression: 1,
+            ..Header::info(4, 1, 8).with_palette(5)
+        };
+        let file = bmp(header, &[], &PALETTE, &[0, 4, 1, 2]);
+        assert_eq!(Bitmap::parse(&file).unwrap_err(), BitmapError::Truncated);
+    }
+
+    #[test]
+    fn invalid_color_index() {
+        let header = Header::info(2, 1, 8).with_palette(2);
+        let file = bmp(header, &[], &PALETTE[..8], &[1, 2, 0, 0]);
+        assert_eq!(
+            Bitmap::parse(&file).unwrap_err(),
+            BitmapError::InvalidColorIndex(2)
+        );
+
+        // the same for compressed bitmaps
+        let header = Header {
+            compression: 1,
+            ..header
+        };
+        let file = bmp(header, &[], &PALETTE[..8], &[2, 7, 0, 1]);
+        assert_eq!(
+            Bitmap::parse(&file).unwrap_err(),
+            BitmapError::InvalidColorIndex(7)
+        );
+    }
+
+    #[test]
+    fn unsupported_formats() {
+        let data = [0; 8];
+        let header = Header::info(2, 2, 8).with_palette(5);
+        let error = |header| Bitmap::parse(&bmp(header, &[], &PALETTE, &data)).unwrap_err();
+
+        assert_eq!(
+            error(Header { size: 64, ..header }),
+            BitmapError::UnsupportedHeader(64)
+        );
+        assert_eq!(
+            error(Header::info(2, 2, 2).with_palette(4)),
+            BitmapError::UnsupportedBitDepth(2)
+        );
+        assert_eq!(
+            error(Header {
+                compression: 4,
+                ..header
+            }),
+            BitmapError::UnsupportedCompression(4)
+        );
+        // compressed bitmaps can't be stored top down
+        let header = Header {
+            height: -2,
+            compression: 1,
+            ..header
+        };
+        assert_eq!(error(header), BitmapError::UnsupportedCompression(1));
+        assert_eq!(
+            error(Header::info(0, 2, 8).with_palette(5)),
+            BitmapError::InvalidDimensions
+        );
+        assert_eq!(Bitmap::parse(b"PNG").unwrap_err(), BitmapError::NotABitmap);
+    }
+}
@@ -1,6 +1,56 @@
 //! Display characters on the LED-matrix.
+//!
+//! Text which is only known at runtime, e.g. a score, can be drawn with
+//! [draw_text](crate::LedMatrix::draw_text) and
+//! [draw_text_fmt](crate::LedMatrix::draw_text_fmt). To scroll text across
+//! the matrix, use [scroll]. None of them allocate.
+//!
+//! All of them take the [Font] to draw the text with:
+//!
+//! - [FONT_5X7], the default font. Besides ASCII, it covers the umlauts and
+//!   accented letters used in German and French
+//!   (`ä ö ü Ä Ö Ü à â é è ê î ô û ç ß`) and some symbols
+//!   (`€ £ ° ♥ ← → ↑ ↓ ⌄ █`). Its glyphs are in [table].
+//! - [FONT_4X6], a smaller font with most of the same characters.
+//! - [FONT_3X5], a compact font which only has capital letters. Two
+//!   characters fit side by side on the 8x8 LED-matrix, but only one line:
+//!   two lines need 11 rows.
+//! - [FONT_3X3], a tiny font which only has digits and `- . :`. Two lines
+//!   of two digits fit on the 8x8 LED-matrix.
+//!
+//! Other pixel fonts can be imported from BDF files with
+//! [include_font!](crate::include_font).
+//!
+//! Text is drawn in a single color, or colored with a [Style]: with a
+//! background, a color per character or a shader like [rainbow].
+//!
+//! Characters which aren't supported are drawn as the replacement glyph of
+//! the font instead of halting the program. [lookup] and
+//! [`TryFrom<char>`](Character::try_from) report them for the default font,
+//! and [text!](crate::text) rejects them in string literals while compiling.
+
+use core::fmt;
+
+use crate::{color, LedMatrix};
 
 pub mod table;
+pub mod table_3x3;
+pub mod table_3x5;
+pub mod table_4x6;
+
+/// The default font, 5 pixels wide and 7 pixels high.
+pub const FONT_5X7: Font = Font::new(lookup, 7, table::REPLACEMENT);
+
+/// A font which is 4 pixels wide and 6 pixels high.
+pub const FONT_4X6: Font = Font::new(table_4x6::lookup, 6, table_4x6::REPLACEMENT);
+
+/// A compact font which is 3 pixels wide and 5 pixels high. Lower case
+/// letters are drawn as capital letters.
+pub const FONT_3X5: Font = Font::new(table_3x5::lookup, 5, table_3x5::REPLACEMENT);
+
+/// A tiny font which is 3 pixels wide and 3 pixels high, with only digits
+/// and `- . :`.
+pub const FONT_3X3: Font = Font::new(table_3x3::lookup, 3, table_3x3::REPLACEMENT);
 
 /// A single character that can be drawn on the the display.
 ///
@@ -17,7 +67,7 @@ impl Character {
     ///
     /// The width is specified separately for intended whitespace.
     ///
-    const fn new(coordinates: &'static [(usize, usize)], width: usize) -> Self {
+    pub const fn new(coordinates: &'static [(usize, usize)], width: usize) -> Self {
         Self {
             coordinates,
             width,
@@ -31,124 +81,1131 @@ impl Default for Character {
     }
 }
 
-/// Convert a string to an array of characters for display.
+/// Look up the character for a `char`, or `None` if it isn't supported.
+///
+/// Unlike [`TryFrom<char>`](Character::try_from), this can be used in
+/// constant expressions.
+///
+pub const fn lookup(c: char) -> Option<Character> {
+    let character = match c {
+        '0' => table::ZERO,
+        '1' => table::ONE,
+        '2' => table::TWO,
+        '3' => table::THREE,
+        '4' => table::FOUR,
+        '5' => table::FIVE,
+        '6' => table::SIX,
+        '7' => table::SEVEN,
+        '8' => table::EIGHT,
+        '9' => table::NINE,
+        'A' => table::A,
+        'B' => table::B,
+        'C' => table::C,
+        'D' => table::D,
+        'E' => table::E,
+        'F' => table::F,
+        'G' => table::G,
+        'H' => table::H,
+        'I' => table::I,
+        'J' => table::J,
+        'K' => table::K,
+        'L' => table::L,
+        'M' => table::M,
+        'N' => table::N,
+        'O' => table::O,
+        'P' => table::P,
+        'Q' => table::Q,
+        'R' => table::R,
+        'S' => table::S,
+        'T' => table::T,
+        'U' => table::U,
+        'V' => table::V,
+        'W' => table::W,
+        'X' => table::X,
+        'Y' => table::Y,
+        'Z' => table::Z,
+        'a' => table::a,
+        'b' => table::b,
+        'c' => table::c,
+        'd' => table::d,
+        'e' => table::e,
+        'f' => table::f,
+        'g' => table::g,
+        'h' => table::h,
+        'i' => table::i,
+        'j' => table::j,
+        'k' => table::k,
+        'l' => table::l,
+        'm' => table::m,
+        'n' => table::n,
+        'o' => table::o,
+        'p' => table::p,
+        'q' => table::q,
+        'r' => table::r,
+        's' => table::s,
+        't' => table::t,
+        'u' => table::u,
+        'v' => table::v,
+        'w' => table::w,
+        'x' => table::x,
+        'y' => table::y,
+        'z' => table::z,
+        ' ' => table::SPACE,
+        '&' => table::AMPERSAND,
+        '\'' => table::SIN_QUOTE,
+        '(' => table::L_PARENTH,
+        ')' => table::R_PARENTH,
+        '*' => table::ASTERISK,
+        '+' => table::PLUS,
+        '-' => table::MINUS,
+        '=' => table::EQUAL,
+        '.' => table::F_STOP,
+        '!' => table::EXCLAM,
+        '"' => table::D_QUOTE,
+        '#' => table::HASH,
+        '$' => table::DOLLAR,
+        '%' => table::PERCENT,
+        '^' => table::CARET,
+        '⌄' => table::D_CARET,
+        ',' => table::COMMA,
+        ':' => table::COLON,
+        ';' => table::S_COLON,
+        '?' => table::QUESTION,
+        '@' => table::AT,
+        '/' => table::SLASH,
+        '<' => table::LESS,
+        '>' => table::MORE,
+        '|' => table::V_BAR,
+        '\\' => table::B_SLASH,
+        '€' => table::EURO,
+        '£' => table::POUND,
+        '[' => table::L_BRACKET,
+        ']' => table::R_BRACKET,
+        '{' => table::L_BRACE,
+        '}' => table::R_BRACE,
+        '_' => table::U_SCORE,
+        '█' => table::CURSOR,
+        '~' => table::TILDE,
+        'Ä' => table::A_UMLAUT,
+        'Ö' => table::O_UMLAUT,
+        'Ü' => table::U_UMLAUT,
+        'ä' => table::a_umlaut,
+        'ö' => table::o_umlaut,
+        'ü' => table::u_umlaut,
+        'à' => table::a_grave,
+        'â' => table::a_circ,
+        'é' => table::e_acute,
+        'è' => table::e_grave,
+        'ê' => table::e_circ,
+        'î' => table::i_circ,
+        'ô' => table::o_circ,
+        'û' => table::u_circ,
+        'ç' => table::c_cedil,
+        'ß' => table::sharp_s,
+        '°' => table::DEGREE,
+        '♥' => table::HEART,
+        '←' => table::L_ARROW,
+        '→' => table::R_ARROW,
+        '↑' => table::U_ARROW,
+        '↓' => table::D_ARROW,
+        _ => return None,
+    };
+    Some(character)
+}
+
+/// Like [lookup], but draws unsupported characters as the replacement glyph.
+const fn lookup_or(c: char, replacement: Character) -> Character {
+    match lookup(c) {
+        Some(character) => character,
+        None => replacement,
+    }
+}
+
+/// The error returned when converting an unsupported `char` to a
+/// [Character].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnknownCharacter(pub char);
+
+impl core::fmt::Display for UnknownCharacter {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "unknown character {:?}", self.0)
+    }
+}
+
+impl TryFrom<char> for Character {
+    type Error = UnknownCharacter;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        lookup(value).ok_or(UnknownCharacter(value))
+    }
+}
+
+/// The byte is interpreted as Latin-1, which includes the umlauts and
+/// accented letters of the font. Unsupported bytes are converted to the
+/// [replacement](table::REPLACEMENT) glyph. Use
+/// [`TryFrom<char>`](Character::try_from) to detect them.
+impl From<u8> for Character {
+    fn from(value: u8) -> Self {
+        lookup_or(value as char, table::REPLACEMENT)
+    }
+}
+
+/// Decode the `char` starting at byte `i` of a valid UTF-8 string and
+/// return it along with its length in bytes. Unlike [str::chars], this works
+/// in constant expressions.
+const fn decode(bytes: &[u8], i: usize) -> (char, usize) {
+    let (code, len) = match bytes[i] {
+        b @ 0x00..=0x7f => (b as u32, 1),
+        b @ 0xc0..=0xdf => ((b as u32 & 0x1f) << 6 | (bytes[i + 1] as u32 & 0x3f), 2),
+        b @ 0xe0..=0xef => (
+            (b as u32 & 0x0f) << 12
+                | (bytes[i + 1] as u32 & 0x3f) << 6
+                | (bytes[i + 2] as u32 & 0x3f),
+            3,
+        ),
+        b => (
+            (b as u32 & 0x07) << 18
+                | (bytes[i + 1] as u32 & 0x3f) << 12
+                | (bytes[i + 2] as u32 & 0x3f) << 6
+                | (bytes[i + 3] as u32 & 0x3f),
+            4,
+        ),
+    };
+    match char::from_u32(code) {
+        Some(c) => (c, len),
+        None => panic!("invalid UTF-8"),
+    }
+}
+
+/// Whether every character of the text is supported.
 ///
-/// See []()
+/// This is used by [text!](crate::text) to check string literals while
+/// compiling.
 ///
-pub fn convert_str<const L: usize>(text: [u8; L]) -> ([Character; L], usize) {
-    let mut result = [Character::default(); L];
+pub const fn is_supported(text: &str) -> bool {
+    let bytes = text.as_bytes();
+    let mut i = 0;
+    while i < bytes.len() {
+        let (c, len) = decode(bytes, i);
+        if lookup(c).is_none() {
+            return false;
+        }
+        i += len;
+    }
+    true
+}
+
+/// Convert a string of `L` characters to an array of characters for
+/// display. Also returns the width of the text in pixels.
+///
+/// Unsupported characters are converted to the
+/// [replacement](table::REPLACEMENT) glyph. This function can be used to
+/// convert the text while compiling. To have the number of characters
+/// counted for you, use the macro [convert_str!](crate::convert_str!)
+/// instead.
+///
+/// ```
+/// use led_matrix::character::{convert_str, Character};
+///
+/// static GREETING: ([Character; 8], usize) = convert_str(" Grüezi ");
+///
+/// # fn app(matrix: &mut dyn led_matrix::LedMatrix) {
+/// let (text, length) = &GREETING;
+/// for offset in 0..*length {
+///     matrix.draw_text_billboard_frame(text, offset);
+///     matrix.apply();
+///     matrix.sleep_ms(50);
+/// }
+/// # }
+/// ```
+///
+/// # Panics
+///
+/// Panics if the string doesn't have exactly `L` characters. In a `static`
+/// or `const`, this is a compile error.
+///
+pub const fn convert_str<const L: usize>(text: &str) -> ([Character; L], usize) {
+    let bytes = text.as_bytes();
+    let mut result = [table::SPACE; L];
     let mut length = 0;
-    for (i, b) in text.iter().enumerate() {
-        let mut c = Character::from(*b);
+    let (mut i, mut n) = (0, 0);
+    while i < bytes.len() {
+        assert!(n < L, "the text has more than L characters");
+        let (c, len) = decode(bytes, i);
+        let mut c = lookup_or(c, table::REPLACEMENT);
         c.offset = length;
         length += c.width + 1;
-        result[i] = c;
+        result[n] = c;
+        i += len;
+        n += 1;
     }
-    (result, length - 1)
+    assert!(n == L, "the text has less than L characters");
+    (result, length.saturating_sub(1))
 }
 
-impl From<u8> for Character {
-    fn from(value: u8) -> Self {
-        match value {
-            b'0' => table::ZERO,
-            b'1' => table::ONE,
-            b'2' => table::TWO,
-            b'3' => table::THREE,
-            b'4' => table::FOUR,
-            b'5' => table::FIVE,
-            b'6' => table::SIX,
-            b'7' => table::SEVEN,
-            b'8' => table::EIGHT,
-            b'9' => table::NINE,
-            b'A' => table::A,
-            b'B' => table::B,
-            b'C' => table::C,
-            b'D' => table::D,
-            b'E' => table::E,
-            b'F' => table::F,
-            b'G' => table::G,
-            b'H' => table::H,
-            b'I' => table::I,
-            b'J' => table::J,
-            b'K' => table::K,
-            b'L' => table::L,
-            b'M' => table::M,
-            b'N' => table::N,
-            b'O' => table::O,
-            b'P' => table::P,
-            b'Q' => table::Q,
-            b'R' => table::R,
-            b'S' => table::S,
-            b'T' => table::T,
-            b'U' => table::U,
-            b'V' => table::V,
-            b'W' => table::W,
-            b'X' => table::X,
-            b'Y' => table::Y,
-            b'Z' => table::Z,
-            b'a' => table::a,
-            b'b' => table::b,
-            b'c' => table::c,
-            b'd' => table::d,
-            b'e' => table::e,
-            b'f' => table::f,
-            b'g' => table::g,
-            b'h' => table::h,
-            b'i' => table::i,
-            b'j' => table::j,
-            b'k' => table::k,
-            b'l' => table::l,
-            b'm' => table::m,
-            b'n' => table::n,
-            b'o' => table::o,
-            b'p' => table::p,
-            b'q' => table::q,
-            b'r' => table::r,
-            b's' => table::s,
-            b't' => table::t,
-            b'u' => table::u,
-            b'v' => table::v,
-            b'w' => table::w,
-            b'x' => table::x,
-            b'y' => table::y,
-            b'z' => table::z,
-            b' ' => table::SPACE,
-            b'&' => table::AMPERSAND,
-            b'\'' => table::SIN_QUOTE,
-            b'(' => table::L_PARENTH,
-            b')' => table::R_PARENTH,
-            b'*' => table::ASTERISK,
-            b'+' => table::PLUS,
-            b'-' => table::MINUS,
-            b'=' => table::EQUAL,
-            b'.' => table::F_STOP,
-            b'!' => table::EXCLAM,
-            b'"' => table::D_QUOTE,
-            b'#' => table::HASH,
-            b'$' => table::DOLLAR,
-            b'%' => table::PERCENT,
-            b'^' => table::CARET,
-            // b'⌄' => D_CARET,
-            b',' => table::COMMA,
-            b':' => table::COLON,
-            b';' => table::S_COLON,
-            b'?' => table::QUESTION,
-            b'@' => table::AT,
-            b'/' => table::SLASH,
-            b'<' => table::LESS,
-            b'>' => table::MORE,
-            b'|' => table::V_BAR,
-            b'\\' => table::B_SLASH,
-            // b'€' => EURO,
-            // b'£' => POUND,
-            b'[' => table::L_BRACKET,
-            b']' => table::R_BRACKET,
-            b'{' => table::L_BRACE,
-            b'}' => table::R_BRACE,
-            b'_' => table::U_SCORE,
-            // b'█' => CURSOR,
-            b'~' => table::TILDE,
-            _ => panic!("unknown character"),
+/// The number of characters in a string, as [convert_str] counts them.
+pub const fn char_count(text: &str) -> usize {
+    let bytes = text.as_bytes();
+    let (mut i, mut count) = (0, 0);
+    while i < bytes.len() {
+        // every character starts with a byte which isn't a continuation byte
+        if bytes[i] & 0b1100_0000 != 0b1000_0000 {
+            count += 1;
         }
+        i += 1;
+    }
+    count
+}
+
+/// Convert a string to an array of characters for display while compiling,
+/// like [convert_str](crate::character::convert_str), but count the characters
+/// automatically. Evaluates to the array and the width of the text in pixels.
+///
+/// ```
+/// use led_matrix::{character::Style, color, convert_str, marquee::Marquee};
+///
+/// # fn app(matrix: &mut dyn led_matrix::LedMatrix) {
+/// let (text, length) = &convert_str!("Grüezi mitenand");
+/// let style = Style::color(color::GREEN);
+/// Marquee::new(*length, 8).run(matrix, |marquee, matrix| {
+///     marquee.draw_text(matrix, text, &style)
+/// });
+/// # }
+/// ```
+///
+/// The text must be a constant expression, such as a literal.
+#[macro_export]
+macro_rules! convert_str {
+    ($text:expr) => {{
+        const TEXT: &str = $text;
+        const CONVERTED: (
+            [$crate::character::Character; $crate::character::char_count(TEXT)],
+            usize,
+        ) = $crate::character::convert_str(TEXT);
+        CONVERTED
+    }};
+}
+
+/// Check while compiling that every character of a string literal is
+/// supported by the default font [FONT_5X7], and evaluate to the literal.
+///
+/// Only the default font is checked: a character which [FONT_5X7] has but
+/// another font lacks, e.g. a letter in [FONT_3X3], still compiles and is
+/// drawn as the replacement glyph of that font.
+///
+/// ```
+/// use led_matrix::{character::FONT_5X7, color, text};
+///
+/// # fn app(matrix: &mut dyn led_matrix::LedMatrix) {
+/// matrix.draw_text((0, 0), text!("Hi!"), &FONT_5X7, color::WHITE);
+/// # }
+/// ```
+///
+/// A typo is a compile error instead of a wrong glyph on the matrix:
+///
+/// ```compile_fail
+/// let price = led_matrix::text!("5 ¥");
+/// ```
+#[macro_export]
+macro_rules! text {
+    ($text:literal) => {{
+        const TEXT: &str = $text;
+        const _: () = assert!(
+            $crate::character::is_supported(TEXT),
+            "unsupported character in text"
+        );
+        TEXT
+    }};
+}
+
+/// A bitmap font, which maps characters to glyphs.
+///
+/// The crate comes with [FONT_5X7], [FONT_4X6], [FONT_3X5] and [FONT_3X3].
+/// Other fonts consist of a function looking up the glyphs, like [lookup]
+/// for the default font.
+///
+/// ```
+/// use led_matrix::{
+///     character::{table, Font, FONT_5X7},
+///     color,
+/// };
+///
+/// // draw unsupported characters as a question mark instead of a box
+/// static FONT: Font = FONT_5X7.with_replacement(table::QUESTION);
+///
+/// # fn app(matrix: &mut dyn led_matrix::LedMatrix) {
+/// matrix.draw_text((0, 0), "¥", &FONT, color::RED);
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Font {
+    lookup: fn(char) -> Option<Character>,
+    height: usize,
+    replacement: Character,
+}
+
+impl Font {
+    /// Construct a font from a function looking up its glyphs.
+    ///
+    /// The glyphs are `height` pixels high, counted from their lowest row,
+    /// e.g. the tail of a "g". Unsupported characters are drawn as the
+    /// `replacement` glyph.
+    pub const fn new(
+        lookup: fn(char) -> Option<Character>,
+        height: usize,
+        replacement: Character,
+    ) -> Self {
+        Self {
+            lookup,
+            height,
+            replacement,
+        }
+    }
+
+    /// Draw unsupported characters as another glyph, e.g.
+    /// [table::QUESTION].
+    pub const fn with_replacement(mut self, replacement: Character) -> Self {
+        self.replacement = replacement;
+        self
+    }
+
+    /// The glyph of a `char`, or `None` if the font doesn't support it.
+    pub fn glyph(&self, c: char) -> Option<Character> {
+        (self.lookup)(c)
+    }
+
+    /// The glyph of a `char`, which is the replacement glyph if the font
+    /// doesn't support it.
+    pub fn glyph_or_replacement(&self, c: char) -> Character {
+        self.glyph(c).unwrap_or(self.replacement)
+    }
+
+    pub const fn replacement(&self) -> Character {
+        self.replacement
+    }
+
+    /// How far to move to the right after drawing a `char`, its width plus
+    /// the empty column between two characters.
+    pub fn advance(&self, c: char) -> usize {
+        self.glyph_or_replacement(c).width + 1
+    }
+
+    /// The height of the glyphs in pixels.
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The distance between two lines of text, including an empty row
+    /// between them.
+    pub const fn line_height(&self) -> usize {
+        self.height + 1
+    }
+
+    /// The width of a text in pixels, including the empty column between two
+    /// characters.
+    pub fn text_width(&self, text: impl IntoIterator<Item = char>) -> usize {
+        let width: usize = text.into_iter().map(|c| self.advance(c)).sum();
+        width.saturating_sub(1)
+    }
+}
+
+impl Default for Font {
+    fn default() -> Self {
+        FONT_5X7
+    }
+}
+
+/// How to color text: the color of its characters and an optional
+/// background.
+///
+/// The characters can have a single color, cycle through a list of colors or
+/// be colored pixel by pixel with a shader.
+///
+/// ```
+/// use led_matrix::{
+///     character::{self, Style, FONT_5X7},
+///     color,
+/// };
+///
+/// # fn app(matrix: &mut dyn led_matrix::LedMatrix) {
+/// let warning = Style::color(color::YELLOW).with_background(color::RED);
+/// matrix.draw_styled_text((0, 0), format_args!("!"), &FONT_5X7, &warning);
+///
+/// let flags = Style::colors(&[color::RED, color::WHITE]);
+/// matrix.draw_styled_text((0, 0), format_args!("CH"), &FONT_5X7, &flags);
+///
+/// let fade = character::gradient(color::BLUE, color::PINK, 8);
+/// matrix.draw_styled_text((0, 0), format_args!("Hi"), &FONT_5X7, &Style::shader(&fade));
+/// # }
+/// ```
+#[derive(Clone, Copy)]
+pub struct Style<'a> {
+    foreground: Foreground<'a>,
+    background: Option<(u8, u8, u8)>,
+}
+
+#[derive(Clone, Copy)]
+enum Foreground<'a> {
+    Color((u8, u8, u8)),
+    Colors(&'a [(u8, u8, u8)]),
+    Shader(&'a dyn Fn(Pixel) -> (u8, u8, u8)),
+}
+
+/// A pixel of a character, which a [shader](Style::shader) computes the color
+/// of.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pixel {
+    /// The column within the text, counted from its left edge. It stays the
+    /// same while the text scrolls.
+    pub x: usize,
+    /// The row, counted from the bottom of the line.
+    pub y: usize,
+    /// The index of the character within the text.
+    pub index: usize,
+}
+
+impl<'a> Style<'a> {
+    /// Draw all characters in the same color.
+    pub const fn color(color: (u8, u8, u8)) -> Self {
+        Self {
+            foreground: Foreground::Color(color),
+            background: None,
+        }
+    }
+
+    /// Draw every character in the next color of the list, starting over
+    /// after the last one.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no colors.
+    pub const fn colors(colors: &'a [(u8, u8, u8)]) -> Self {
+        assert!(!colors.is_empty(), "style without colors");
+        Self {
+            foreground: Foreground::Colors(colors),
+            background: None,
+        }
+    }
+
+    /// Compute the color of every pixel with a function, e.g. [rainbow] or
+    /// [gradient].
+    pub const fn shader(shader: &'a dyn Fn(Pixel) -> (u8, u8, u8)) -> Self {
+        Self {
+            foreground: Foreground::Shader(shader),
+            background: None,
+        }
+    }
+
+    /// Fill the area of the text with a background color, instead of
+    /// drawing the characters on top of what's already there.
+    pub const fn with_background(mut self, background: (u8, u8, u8)) -> Self {
+        self.background = Some(background);
+        self
+    }
+
+    pub const fn background(&self) -> Option<(u8, u8, u8)> {
+        self.background
+    }
+
+    /// The color of a pixel of a character.
+    pub fn foreground(&self, pixel: Pixel) -> (u8, u8, u8) {
+        match self.foreground {
+            Foreground::Color(color) => color,
+            Foreground::Colors(colors) => colors[pixel.index % colors.len()],
+            Foreground::Shader(shader) => shader(pixel),
+        }
+    }
+}
+
+impl From<(u8, u8, u8)> for Style<'_> {
+    fn from(color: (u8, u8, u8)) -> Self {
+        Self::color(color)
+    }
+}
+
+impl fmt::Debug for Style<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("Style");
+        match self.foreground {
+            Foreground::Color(color) => debug.field("color", &color),
+            Foreground::Colors(colors) => debug.field("colors", &colors),
+            Foreground::Shader(_) => debug.field("shader", &format_args!("..")),
+        };
+        debug.field("background", &self.background).finish()
+    }
+}
+
+/// A shader which colors the text in the colors of the rainbow, changing
+/// from column to column.
+///
+/// To let the colors move along the text, shift the pixels over time:
+///
+/// ```
+/// use led_matrix::character::{rainbow, Pixel, Style};
+///
+/// let time = 7;
+/// let moving = |pixel: Pixel| rainbow(Pixel { x: pixel.x + time, ..pixel });
+/// let style = Style::shader(&moving);
+/// ```
+pub fn rainbow(pixel: Pixel) -> (u8, u8, u8) {
+    let hue = (pixel.x % 18) as f32 * 20.0;
+    color::Color::from_hsv(hue, 1.0, 1.0).into()
+}
+
+/// A shader which fades from one color to another over the first `width`
+/// columns of the text, e.g. its [width](Font::text_width).
+pub fn gradient(
+    from: (u8, u8, u8),
+    to: (u8, u8, u8),
+    width: usize,
+) -> impl Fn(Pixel) -> (u8, u8, u8) {
+    move |pixel| {
+        let t = match width {
+            0 | 1 => 255,
+            _ => (pixel.x.min(width - 1) * 255 / (width - 1)) as u8,
+        };
+        color::Color::from(from).lerp(to.into(), t).into()
+    }
+}
+
+/// Draws text as it's being formatted, so formatting doesn't need to
+/// allocate.
+pub(crate) struct TextWriter<'a, const W: usize, const H: usize, M: LedMatrix<W, H> + ?Sized> {
+    matrix: &'a mut M,
+    // position of the next character
+    x: i32,
+    y: i32,
+    font: &'a Font,
+    style: &'a Style<'a>,
+    // width of the text drawn so far, including the empty column after it
+    width: usize,
+    // number of characters drawn so far
+    index: usize,
+}
+
+impl<'a, const W: usize, const H: usize, M: LedMatrix<W, H> + ?Sized> TextWriter<'a, W, H, M> {
+    pub(crate) fn new(
+        matrix: &'a mut M,
+        (x, y): (i32, i32),
+        font: &'a Font,
+        style: &'a Style<'a>,
+    ) -> Self {
+        Self {
+            matrix,
+            x,
+            y,
+            font,
+            style,
+            width: 0,
+            index: 0,
+        }
+    }
+
+    /// The width of the text drawn so far.
+    pub(crate) fn width(&self) -> usize {
+        self.width.saturating_sub(1)
+    }
+}
+
+impl<const W: usize, const H: usize, M: LedMatrix<W, H> + ?Sized> fmt::Write
+    for TextWriter<'_, W, H, M>
+{
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            let character = self.font.glyph_or_replacement(c);
+            if let Some(background) = self.style.background {
+                // including the empty column to the previous character
+                let first = if self.index == 0 { 0 } else { -1 };
+                for x in first..character.width as i32 {
+                    for y in 0..self.font.height as i32 {
+                        let position = (self.x.saturating_add(x), self.y.saturating_add(y));
+                        self.matrix.draw_point(position, background);
+                    }
+                }
+            }
+            for &(x, y) in character.coordinates {
+                let position = (
+                    self.x.saturating_add(x as i32),
+                    self.y.saturating_add(y as i32),
+                );
+                let pixel = Pixel {
+                    x: self.width + x,
+                    y,
+                    index: self.index,
+                };
+                self.matrix
+                    .draw_point(position, self.style.foreground(pixel));
+            }
+            self.x = self.x.saturating_add(character.width as i32 + 1);
+            self.width += character.width + 1;
+            self.index += 1;
+        }
+        Ok(())
+    }
+}
+
+/// The columns of pixels of a text, from left to right.
+///
+/// Every column is a bit mask, where bit `y` is set if the pixel in row `y`
+/// (counted from the bottom) is lit. There is an empty column between two
+/// characters.
+#[derive(Debug, Clone)]
+pub struct Columns<I: Iterator<Item = char>> {
+    chars: I,
+    current: Option<Character>,
+    // next column of the current character
+    column: usize,
+    // number of characters started so far
+    count: usize,
+    font: Font,
+}
+
+impl<I: Iterator<Item = char>> Columns<I> {
+    /// The columns of a text drawn with a font.
+    pub fn new(text: impl IntoIterator<IntoIter = I>, font: &Font) -> Self {
+        Self {
+            chars: text.into_iter(),
+            current: None,
+            column: 0,
+            count: 0,
+            font: *font,
+        }
+    }
+}
+
+impl<I: Iterator<Item = char>> Iterator for Columns<I> {
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.current {
+            Some(character) if self.column < character.width => {
+                let column = self.column;
+                self.column += 1;
+                Some(
+                    character
+                        .coordinates
+                        .iter()
+                        .fold(0, |mask, &(x, y)| match x == column {
+                            true => mask | 1 << y,
+                            false => mask,
+                        }),
+                )
+            }
+            _ => {
+                let first = self.current.is_none();
+                let c = self.chars.next()?;
+                self.current = Some(self.font.glyph_or_replacement(c));
+                self.column = 0;
+                self.count += 1;
+                match first {
+                    true => self.next(),
+                    // the empty column between two characters
+                    false => Some(0),
+                }
+            }
+        }
+    }
+}
+
+/// Scroll a text across the matrix from right to left, one column per frame.
+///
+/// The text enters on the right edge and the iterator ends once it has left
+/// on the left edge. The text may be any iterator over `char`s, even an
+/// endless one:
+///
+/// ```
+/// use led_matrix::{
+///     character::{scroll, FONT_5X7},
+///     color,
+/// };
+///
+/// # fn app(matrix: &mut dyn led_matrix::LedMatrix) {
+/// for frame in scroll("Hello, world! ".chars().cycle(), &FONT_5X7) {
+///     frame.draw(matrix, color::AQUA);
+///     matrix.apply();
+///     matrix.sleep_ms(50);
+/// }
+/// # }
+/// ```
+///
+pub fn scroll<const W: usize, T: IntoIterator<Item = char>>(
+    text: T,
+    font: &Font,
+) -> Scroll<T::IntoIter, W> {
+    Scroll {
+        columns: Columns::new(text, font),
+        window: TextFrame {
+            columns: [0; W],
+            indices: [0; W],
+            x: -(W as isize),
+        },
+        trailing: 0,
+    }
+}
+
+/// An iterator over the frames of scrolling text, see [scroll].
+#[derive(Debug, Clone)]
+pub struct Scroll<I: Iterator<Item = char>, const W: usize = 8> {
+    columns: Columns<I>,
+    window: TextFrame<W>,
+    // number of empty columns shifted in after the end of the text
+    trailing: usize,
+}
+
+impl<I: Iterator<Item = char>, const W: usize> Iterator for Scroll<I, W> {
+    type Item = TextFrame<W>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let column = match self.columns.next() {
+            Some(column) => column,
+            None => {
+                self.trailing += 1;
+                0
+            }
+        };
+        // the text has left the matrix, or there never was any
+        let blank = self.window.columns.iter().all(|&c| c == 0);
+        if self.trailing >= W || self.trailing > 0 && blank {
+            return None;
+        }
+        self.window.columns.rotate_left(1);
+        self.window.columns[W - 1] = column;
+        self.window.indices.rotate_left(1);
+        self.window.indices[W - 1] = self.columns.count.saturating_sub(1);
+        self.window.x += 1;
+        Some(self.window.clone())
+    }
+}
+
+/// A single frame of scrolling text, see [scroll].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextFrame<const W: usize = 8> {
+    // the lit pixels of every column as a bit mask, see Columns
+    columns: [u32; W],
+    // the index of the character every column belongs to
+    indices: [usize; W],
+    // the column of the text at the left edge of the matrix
+    x: isize,
+}
+
+impl<const W: usize> TextFrame<W> {
+    /// Draw the frame at the bottom of the matrix, the lit pixels in the
+    /// given color. All other LEDs are turned off.
+    pub fn draw<const H: usize>(&self, matrix: &mut dyn LedMatrix<W, H>, color: (u8, u8, u8)) {
+        self.draw_styled(matrix, &Style::color(color));
+    }
+
+    /// Draw the frame at the bottom of the matrix with a [Style]. All other
+    /// LEDs are set to the background color, or turned off if there is none.
+    pub fn draw_styled<const H: usize>(&self, matrix: &mut dyn LedMatrix<W, H>, style: &Style) {
+        let background = style.background.unwrap_or((0, 0, 0));
+        for (i, column) in self.columns.iter().enumerate() {
+            for y in 0..H {
+                matrix[(i, y)] = match y < 32 && column & (1 << y) != 0 {
+                    true => style.foreground(Pixel {
+                        // lit pixels are always within the text
+                        x: (self.x + i as isize) as usize,
+                        y,
+                        index: self.indices[i],
+                    }),
+                    false => background,
+                };
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::{
+        convert_str, gradient, is_supported, lookup, rainbow, scroll, table, table_3x3, Character,
+        Columns, Pixel, Style, UnknownCharacter, FONT_3X3, FONT_3X5,
+    };
+    use crate::{color, layout::TextBox, LedMatrix as _};
+    use core::cell::RefCell;
+    use led_matrix_headless::LedMatrix;
+    use std::{format, string::String, vec::Vec};
+
+    fn same(a: Character, b: Character) -> bool {
+        a.coordinates == b.coordinates && a.width == b.width
+    }
+
+    /// Draw on an 8x8 matrix and render it as text, from the top row down.
+    /// White is `#`, red, green and blue are `r`, `g` and `b`.
+    fn render(draw: impl FnOnce(&mut LedMatrix)) -> Vec<String> {
+        let mut matrix = LedMatrix::new();
+        draw(&mut matrix);
+        matrix.apply();
+        let leds = matrix.last_frame().unwrap().leds;
+        leds.iter()
+            .map(|row| {
+                row.iter()
+                    .map(|&led| match led {
+                        color::BLACK => '.',
+                        color::WHITE => '#',
+                        color::RED => 'r',
+                        color::GREEN => 'g',
+                        color::BLUE => 'b',
+                        _ => '?',
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn two_lines_of_the_3x3_font_fit_on_the_matrix() {
+        assert_eq!(TextBox::new(&FONT_3X3, (8, 8)).lines_per_page(), 2);
+        assert_eq!(TextBox::new(&FONT_3X5, (8, 8)).lines_per_page(), 1);
+
+        let text_box = TextBox::new(&FONT_3X3, (8, 8));
+        let style = Style::color(color::WHITE);
+        let rendered = render(|matrix| text_box.draw(matrix, (0, 0), "12 34", &style));
+        assert_eq!(
+            rendered,
+            [
+                "##..##..", //
+                ".#...#..", "###..##.", "........", "###.#.#.", ".##.###.", "###...#.", "........",
+            ]
+        );
+    }
+
+    #[test]
+    fn digits_of_the_3x3_font_are_distinct() {
+        let digits: Vec<Vec<(usize, usize)>> = ('0'..='9')
+            .map(|c| {
+                let mut pixels = table_3x3::lookup(c).unwrap().coordinates.to_vec();
+                pixels.sort();
+                pixels
+            })
+            .collect();
+        for (i, a) in digits.iter().enumerate() {
+            assert!(a.iter().all(|&(x, y)| x < 3 && y < 3));
+            for b in &digits[i + 1..] {
+                assert_ne!(a, b);
+            }
+        }
+        assert!(table_3x3::lookup('a').is_none());
+    }
+
+    #[test]
+    fn background_fills_the_cells_and_the_gaps() {
+        let style = Style::color(color::WHITE).with_background(color::BLUE);
+        let rendered = render(|matrix| {
+            let args = format_args!("12");
+            assert_eq!(matrix.draw_styled_text((1, 1), args, &FONT_3X3, &style), 7);
+        });
+        assert_eq!(
+            rendered,
+            [
+                "........", //
+                "........", "........", "........", ".##bb##b", ".b#bbb#b", ".###bb##", "........",
+            ]
+        );
+    }
+
+    #[test]
+    fn colors_per_character() {
+        let style = Style::colors(&[color::RED, color::GREEN]);
+        let rendered = render(|matrix| {
+            matrix.draw_styled_text((0, 0), format_args!(".-."), &FONT_3X3, &style);
+        });
+        assert_eq!(rendered[5..], ["........", "..ggg...", "r.....r."]);
+    }
+
+    #[test]
+    fn shader_pixels() {
+        let pixels = RefCell::new(Vec::new());
+        let shader = |pixel: Pixel| {
+            pixels.borrow_mut().push(pixel);
+            color::WHITE
+        };
+        render(|matrix| {
+            let style = Style::shader(&shader);
+            matrix.draw_styled_text((3, 2), format_args!("7-"), &FONT_3X3, &style);
+        });
+        let mut pixels = pixels.into_inner();
+        pixels.sort_by_key(|pixel| (pixel.index, pixel.x, pixel.y));
+        let pixel = |x, y, index| Pixel { x, y, index };
+        // the columns continue after the gap between the characters, the rows
+        // are counted from the bottom of the glyphs
+        assert_eq!(
+            pixels,
+            [
+                pixel(0, 2, 0),
+                pixel(1, 2, 0),
+                pixel(2, 0, 0),
+                pixel(2, 1, 0),
+                pixel(2, 2, 0),
+                pixel(4, 1, 1),
+                pixel(5, 1, 1),
+                pixel(6, 1, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn rainbow_and_gradient() {
+        let pixel = |x| Pixel { x, y: 0, index: 0 };
+        assert_eq!(rainbow(pixel(0)), color::RED);
+        assert_eq!(rainbow(pixel(6)), color::LIME);
+        assert_eq!(rainbow(pixel(12)), color::BLUE);
+        assert_eq!(rainbow(pixel(18)), color::RED);
+
+        let fade = gradient(color::BLACK, color::WHITE, 5);
+        assert_eq!(fade(pixel(0)), color::BLACK);
+        assert_eq!(fade(pixel(2)), (127, 127, 127));
+        assert_eq!(fade(pixel(4)), color::WHITE);
+        assert_eq!(fade(pixel(9)), color::WHITE);
+        // too narrow to fade
+        assert_eq!(gradient(color::BLACK, color::RED, 1)(pixel(0)), color::RED);
+    }
+
+    #[test]
+    fn draw_text_at_an_offset() {
+        let rendered = render(|matrix| {
+            assert_eq!(matrix.draw_text((2, 1), "12", &FONT_3X3, color::WHITE), 7);
+        });
+        assert_eq!(
+            rendered[4..],
+            ["..##..##", "...#...#", "..###..#", "........"]
+        );
+    }
+
+    #[test]
+    fn draw_text_clipped_at_the_edges() {
+        let rendered = render(|matrix| {
+            // the width includes the columns which aren't on the matrix
+            assert_eq!(matrix.draw_text((-2, 0), "12", &FONT_3X3, color::WHITE), 7);
+            assert_eq!(matrix.draw_text((6, 4), "12", &FONT_3X3, color::WHITE), 7);
+        });
+        assert_eq!(
+            rendered,
+            [
+                "........", //
+                "......##", ".......#", "......##", "........", "..##....", "...#....", "#..##...",
+            ]
+        );
+    }
+
+    #[test]
+    fn formatting_across_several_writes() {
+        // the pieces of the format string and every argument are written one
+        // after another, the characters still count through all of them
+        let (dot, number) = ('.', 7);
+        let style = Style::colors(&[color::RED, color::GREEN]);
+        let rendered = render(|matrix| {
+            let args = format_args!("{dot}{number}{dot}");
+            assert_eq!(matrix.draw_styled_text((0, 0), args, &FONT_3X3, &style), 7);
+        });
+        assert_eq!(rendered[5..], ["..ggg...", "....g...", "r...g.r."]);
+    }
+
+    #[test]
+    fn columns() {
+        let columns: Vec<u32> = Columns::new("1-".chars(), &FONT_3X3).collect();
+        assert_eq!(columns, [0b101, 0b111, 0b001, 0, 0b010, 0b010, 0b010]);
+        assert_eq!(Columns::new("".chars(), &FONT_3X3).count(), 0);
+    }
+
+    #[test]
+    fn scroll_text() {
+        let frames: Vec<_> = scroll::<8, _>("1".chars(), &FONT_3X3).collect();
+        // 3 frames until the text is on the matrix, 7 until it has left
+        assert_eq!(frames.len(), 10);
+
+        let style = Style::color(color::WHITE).with_background(color::BLUE);
+        let draw =
+            |frame: usize| render(|matrix| frames[frame].draw_styled(matrix, &style))[5..].to_vec();
+        // the text enters on the right edge
+        assert_eq!(draw(0), ["bbbbbbb#", "bbbbbbbb", "bbbbbbb#"]);
+        assert_eq!(draw(2), ["bbbbb##b", "bbbbbb#b", "bbbbb###"]);
+        // and its last column leaves on the left edge
+        assert_eq!(draw(9), ["bbbbbbbb", "bbbbbbbb", "#bbbbbbb"]);
+
+        assert_eq!(scroll::<8, _>("".chars(), &FONT_3X3).count(), 0);
+        assert_eq!(scroll::<8, _>(" ".chars(), &FONT_3X3).count(), 2);
+    }
+
+    #[test]
+    fn scroll_shader_pixels() {
+        let pixels = RefCell::new(Vec::new());
+        let shader = |pixel: Pixel| {
+            pixels.borrow_mut().push(pixel);
+            color::WHITE
+        };
+        let frame = scroll::<8, _>("-1".chars(), &FONT_3X3).nth(5).unwrap();
+        render(|matrix| frame.draw_styled(matrix, &Style::shader(&shader)));
+        let mut pixels = pixels.into_inner();
+        pixels.sort_by_key(|pixel| (pixel.x, pixel.y));
+        let pixel = |x, y, index| Pixel { x, y, index };
+        // the columns of the text are counted from its left edge
+        assert_eq!(
+            pixels,
+            [
+                pixel(0, 1, 0),
+                pixel(1, 1, 0),
+                pixel(2, 1, 0),
+                pixel(4, 0, 1),
+                pixel(4, 2, 1),
+                pixel(5, 0, 1),
+                pixel(5, 1, 1),
+                pixel(5, 2, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn lookup_and_conversions() {
+        assert!(same(lookup('A').unwrap(), table::A));
+        assert!(lookup('¥').is_none());
+
+        assert!(same(Character::try_from('ä').unwrap(), table::a_umlaut));
+        let error = Character::try_from('¥').unwrap_err();
+        assert_eq!(error, UnknownCharacter('¥'));
+        assert_eq!(format!("{error}"), "unknown character '¥'");
+
+        // bytes are Latin-1
+        assert!(same(Character::from(0xe4), table::a_umlaut));
+        assert!(same(Character::from(0xa5), table::REPLACEMENT));
+
+        assert!(is_supported("Grüezi"));
+        assert!(!is_supported("5 ¥"));
+        let (text, width) = convert_str::<2>("¥A");
+        assert!(same(text[0], table::REPLACEMENT));
+        assert_eq!(text[1].offset, table::REPLACEMENT.width + 1);
+        assert_eq!(width, table::REPLACEMENT.width + 1 + table::A.width);
+    }
+
+    #[test]
+    fn unsupported_chars_are_drawn_as_the_replacement() {
+        let rendered = render(|matrix| {
+            matrix.draw_text((0, 0), "1a", &FONT_3X3, color::WHITE);
+        });
+        assert_eq!(rendered[5..], ["##..###.", ".#..###.", "###.###."]);
+
+        let font = FONT_3X3.with_replacement(table_3x3::MINUS);
+        assert!(same(font.replacement(), table_3x3::MINUS));
+        let rendered = render(|matrix| {
+            matrix.draw_text((0, 0), "1a", &font, color::WHITE);
+        });
+        assert_eq!(rendered[5..], ["##......", ".#..###.", "###....."]);
+    }
+
+    #[test]
+    fn advance_of_unsupported_chars() {
+        assert!(FONT_3X3.glyph('a').is_none());
+        assert_eq!(FONT_3X3.advance('a'), 4);
+        assert_eq!(FONT_3X3.text_width("a1".chars()), 7);
+
+        let font = FONT_3X3.with_replacement(table_3x3::F_STOP);
+        assert_eq!(font.advance('a'), 2);
+        assert_eq!(font.text_width("a1".chars()), 5);
+        let rendered = render(|matrix| {
+            assert_eq!(matrix.draw_text((0, 0), "a1", &font, color::WHITE), 5);
+        });
+        assert_eq!(rendered[7], "#.###...");
     }
 }
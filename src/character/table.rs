@@ -1482,3 +1482,435 @@ pub const CURSOR: Character = Character::new(
     5,
 );
 pub const TILDE: Character = Character::new(&[(0, 5), (1, 6), (2, 5), (3, 6)], 4);
+pub const A_UMLAUT: Character = Character::new(
+    &[
+        (1, 6),
+        (3, 6),
+        (1, 4),
+        (2, 4),
+        (3, 4),
+        (0, 3),
+        (4, 3),
+        (0, 2),
+        (1, 2),
+        (2, 2),
+        (3, 2),
+        (4, 2),
+        (0, 1),
+        (4, 1),
+        (0, 0),
+        (4, 0),
+    ],
+    5,
+);
+pub const O_UMLAUT: Character = Character::new(
+    &[
+        (0, 6),
+        (4, 6),
+        (1, 5),
+        (2, 5),
+        (3, 5),
+        (0, 4),
+        (4, 4),
+        (0, 3),
+        (4, 3),
+        (0, 2),
+        (4, 2),
+        (0, 1),
+        (4, 1),
+        (1, 0),
+        (2, 0),
+        (3, 0),
+    ],
+    5,
+);
+pub const U_UMLAUT: Character = Character::new(
+    &[
+        (1, 6),
+        (3, 6),
+        (0, 4),
+        (4, 4),
+        (0, 3),
+        (4, 3),
+        (0, 2),
+        (4, 2),
+        (0, 1),
+        (4, 1),
+        (1, 0),
+        (2, 0),
+        (3, 0),
+    ],
+    5,
+);
+pub const a_umlaut: Character = Character::new(
+    &[
+        (1, 6),
+        (3, 6),
+        (1, 4),
+        (2, 4),
+        (3, 4),
+        (4, 3),
+        (1, 2),
+        (2, 2),
+        (3, 2),
+        (4, 2),
+        (0, 1),
+        (4, 1),
+        (1, 0),
+        (2, 0),
+        (3, 0),
+        (4, 0),
+    ],
+    5,
+);
+pub const o_umlaut: Character = Character::new(
+    &[
+        (1, 6),
+        (3, 6),
+        (1, 4),
+        (2, 4),
+        (3, 4),
+        (0, 3),
+        (4, 3),
+        (0, 2),
+        (4, 2),
+        (0, 1),
+        (4, 1),
+        (1, 0),
+        (2, 0),
+        (3, 0),
+    ],
+    5,
+);
+pub const u_umlaut: Character = Character::new(
+    &[
+        (1, 6),
+        (3, 6),
+        (0, 4),
+        (4, 4),
+        (0, 3),
+        (4, 3),
+        (0, 2),
+        (4, 2),
+        (0, 1),
+        (3, 1),
+        (4, 1),
+        (1, 0),
+        (2, 0),
+        (4, 0),
+    ],
+    5,
+);
+pub const a_grave: Character = Character::new(
+    &[
+        (1, 6),
+        (2, 5),
+        (1, 4),
+        (2, 4),
+        (3, 4),
+        (4, 3),
+        (1, 2),
+        (2, 2),
+        (3, 2),
+        (4, 2),
+        (0, 1),
+        (4, 1),
+        (1, 0),
+        (2, 0),
+        (3, 0),
+        (4, 0),
+    ],
+    5,
+);
+pub const a_circ: Character = Character::new(
+    &[
+        (2, 6),
+        (1, 5),
+        (3, 5),
+        (1, 4),
+        (2, 4),
+        (3, 4),
+        (4, 3),
+        (1, 2),
+        (2, 2),
+        (3, 2),
+        (4, 2),
+        (0, 1),
+        (4, 1),
+        (1, 0),
+        (2, 0),
+        (3, 0),
+        (4, 0),
+    ],
+    5,
+);
+pub const e_acute: Character = Character::new(
+    &[
+        (3, 6),
+        (2, 5),
+        (1, 4),
+        (2, 4),
+        (3, 4),
+        (0, 3),
+        (4, 3),
+        (0, 2),
+        (1, 2),
+        (2, 2),
+        (3, 2),
+        (4, 2),
+        (0, 1),
+        (1, 0),
+        (2, 0),
+        (3, 0),
+        (4, 0),
+    ],
+    5,
+);
+pub const e_grave: Character = Character::new(
+    &[
+        (1, 6),
+        (2, 5),
+        (1, 4),
+        (2, 4),
+        (3, 4),
+        (0, 3),
+        (4, 3),
+        (0, 2),
+        (1, 2),
+        (2, 2),
+        (3, 2),
+        (4, 2),
+        (0, 1),
+        (1, 0),
+        (2, 0),
+        (3, 0),
+        (4, 0),
+    ],
+    5,
+);
+pub const e_circ: Character = Character::new(
+    &[
+        (2, 6),
+        (1, 5),
+        (3, 5),
+        (1, 4),
+        (2, 4),
+        (3, 4),
+        (0, 3),
+        (4, 3),
+        (0, 2),
+        (1, 2),
+        (2, 2),
+        (3, 2),
+        (4, 2),
+        (0, 1),
+        (1, 0),
+        (2, 0),
+        (3, 0),
+        (4, 0),
+    ],
+    5,
+);
+pub const i_circ: Character = Character::new(
+    &[
+        (1, 6),
+        (0, 5),
+        (2, 5),
+        (0, 4),
+        (1, 4),
+        (1, 3),
+        (1, 2),
+        (1, 1),
+        (0, 0),
+        (1, 0),
+        (2, 0),
+    ],
+    3,
+);
+pub const o_circ: Character = Character::new(
+    &[
+        (2, 6),
+        (1, 5),
+        (3, 5),
+        (1, 4),
+        (2, 4),
+        (3, 4),
+        (0, 3),
+        (4, 3),
+        (0, 2),
+        (4, 2),
+        (0, 1),
+        (4, 1),
+        (1, 0),
+        (2, 0),
+        (3, 0),
+    ],
+    5,
+);
+pub const u_circ: Character = Character::new(
+    &[
+        (2, 6),
+        (1, 5),
+        (3, 5),
+        (0, 4),
+        (4, 4),
+        (0, 3),
+        (4, 3),
+        (0, 2),
+        (4, 2),
+        (0, 1),
+        (3, 1),
+        (4, 1),
+        (1, 0),
+        (2, 0),
+        (4, 0),
+    ],
+    5,
+);
+pub const c_cedil: Character = Character::new(
+    &[
+        (1, 6),
+        (2, 6),
+        (3, 6),
+        (0, 5),
+        (4, 5),
+        (0, 4),
+        (0, 3),
+        (4, 3),
+        (1, 2),
+        (2, 2),
+        (3, 2),
+        (2, 1),
+        (1, 0),
+        (2, 0),
+    ],
+    5,
+);
+pub const sharp_s: Character = Character::new(
+    &[
+        (1, 6),
+        (2, 6),
+        (0, 5),
+        (3, 5),
+        (0, 4),
+        (2, 4),
+        (0, 3),
+        (3, 3),
+        (0, 2),
+        (4, 2),
+        (0, 1),
+        (4, 1),
+        (0, 0),
+        (2, 0),
+        (3, 0),
+    ],
+    5,
+);
+pub const DEGREE: Character = Character::new(&[(1, 6), (0, 5), (2, 5), (1, 4)], 3);
+pub const HEART: Character = Character::new(
+    &[
+        (1, 5),
+        (3, 5),
+        (0, 4),
+        (1, 4),
+        (2, 4),
+        (3, 4),
+        (4, 4),
+        (0, 3),
+        (1, 3),
+        (2, 3),
+        (3, 3),
+        (4, 3),
+        (1, 2),
+        (2, 2),
+        (3, 2),
+        (2, 1),
+    ],
+    5,
+);
+pub const L_ARROW: Character = Character::new(
+    &[
+        (2, 5),
+        (1, 4),
+        (0, 3),
+        (1, 3),
+        (2, 3),
+        (3, 3),
+        (4, 3),
+        (1, 2),
+        (2, 1),
+    ],
+    5,
+);
+pub const R_ARROW: Character = Character::new(
+    &[
+        (2, 5),
+        (3, 4),
+        (0, 3),
+        (1, 3),
+        (2, 3),
+        (3, 3),
+        (4, 3),
+        (3, 2),
+        (2, 1),
+    ],
+    5,
+);
+pub const U_ARROW: Character = Character::new(
+    &[
+        (2, 6),
+        (1, 5),
+        (2, 5),
+        (3, 5),
+        (0, 4),
+        (2, 4),
+        (4, 4),
+        (2, 3),
+        (2, 2),
+        (2, 1),
+        (2, 0),
+    ],
+    5,
+);
+pub const D_ARROW: Character = Character::new(
+    &[
+        (2, 6),
+        (2, 5),
+        (2, 4),
+        (2, 3),
+        (0, 2),
+        (2, 2),
+        (4, 2),
+        (1, 1),
+        (2, 1),
+        (3, 1),
+        (2, 0),
+    ],
+    5,
+);
+/// Drawn in place of characters which aren't supported, a hollow box.
+pub const REPLACEMENT: Character = Character::new(
+    &[
+        (0, 0),
+        (1, 0),
+        (2, 0),
+        (3, 0),
+        (4, 0),
+        (0, 1),
+        (4, 1),
+        (0, 2),
+        (4, 2),
+        (0, 3),
+        (4, 3),
+        (0, 4),
+        (4, 4),
+        (0, 5),
+        (4, 5),
+        (0, 6),
+        (1, 6),
+        (2, 6),
+        (3, 6),
+        (4, 6),
+    ],
+    5,
+);
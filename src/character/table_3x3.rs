@@ -0,0 +1,84 @@
+//! Glyphs of the tiny 3x3 font, which only has digits and a few symbols.
+//! Two lines of two digits fit on the 8x8 LED-matrix, e.g. the hours above
+//! the minutes of a clock.
+
+use super::Character;
+
+pub const ZERO: Character = Character::new(
+    &[
+        (0, 2),
+        (1, 2),
+        (2, 2),
+        (0, 1),
+        (2, 1),
+        (0, 0),
+        (1, 0),
+        (2, 0),
+    ],
+    3,
+);
+pub const ONE: Character = Character::new(&[(0, 2), (1, 2), (1, 1), (0, 0), (1, 0), (2, 0)], 3);
+pub const TWO: Character = Character::new(&[(0, 2), (1, 2), (1, 1), (1, 0), (2, 0)], 3);
+pub const THREE: Character = Character::new(
+    &[
+        (0, 2),
+        (1, 2),
+        (2, 2),
+        (1, 1),
+        (2, 1),
+        (0, 0),
+        (1, 0),
+        (2, 0),
+    ],
+    3,
+);
+pub const FOUR: Character = Character::new(&[(0, 2), (2, 2), (0, 1), (1, 1), (2, 1), (2, 0)], 3);
+pub const FIVE: Character = Character::new(&[(1, 2), (2, 2), (1, 1), (0, 0), (1, 0)], 3);
+pub const SIX: Character =
+    Character::new(&[(0, 2), (0, 1), (1, 1), (2, 1), (0, 0), (1, 0), (2, 0)], 3);
+pub const SEVEN: Character = Character::new(&[(0, 2), (1, 2), (2, 2), (2, 1), (2, 0)], 3);
+pub const EIGHT: Character =
+    Character::new(&[(1, 2), (2, 2), (0, 1), (1, 1), (2, 1), (0, 0), (1, 0)], 3);
+pub const NINE: Character =
+    Character::new(&[(0, 2), (1, 2), (2, 2), (0, 1), (1, 1), (2, 1), (2, 0)], 3);
+pub const SPACE: Character = Character::new(&[], 2);
+pub const MINUS: Character = Character::new(&[(0, 1), (1, 1), (2, 1)], 3);
+pub const F_STOP: Character = Character::new(&[(0, 0)], 1);
+pub const COLON: Character = Character::new(&[(0, 2), (0, 0)], 1);
+/// Drawn in place of characters which aren't supported.
+pub const REPLACEMENT: Character = Character::new(
+    &[
+        (0, 2),
+        (1, 2),
+        (2, 2),
+        (0, 1),
+        (1, 1),
+        (2, 1),
+        (0, 0),
+        (1, 0),
+        (2, 0),
+    ],
+    3,
+);
+
+/// Look up the glyph of a `char`, or `None` if it isn't supported.
+pub const fn lookup(value: char) -> Option<Character> {
+    let character = match value {
+        '0' => ZERO,
+        '1' => ONE,
+        '2' => TWO,
+        '3' => THREE,
+        '4' => FOUR,
+        '5' => FIVE,
+        '6' => SIX,
+        '7' => SEVEN,
+        '8' => EIGHT,
+        '9' => NINE,
+        ' ' => SPACE,
+        '-' => MINUS,
+        '.' => F_STOP,
+        ':' => COLON,
+        _ => return None,
+    };
+    Some(character)
+}
@@ -0,0 +1,757 @@
+//! Glyphs of the compact 3x5 font, which only has capital letters.
+
+#![allow(non_upper_case_globals)]
+
+use super::Character;
+
+pub const ZERO: Character = Character::new(
+    &[
+        (0, 4),
+        (1, 4),
+        (2, 4),
+        (0, 3),
+        (2, 3),
+        (0, 2),
+        (2, 2),
+        (0, 1),
+        (2, 1),
+        (0, 0),
+        (1, 0),
+        (2, 0),
+    ],
+    3,
+);
+pub const ONE: Character = Character::new(
+    &[
+        (1, 4),
+        (0, 3),
+        (1, 3),
+        (1, 2),
+        (1, 1),
+        (0, 0),
+        (1, 0),
+        (2, 0),
+    ],
+    3,
+);
+pub const TWO: Character = Character::new(
+    &[
+        (0, 4),
+        (1, 4),
+        (2, 3),
+        (1, 2),
+        (0, 1),
+        (0, 0),
+        (1, 0),
+        (2, 0),
+    ],
+    3,
+);
+pub const THREE: Character =
+    Character::new(&[(0, 4), (1, 4), (2, 3), (1, 2), (2, 1), (0, 0), (1, 0)], 3);
+pub const FOUR: Character = Character::new(
+    &[
+        (0, 4),
+        (2, 4),
+        (0, 3),
+        (2, 3),
+        (0, 2),
+        (1, 2),
+        (2, 2),
+        (2, 1),
+        (2, 0),
+    ],
+    3,
+);
+pub const FIVE: Character = Character::new(
+    &[
+        (0, 4),
+        (1, 4),
+        (2, 4),
+        (0, 3),
+        (0, 2),
+        (1, 2),
+        (2, 1),
+        (0, 0),
+        (1, 0),
+    ],
+    3,
+);
+pub const SIX: Character = Character::new(
+    &[
+        (1, 4),
+        (2, 4),
+        (0, 3),
+        (0, 2),
+        (1, 2),
+        (2, 2),
+        (0, 1),
+        (2, 1),
+        (0, 0),
+        (1, 0),
+        (2, 0),
+    ],
+    3,
+);
+pub const SEVEN: Character =
+    Character::new(&[(0, 4), (1, 4), (2, 4), (2, 3), (1, 2), (1, 1), (1, 0)], 3);
+pub const EIGHT: Character = Character::new(
+    &[
+        (0, 4),
+        (1, 4),
+        (2, 4),
+        (0, 3),
+        (2, 3),
+        (0, 2),
+        (1, 2),
+        (2, 2),
+        (0, 1),
+        (2, 1),
+        (0, 0),
+        (1, 0),
+        (2, 0),
+    ],
+    3,
+);
+pub const NINE: Character = Character::new(
+    &[
+        (0, 4),
+        (1, 4),
+        (2, 4),
+        (0, 3),
+        (2, 3),
+        (0, 2),
+        (1, 2),
+        (2, 2),
+        (2, 1),
+        (0, 0),
+        (1, 0),
+    ],
+    3,
+);
+pub const A: Character = Character::new(
+    &[
+        (1, 4),
+        (0, 3),
+        (2, 3),
+        (0, 2),
+        (1, 2),
+        (2, 2),
+        (0, 1),
+        (2, 1),
+        (0, 0),
+        (2, 0),
+    ],
+    3,
+);
+pub const B: Character = Character::new(
+    &[
+        (0, 4),
+        (1, 4),
+        (0, 3),
+        (2, 3),
+        (0, 2),
+        (1, 2),
+        (0, 1),
+        (2, 1),
+        (0, 0),
+        (1, 0),
+    ],
+    3,
+);
+pub const C: Character =
+    Character::new(&[(1, 4), (2, 4), (0, 3), (0, 2), (0, 1), (1, 0), (2, 0)], 3);
+pub const D: Character = Character::new(
+    &[
+        (0, 4),
+        (1, 4),
+        (0, 3),
+        (2, 3),
+        (0, 2),
+        (2, 2),
+        (0, 1),
+        (2, 1),
+        (0, 0),
+        (1, 0),
+    ],
+    3,
+);
+pub const E: Character = Character::new(
+    &[
+        (0, 4),
+        (1, 4),
+        (2, 4),
+        (0, 3),
+        (0, 2),
+        (1, 2),
+        (0, 1),
+        (0, 0),
+        (1, 0),
+        (2, 0),
+    ],
+    3,
+);
+pub const F: Character = Character::new(
+    &[
+        (0, 4),
+        (1, 4),
+        (2, 4),
+        (0, 3),
+        (0, 2),
+        (1, 2),
+        (0, 1),
+        (0, 0),
+    ],
+    3,
+);
+pub const G: Character = Character::new(
+    &[
+        (1, 4),
+        (2, 4),
+        (0, 3),
+        (0, 2),
+        (2, 2),
+        (0, 1),
+        (2, 1),
+        (1, 0),
+        (2, 0),
+    ],
+    3,
+);
+pub const H: Character = Character::new(
+    &[
+        (0, 4),
+        (2, 4),
+        (0, 3),
+        (2, 3),
+        (0, 2),
+        (1, 2),
+        (2, 2),
+        (0, 1),
+        (2, 1),
+        (0, 0),
+        (2, 0),
+    ],
+    3,
+);
+pub const I: Character = Character::new(
+    &[
+        (0, 4),
+        (1, 4),
+        (2, 4),
+        (1, 3),
+        (1, 2),
+        (1, 1),
+        (0, 0),
+        (1, 0),
+        (2, 0),
+    ],
+    3,
+);
+pub const J: Character = Character::new(&[(2, 4), (2, 3), (2, 2), (0, 1), (2, 1), (1, 0)], 3);
+pub const K: Character = Character::new(
+    &[
+        (0, 4),
+        (2, 4),
+        (0, 3),
+        (2, 3),
+        (0, 2),
+        (1, 2),
+        (0, 1),
+        (2, 1),
+        (0, 0),
+        (2, 0),
+    ],
+    3,
+);
+pub const L: Character =
+    Character::new(&[(0, 4), (0, 3), (0, 2), (0, 1), (0, 0), (1, 0), (2, 0)], 3);
+pub const M: Character = Character::new(
+    &[
+        (0, 4),
+        (4, 4),
+        (0, 3),
+        (1, 3),
+        (3, 3),
+        (4, 3),
+        (0, 2),
+        (2, 2),
+        (4, 2),
+        (0, 1),
+        (4, 1),
+        (0, 0),
+        (4, 0),
+    ],
+    5,
+);
+pub const N: Character = Character::new(
+    &[
+        (0, 4),
+        (3, 4),
+        (0, 3),
+        (1, 3),
+        (3, 3),
+        (0, 2),
+        (2, 2),
+        (3, 2),
+        (0, 1),
+        (3, 1),
+        (0, 0),
+        (3, 0),
+    ],
+    4,
+);
+pub const O: Character = Character::new(
+    &[
+        (1, 4),
+        (0, 3),
+        (2, 3),
+        (0, 2),
+        (2, 2),
+        (0, 1),
+        (2, 1),
+        (1, 0),
+    ],
+    3,
+);
+pub const P: Character = Character::new(
+    &[
+        (0, 4),
+        (1, 4),
+        (0, 3),
+        (2, 3),
+        (0, 2),
+        (1, 2),
+        (0, 1),
+        (0, 0),
+    ],
+    3,
+);
+pub const Q: Character = Character::new(
+    &[
+        (1, 4),
+        (0, 3),
+        (2, 3),
+        (0, 2),
+        (2, 2),
+        (0, 1),
+        (1, 1),
+        (1, 0),
+        (2, 0),
+    ],
+    3,
+);
+pub const R: Character = Character::new(
+    &[
+        (0, 4),
+        (1, 4),
+        (0, 3),
+        (2, 3),
+        (0, 2),
+        (1, 2),
+        (0, 1),
+        (2, 1),
+        (0, 0),
+        (2, 0),
+    ],
+    3,
+);
+pub const S: Character =
+    Character::new(&[(1, 4), (2, 4), (0, 3), (1, 2), (2, 1), (0, 0), (1, 0)], 3);
+pub const T: Character =
+    Character::new(&[(0, 4), (1, 4), (2, 4), (1, 3), (1, 2), (1, 1), (1, 0)], 3);
+pub const U: Character = Character::new(
+    &[
+        (0, 4),
+        (2, 4),
+        (0, 3),
+        (2, 3),
+        (0, 2),
+        (2, 2),
+        (0, 1),
+        (2, 1),
+        (0, 0),
+        (1, 0),
+        (2, 0),
+    ],
+    3,
+);
+pub const V: Character = Character::new(
+    &[
+        (0, 4),
+        (2, 4),
+        (0, 3),
+        (2, 3),
+        (0, 2),
+        (2, 2),
+        (0, 1),
+        (2, 1),
+        (1, 0),
+    ],
+    3,
+);
+pub const W: Character = Character::new(
+    &[
+        (0, 4),
+        (4, 4),
+        (0, 3),
+        (4, 3),
+        (0, 2),
+        (2, 2),
+        (4, 2),
+        (0, 1),
+        (1, 1),
+        (3, 1),
+        (4, 1),
+        (0, 0),
+        (4, 0),
+    ],
+    5,
+);
+pub const X: Character = Character::new(
+    &[
+        (0, 4),
+        (2, 4),
+        (0, 3),
+        (2, 3),
+        (1, 2),
+        (0, 1),
+        (2, 1),
+        (0, 0),
+        (2, 0),
+    ],
+    3,
+);
+pub const Y: Character =
+    Character::new(&[(0, 4), (2, 4), (0, 3), (2, 3), (1, 2), (1, 1), (1, 0)], 3);
+pub const Z: Character = Character::new(
+    &[
+        (0, 4),
+        (1, 4),
+        (2, 4),
+        (2, 3),
+        (1, 2),
+        (0, 1),
+        (0, 0),
+        (1, 0),
+        (2, 0),
+    ],
+    3,
+);
+pub const A_UMLAUT: Character = Character::new(
+    &[
+        (0, 4),
+        (2, 4),
+        (1, 3),
+        (0, 2),
+        (2, 2),
+        (0, 1),
+        (1, 1),
+        (2, 1),
+        (0, 0),
+        (2, 0),
+    ],
+    3,
+);
+pub const O_UMLAUT: Character = Character::new(
+    &[
+        (0, 4),
+        (2, 4),
+        (1, 3),
+        (0, 2),
+        (2, 2),
+        (0, 1),
+        (2, 1),
+        (1, 0),
+    ],
+    3,
+);
+pub const U_UMLAUT: Character = Character::new(
+    &[
+        (0, 4),
+        (2, 4),
+        (0, 2),
+        (2, 2),
+        (0, 1),
+        (2, 1),
+        (0, 0),
+        (1, 0),
+        (2, 0),
+    ],
+    3,
+);
+pub const SPACE: Character = Character::new(&[], 2);
+pub const EXCLAM: Character = Character::new(&[(0, 4), (0, 3), (0, 2), (0, 0)], 1);
+pub const D_QUOTE: Character = Character::new(&[(0, 4), (2, 4), (0, 3), (2, 3)], 3);
+pub const HASH: Character = Character::new(
+    &[
+        (0, 4),
+        (2, 4),
+        (0, 3),
+        (1, 3),
+        (2, 3),
+        (0, 2),
+        (2, 2),
+        (0, 1),
+        (1, 1),
+        (2, 1),
+        (0, 0),
+        (2, 0),
+    ],
+    3,
+);
+pub const DOLLAR: Character = Character::new(
+    &[
+        (1, 4),
+        (2, 4),
+        (0, 3),
+        (1, 3),
+        (1, 2),
+        (1, 1),
+        (2, 1),
+        (0, 0),
+        (1, 0),
+    ],
+    3,
+);
+pub const PERCENT: Character =
+    Character::new(&[(0, 4), (2, 4), (2, 3), (1, 2), (0, 1), (0, 0), (2, 0)], 3);
+pub const AMPERSAND: Character = Character::new(
+    &[
+        (1, 4),
+        (0, 3),
+        (2, 3),
+        (1, 2),
+        (0, 1),
+        (2, 1),
+        (1, 0),
+        (2, 0),
+    ],
+    3,
+);
+pub const SIN_QUOTE: Character = Character::new(&[(0, 4), (0, 3)], 1);
+pub const L_PARENTH: Character = Character::new(&[(1, 4), (0, 3), (0, 2), (0, 1), (1, 0)], 2);
+pub const R_PARENTH: Character = Character::new(&[(0, 4), (1, 3), (1, 2), (1, 1), (0, 0)], 2);
+pub const ASTERISK: Character = Character::new(&[(0, 3), (2, 3), (1, 2), (0, 1), (2, 1)], 3);
+pub const PLUS: Character = Character::new(&[(1, 3), (0, 2), (1, 2), (2, 2), (1, 1)], 3);
+pub const COMMA: Character = Character::new(&[(1, 1), (0, 0)], 2);
+pub const MINUS: Character = Character::new(&[(0, 2), (1, 2), (2, 2)], 3);
+pub const F_STOP: Character = Character::new(&[(0, 0)], 1);
+pub const SLASH: Character = Character::new(&[(2, 4), (2, 3), (1, 2), (0, 1), (0, 0)], 3);
+pub const COLON: Character = Character::new(&[(0, 3), (0, 1)], 1);
+pub const S_COLON: Character = Character::new(&[(1, 3), (1, 1), (0, 0)], 2);
+pub const LESS: Character = Character::new(&[(2, 4), (1, 3), (0, 2), (1, 1), (2, 0)], 3);
+pub const EQUAL: Character = Character::new(&[(0, 3), (1, 3), (2, 3), (0, 1), (1, 1), (2, 1)], 3);
+pub const MORE: Character = Character::new(&[(0, 4), (1, 3), (2, 2), (1, 1), (0, 0)], 3);
+pub const QUESTION: Character = Character::new(&[(0, 4), (1, 4), (2, 3), (1, 2), (1, 0)], 3);
+pub const AT: Character = Character::new(
+    &[
+        (1, 4),
+        (0, 3),
+        (2, 3),
+        (0, 2),
+        (1, 2),
+        (2, 2),
+        (0, 1),
+        (1, 0),
+        (2, 0),
+    ],
+    3,
+);
+pub const L_BRACKET: Character =
+    Character::new(&[(0, 4), (1, 4), (0, 3), (0, 2), (0, 1), (0, 0), (1, 0)], 2);
+pub const B_SLASH: Character = Character::new(&[(0, 4), (0, 3), (1, 2), (2, 1), (2, 0)], 3);
+pub const R_BRACKET: Character =
+    Character::new(&[(0, 4), (1, 4), (1, 3), (1, 2), (1, 1), (0, 0), (1, 0)], 2);
+pub const CARET: Character = Character::new(&[(1, 4), (0, 3), (2, 3)], 3);
+pub const U_SCORE: Character = Character::new(&[(0, 0), (1, 0), (2, 0)], 3);
+pub const L_BRACE: Character = Character::new(
+    &[
+        (1, 4),
+        (2, 4),
+        (1, 3),
+        (0, 2),
+        (1, 2),
+        (1, 1),
+        (1, 0),
+        (2, 0),
+    ],
+    3,
+);
+pub const V_BAR: Character = Character::new(&[(0, 4), (0, 3), (0, 2), (0, 1), (0, 0)], 1);
+pub const R_BRACE: Character = Character::new(
+    &[
+        (0, 4),
+        (1, 4),
+        (1, 3),
+        (1, 2),
+        (2, 2),
+        (1, 1),
+        (0, 0),
+        (1, 0),
+    ],
+    3,
+);
+pub const TILDE: Character = Character::new(&[(1, 3), (3, 3), (0, 2), (2, 2)], 4);
+pub const DEGREE: Character = Character::new(
+    &[
+        (0, 4),
+        (1, 4),
+        (2, 4),
+        (0, 3),
+        (2, 3),
+        (0, 2),
+        (1, 2),
+        (2, 2),
+    ],
+    3,
+);
+pub const EURO: Character = Character::new(
+    &[
+        (1, 4),
+        (2, 4),
+        (0, 3),
+        (1, 3),
+        (2, 3),
+        (0, 2),
+        (0, 1),
+        (1, 1),
+        (2, 1),
+        (1, 0),
+        (2, 0),
+    ],
+    3,
+);
+pub const POUND: Character = Character::new(
+    &[
+        (1, 4),
+        (2, 4),
+        (1, 3),
+        (0, 2),
+        (1, 2),
+        (2, 2),
+        (1, 1),
+        (0, 0),
+        (1, 0),
+        (2, 0),
+    ],
+    3,
+);
+pub const HEART: Character = Character::new(
+    &[
+        (0, 4),
+        (2, 4),
+        (0, 3),
+        (1, 3),
+        (2, 3),
+        (0, 2),
+        (1, 2),
+        (2, 2),
+        (1, 1),
+    ],
+    3,
+);
+/// Drawn in place of characters which aren't supported.
+pub const REPLACEMENT: Character = Character::new(
+    &[
+        (0, 4),
+        (1, 4),
+        (2, 4),
+        (0, 3),
+        (1, 3),
+        (2, 3),
+        (0, 2),
+        (1, 2),
+        (2, 2),
+        (0, 1),
+        (1, 1),
+        (2, 1),
+        (0, 0),
+        (1, 0),
+        (2, 0),
+    ],
+    3,
+);
+
+/// Look up the glyph of a `char`, or `None` if it isn't supported.
+pub const fn lookup(value: char) -> Option<Character> {
+    let character = match value {
+        '0' => ZERO,
+        '1' => ONE,
+        '2' => TWO,
+        '3' => THREE,
+        '4' => FOUR,
+        '5' => FIVE,
+        '6' => SIX,
+        '7' => SEVEN,
+        '8' => EIGHT,
+        '9' => NINE,
+        'A' | 'a' => A,
+        'B' | 'b' => B,
+        'C' | 'c' => C,
+        'D' | 'd' => D,
+        'E' | 'e' => E,
+        'F' | 'f' => F,
+        'G' | 'g' => G,
+        'H' | 'h' => H,
+        'I' | 'i' => I,
+        'J' | 'j' => J,
+        'K' | 'k' => K,
+        'L' | 'l' => L,
+        'M' | 'm' => M,
+        'N' | 'n' => N,
+        'O' | 'o' => O,
+        'P' | 'p' => P,
+        'Q' | 'q' => Q,
+        'R' | 'r' => R,
+        'S' | 's' => S,
+        'T' | 't' => T,
+        'U' | 'u' => U,
+        'V' | 'v' => V,
+        'W' | 'w' => W,
+        'X' | 'x' => X,
+        'Y' | 'y' => Y,
+        'Z' | 'z' => Z,
+        'Ä' | 'ä' => A_UMLAUT,
+        'Ö' | 'ö' => O_UMLAUT,
+        'Ü' | 'ü' => U_UMLAUT,
+        ' ' => SPACE,
+        '!' => EXCLAM,
+        '"' => D_QUOTE,
+        '#' => HASH,
+        '$' => DOLLAR,
+        '%' => PERCENT,
+        '&' => AMPERSAND,
+        '\'' => SIN_QUOTE,
+        '(' => L_PARENTH,
+        ')' => R_PARENTH,
+        '*' => ASTERISK,
+        '+' => PLUS,
+        ',' => COMMA,
+        '-' => MINUS,
+        '.' => F_STOP,
+        '/' => SLASH,
+        ':' => COLON,
+        ';' => S_COLON,
+        '<' => LESS,
+        '=' => EQUAL,
+        '>' => MORE,
+        '?' => QUESTION,
+        '@' => AT,
+        '[' => L_BRACKET,
+        '\\' => B_SLASH,
+        ']' => R_BRACKET,
+        '^' => CARET,
+        '_' => U_SCORE,
+        '{' => L_BRACE,
+        '|' => V_BAR,
+        '}' => R_BRACE,
+        '~' => TILDE,
+        '°' => DEGREE,
+        '€' => EURO,
+        '£' => POUND,
+        '♥' => HEART,
+        _ => return None,
+    };
+    Some(character)
+}
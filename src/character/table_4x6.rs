@@ -0,0 +1,1528 @@
+//! Glyphs of the 4x6 font.
+
+#![allow(non_upper_case_globals)]
+
+use super::Character;
+
+pub const ZERO: Character = Character::new(
+    &[
+        (1, 5),
+        (2, 5),
+        (0, 4),
+        (3, 4),
+        (0, 3),
+        (2, 3),
+        (3, 3),
+        (0, 2),
+        (1, 2),
+        (3, 2),
+        (0, 1),
+        (3, 1),
+        (1, 0),
+        (2, 0),
+    ],
+    4,
+);
+pub const ONE: Character = Character::new(
+    &[
+        (1, 5),
+        (0, 4),
+        (1, 4),
+        (1, 3),
+        (1, 2),
+        (1, 1),
+        (0, 0),
+        (1, 0),
+        (2, 0),
+    ],
+    3,
+);
+pub const TWO: Character = Character::new(
+    &[
+        (1, 5),
+        (2, 5),
+        (0, 4),
+        (3, 4),
+        (2, 3),
+        (1, 2),
+        (0, 1),
+        (0, 0),
+        (1, 0),
+        (2, 0),
+        (3, 0),
+    ],
+    4,
+);
+pub const THREE: Character = Character::new(
+    &[
+        (0, 5),
+        (1, 5),
+        (2, 5),
+        (3, 4),
+        (1, 3),
+        (2, 3),
+        (3, 2),
+        (3, 1),
+        (0, 0),
+        (1, 0),
+        (2, 0),
+    ],
+    4,
+);
+pub const FOUR: Character = Character::new(
+    &[
+        (0, 5),
+        (3, 5),
+        (0, 4),
+        (3, 4),
+        (0, 3),
+        (1, 3),
+        (2, 3),
+        (3, 3),
+        (3, 2),
+        (3, 1),
+        (3, 0),
+    ],
+    4,
+);
+pub const FIVE: Character = Character::new(
+    &[
+        (0, 5),
+        (1, 5),
+        (2, 5),
+        (3, 5),
+        (0, 4),
+        (0, 3),
+        (1, 3),
+        (2, 3),
+        (3, 2),
+        (3, 1),
+        (0, 0),
+        (1, 0),
+        (2, 0),
+    ],
+    4,
+);
+pub const SIX: Character = Character::new(
+    &[
+        (1, 5),
+        (2, 5),
+        (0, 4),
+        (0, 3),
+        (1, 3),
+        (2, 3),
+        (0, 2),
+        (3, 2),
+        (0, 1),
+        (3, 1),
+        (1, 0),
+        (2, 0),
+    ],
+    4,
+);
+pub const SEVEN: Character = Character::new(
+    &[
+        (0, 5),
+        (1, 5),
+        (2, 5),
+        (3, 5),
+        (3, 4),
+        (2, 3),
+        (1, 2),
+        (1, 1),
+        (1, 0),
+    ],
+    4,
+);
+pub const EIGHT: Character = Character::new(
+    &[
+        (1, 5),
+        (2, 5),
+        (0, 4),
+        (3, 4),
+        (1, 3),
+        (2, 3),
+        (0, 2),
+        (3, 2),
+        (0, 1),
+        (3, 1),
+        (1, 0),
+        (2, 0),
+    ],
+    4,
+);
+pub const NINE: Character = Character::new(
+    &[
+        (1, 5),
+        (2, 5),
+        (0, 4),
+        (3, 4),
+        (0, 3),
+        (3, 3),
+        (1, 2),
+        (2, 2),
+        (3, 2),
+        (3, 1),
+        (1, 0),
+        (2, 0),
+    ],
+    4,
+);
+pub const A: Character = Character::new(
+    &[
+        (1, 5),
+        (2, 5),
+        (0, 4),
+        (3, 4),
+        (0, 3),
+        (3, 3),
+        (0, 2),
+        (1, 2),
+        (2, 2),
+        (3, 2),
+        (0, 1),
+        (3, 1),
+        (0, 0),
+        (3, 0),
+    ],
+    4,
+);
+pub const B: Character = Character::new(
+    &[
+        (0, 5),
+        (1, 5),
+        (2, 5),
+        (0, 4),
+        (3, 4),
+        (0, 3),
+        (1, 3),
+        (2, 3),
+        (0, 2),
+        (3, 2),
+        (0, 1),
+        (3, 1),
+        (0, 0),
+        (1, 0),
+        (2, 0),
+    ],
+    4,
+);
+pub const C: Character = Character::new(
+    &[
+        (1, 5),
+        (2, 5),
+        (0, 4),
+        (3, 4),
+        (0, 3),
+        (0, 2),
+        (0, 1),
+        (3, 1),
+        (1, 0),
+        (2, 0),
+    ],
+    4,
+);
+pub const D: Character = Character::new(
+    &[
+        (0, 5),
+        (1, 5),
+        (2, 5),
+        (0, 4),
+        (3, 4),
+        (0, 3),
+        (3, 3),
+        (0, 2),
+        (3, 2),
+        (0, 1),
+        (3, 1),
+        (0, 0),
+        (1, 0),
+        (2, 0),
+    ],
+    4,
+);
+pub const E: Character = Character::new(
+    &[
+        (0, 5),
+        (1, 5),
+        (2, 5),
+        (3, 5),
+        (0, 4),
+        (0, 3),
+        (1, 3),
+        (2, 3),
+        (0, 2),
+        (0, 1),
+        (0, 0),
+        (1, 0),
+        (2, 0),
+        (3, 0),
+    ],
+    4,
+);
+pub const F: Character = Character::new(
+    &[
+        (0, 5),
+        (1, 5),
+        (2, 5),
+        (3, 5),
+        (0, 4),
+        (0, 3),
+        (1, 3),
+        (2, 3),
+        (0, 2),
+        (0, 1),
+        (0, 0),
+    ],
+    4,
+);
+pub const G: Character = Character::new(
+    &[
+        (1, 5),
+        (2, 5),
+        (0, 4),
+        (0, 3),
+        (0, 2),
+        (2, 2),
+        (3, 2),
+        (0, 1),
+        (3, 1),
+        (1, 0),
+        (2, 0),
+        (3, 0),
+    ],
+    4,
+);
+pub const H: Character = Character::new(
+    &[
+        (0, 5),
+        (3, 5),
+        (0, 4),
+        (3, 4),
+        (0, 3),
+        (1, 3),
+        (2, 3),
+        (3, 3),
+        (0, 2),
+        (3, 2),
+        (0, 1),
+        (3, 1),
+        (0, 0),
+        (3, 0),
+    ],
+    4,
+);
+pub const I: Character = Character::new(
+    &[
+        (0, 5),
+        (1, 5),
+        (2, 5),
+        (1, 4),
+        (1, 3),
+        (1, 2),
+        (1, 1),
+        (0, 0),
+        (1, 0),
+        (2, 0),
+    ],
+    3,
+);
+pub const J: Character = Character::new(
+    &[
+        (3, 5),
+        (3, 4),
+        (3, 3),
+        (3, 2),
+        (0, 1),
+        (3, 1),
+        (1, 0),
+        (2, 0),
+    ],
+    4,
+);
+pub const K: Character = Character::new(
+    &[
+        (0, 5),
+        (3, 5),
+        (0, 4),
+        (2, 4),
+        (0, 3),
+        (1, 3),
+        (0, 2),
+        (2, 2),
+        (0, 1),
+        (3, 1),
+        (0, 0),
+        (3, 0),
+    ],
+    4,
+);
+pub const L: Character = Character::new(
+    &[
+        (0, 5),
+        (0, 4),
+        (0, 3),
+        (0, 2),
+        (0, 1),
+        (0, 0),
+        (1, 0),
+        (2, 0),
+        (3, 0),
+    ],
+    4,
+);
+pub const M: Character = Character::new(
+    &[
+        (0, 5),
+        (4, 5),
+        (0, 4),
+        (1, 4),
+        (3, 4),
+        (4, 4),
+        (0, 3),
+        (2, 3),
+        (4, 3),
+        (0, 2),
+        (4, 2),
+        (0, 1),
+        (4, 1),
+        (0, 0),
+        (4, 0),
+    ],
+    5,
+);
+pub const N: Character = Character::new(
+    &[
+        (0, 5),
+        (3, 5),
+        (0, 4),
+        (1, 4),
+        (3, 4),
+        (0, 3),
+        (2, 3),
+        (3, 3),
+        (0, 2),
+        (3, 2),
+        (0, 1),
+        (3, 1),
+        (0, 0),
+        (3, 0),
+    ],
+    4,
+);
+pub const O: Character = Character::new(
+    &[
+        (1, 5),
+        (2, 5),
+        (0, 4),
+        (3, 4),
+        (0, 3),
+        (3, 3),
+        (0, 2),
+        (3, 2),
+        (0, 1),
+        (3, 1),
+        (1, 0),
+        (2, 0),
+    ],
+    4,
+);
+pub const P: Character = Character::new(
+    &[
+        (0, 5),
+        (1, 5),
+        (2, 5),
+        (0, 4),
+        (3, 4),
+        (0, 3),
+        (3, 3),
+        (0, 2),
+        (1, 2),
+        (2, 2),
+        (0, 1),
+        (0, 0),
+    ],
+    4,
+);
+pub const Q: Character = Character::new(
+    &[
+        (1, 5),
+        (2, 5),
+        (0, 4),
+        (3, 4),
+        (0, 3),
+        (3, 3),
+        (0, 2),
+        (3, 2),
+        (0, 1),
+        (2, 1),
+        (1, 0),
+        (3, 0),
+    ],
+    4,
+);
+pub const R: Character = Character::new(
+    &[
+        (0, 5),
+        (1, 5),
+        (2, 5),
+        (0, 4),
+        (3, 4),
+        (0, 3),
+        (3, 3),
+        (0, 2),
+        (1, 2),
+        (2, 2),
+        (0, 1),
+        (2, 1),
+        (0, 0),
+        (3, 0),
+    ],
+    4,
+);
+pub const S: Character = Character::new(
+    &[
+        (1, 5),
+        (2, 5),
+        (3, 5),
+        (0, 4),
+        (1, 3),
+        (2, 3),
+        (3, 2),
+        (3, 1),
+        (0, 0),
+        (1, 0),
+        (2, 0),
+    ],
+    4,
+);
+pub const T: Character = Character::new(
+    &[
+        (0, 5),
+        (1, 5),
+        (2, 5),
+        (1, 4),
+        (1, 3),
+        (1, 2),
+        (1, 1),
+        (1, 0),
+    ],
+    3,
+);
+pub const U: Character = Character::new(
+    &[
+        (0, 5),
+        (3, 5),
+        (0, 4),
+        (3, 4),
+        (0, 3),
+        (3, 3),
+        (0, 2),
+        (3, 2),
+        (0, 1),
+        (3, 1),
+        (1, 0),
+        (2, 0),
+    ],
+    4,
+);
+pub const V: Character = Character::new(
+    &[
+        (0, 5),
+        (2, 5),
+        (0, 4),
+        (2, 4),
+        (0, 3),
+        (2, 3),
+        (0, 2),
+        (2, 2),
+        (0, 1),
+        (2, 1),
+        (1, 0),
+    ],
+    3,
+);
+pub const W: Character = Character::new(
+    &[
+        (0, 5),
+        (4, 5),
+        (0, 4),
+        (4, 4),
+        (0, 3),
+        (4, 3),
+        (0, 2),
+        (2, 2),
+        (4, 2),
+        (0, 1),
+        (1, 1),
+        (3, 1),
+        (4, 1),
+        (0, 0),
+        (4, 0),
+    ],
+    5,
+);
+pub const X: Character = Character::new(
+    &[
+        (0, 5),
+        (3, 5),
+        (0, 4),
+        (3, 4),
+        (1, 3),
+        (2, 3),
+        (1, 2),
+        (2, 2),
+        (0, 1),
+        (3, 1),
+        (0, 0),
+        (3, 0),
+    ],
+    4,
+);
+pub const Y: Character = Character::new(
+    &[
+        (0, 5),
+        (2, 5),
+        (0, 4),
+        (2, 4),
+        (1, 3),
+        (1, 2),
+        (1, 1),
+        (1, 0),
+    ],
+    3,
+);
+pub const Z: Character = Character::new(
+    &[
+        (0, 5),
+        (1, 5),
+        (2, 5),
+        (3, 5),
+        (3, 4),
+        (2, 3),
+        (1, 2),
+        (0, 1),
+        (0, 0),
+        (1, 0),
+        (2, 0),
+        (3, 0),
+    ],
+    4,
+);
+pub const a: Character = Character::new(
+    &[
+        (1, 3),
+        (2, 3),
+        (3, 3),
+        (0, 2),
+        (3, 2),
+        (0, 1),
+        (3, 1),
+        (1, 0),
+        (2, 0),
+        (3, 0),
+    ],
+    4,
+);
+pub const b: Character = Character::new(
+    &[
+        (0, 5),
+        (0, 4),
+        (0, 3),
+        (1, 3),
+        (2, 3),
+        (0, 2),
+        (3, 2),
+        (0, 1),
+        (3, 1),
+        (0, 0),
+        (1, 0),
+        (2, 0),
+    ],
+    4,
+);
+pub const c: Character = Character::new(
+    &[
+        (1, 3),
+        (2, 3),
+        (3, 3),
+        (0, 2),
+        (0, 1),
+        (1, 0),
+        (2, 0),
+        (3, 0),
+    ],
+    4,
+);
+pub const d: Character = Character::new(
+    &[
+        (3, 5),
+        (3, 4),
+        (1, 3),
+        (2, 3),
+        (3, 3),
+        (0, 2),
+        (3, 2),
+        (0, 1),
+        (3, 1),
+        (1, 0),
+        (2, 0),
+        (3, 0),
+    ],
+    4,
+);
+pub const e: Character = Character::new(
+    &[
+        (1, 3),
+        (2, 3),
+        (0, 2),
+        (1, 2),
+        (2, 2),
+        (3, 2),
+        (0, 1),
+        (1, 0),
+        (2, 0),
+        (3, 0),
+    ],
+    4,
+);
+pub const f: Character = Character::new(
+    &[
+        (2, 5),
+        (1, 4),
+        (0, 3),
+        (1, 3),
+        (2, 3),
+        (1, 2),
+        (1, 1),
+        (1, 0),
+    ],
+    3,
+);
+pub const g: Character = Character::new(
+    &[
+        (1, 4),
+        (2, 4),
+        (3, 4),
+        (0, 3),
+        (3, 3),
+        (1, 2),
+        (2, 2),
+        (3, 2),
+        (3, 1),
+        (1, 0),
+        (2, 0),
+    ],
+    4,
+);
+pub const h: Character = Character::new(
+    &[
+        (0, 5),
+        (0, 4),
+        (0, 3),
+        (1, 3),
+        (2, 3),
+        (0, 2),
+        (3, 2),
+        (0, 1),
+        (3, 1),
+        (0, 0),
+        (3, 0),
+    ],
+    4,
+);
+pub const i: Character = Character::new(&[(0, 5), (0, 3), (0, 2), (0, 1), (0, 0)], 1);
+pub const j: Character = Character::new(&[(2, 5), (2, 3), (2, 2), (0, 1), (2, 1), (1, 0)], 3);
+pub const k: Character = Character::new(
+    &[
+        (0, 5),
+        (0, 4),
+        (0, 3),
+        (2, 3),
+        (0, 2),
+        (1, 2),
+        (0, 1),
+        (2, 1),
+        (0, 0),
+        (3, 0),
+    ],
+    4,
+);
+pub const l: Character = Character::new(&[(0, 5), (0, 4), (0, 3), (0, 2), (0, 1), (1, 0)], 2);
+pub const m: Character = Character::new(
+    &[
+        (0, 3),
+        (1, 3),
+        (3, 3),
+        (0, 2),
+        (2, 2),
+        (4, 2),
+        (0, 1),
+        (2, 1),
+        (4, 1),
+        (0, 0),
+        (2, 0),
+        (4, 0),
+    ],
+    5,
+);
+pub const n: Character = Character::new(
+    &[
+        (0, 3),
+        (1, 3),
+        (2, 3),
+        (0, 2),
+        (3, 2),
+        (0, 1),
+        (3, 1),
+        (0, 0),
+        (3, 0),
+    ],
+    4,
+);
+pub const o: Character = Character::new(
+    &[
+        (1, 3),
+        (2, 3),
+        (0, 2),
+        (3, 2),
+        (0, 1),
+        (3, 1),
+        (1, 0),
+        (2, 0),
+    ],
+    4,
+);
+pub const p: Character = Character::new(
+    &[
+        (0, 4),
+        (1, 4),
+        (2, 4),
+        (0, 3),
+        (3, 3),
+        (0, 2),
+        (1, 2),
+        (2, 2),
+        (0, 1),
+        (0, 0),
+    ],
+    4,
+);
+pub const q: Character = Character::new(
+    &[
+        (1, 4),
+        (2, 4),
+        (3, 4),
+        (0, 3),
+        (3, 3),
+        (1, 2),
+        (2, 2),
+        (3, 2),
+        (3, 1),
+        (3, 0),
+    ],
+    4,
+);
+pub const r: Character = Character::new(&[(0, 3), (2, 3), (0, 2), (1, 2), (0, 1), (0, 0)], 3);
+pub const s: Character = Character::new(
+    &[
+        (1, 3),
+        (2, 3),
+        (3, 3),
+        (0, 2),
+        (1, 2),
+        (2, 1),
+        (3, 1),
+        (0, 0),
+        (1, 0),
+        (2, 0),
+    ],
+    4,
+);
+pub const t: Character = Character::new(
+    &[
+        (1, 5),
+        (1, 4),
+        (0, 3),
+        (1, 3),
+        (2, 3),
+        (1, 2),
+        (1, 1),
+        (2, 0),
+    ],
+    3,
+);
+pub const u: Character = Character::new(
+    &[
+        (0, 3),
+        (3, 3),
+        (0, 2),
+        (3, 2),
+        (0, 1),
+        (3, 1),
+        (1, 0),
+        (2, 0),
+        (3, 0),
+    ],
+    4,
+);
+pub const v: Character =
+    Character::new(&[(0, 3), (2, 3), (0, 2), (2, 2), (0, 1), (2, 1), (1, 0)], 3);
+pub const w: Character = Character::new(
+    &[
+        (0, 3),
+        (4, 3),
+        (0, 2),
+        (2, 2),
+        (4, 2),
+        (0, 1),
+        (2, 1),
+        (4, 1),
+        (1, 0),
+        (3, 0),
+    ],
+    5,
+);
+pub const x: Character = Character::new(
+    &[
+        (0, 3),
+        (3, 3),
+        (1, 2),
+        (2, 2),
+        (1, 1),
+        (2, 1),
+        (0, 0),
+        (3, 0),
+    ],
+    4,
+);
+pub const y: Character = Character::new(
+    &[
+        (0, 4),
+        (3, 4),
+        (0, 3),
+        (3, 3),
+        (1, 2),
+        (2, 2),
+        (3, 2),
+        (3, 1),
+        (1, 0),
+        (2, 0),
+    ],
+    4,
+);
+pub const z: Character = Character::new(
+    &[
+        (0, 3),
+        (1, 3),
+        (2, 3),
+        (3, 3),
+        (2, 2),
+        (1, 1),
+        (0, 0),
+        (1, 0),
+        (2, 0),
+        (3, 0),
+    ],
+    4,
+);
+pub const A_UMLAUT: Character = Character::new(
+    &[
+        (0, 5),
+        (3, 5),
+        (1, 4),
+        (2, 4),
+        (0, 3),
+        (3, 3),
+        (0, 2),
+        (1, 2),
+        (2, 2),
+        (3, 2),
+        (0, 1),
+        (3, 1),
+        (0, 0),
+        (3, 0),
+    ],
+    4,
+);
+pub const O_UMLAUT: Character = Character::new(
+    &[
+        (0, 5),
+        (3, 5),
+        (1, 4),
+        (2, 4),
+        (0, 3),
+        (3, 3),
+        (0, 2),
+        (3, 2),
+        (0, 1),
+        (3, 1),
+        (1, 0),
+        (2, 0),
+    ],
+    4,
+);
+pub const U_UMLAUT: Character = Character::new(
+    &[
+        (0, 5),
+        (3, 5),
+        (0, 3),
+        (3, 3),
+        (0, 2),
+        (3, 2),
+        (0, 1),
+        (3, 1),
+        (1, 0),
+        (2, 0),
+    ],
+    4,
+);
+pub const a_umlaut: Character = Character::new(
+    &[
+        (0, 5),
+        (3, 5),
+        (1, 3),
+        (2, 3),
+        (3, 3),
+        (0, 2),
+        (3, 2),
+        (0, 1),
+        (3, 1),
+        (1, 0),
+        (2, 0),
+        (3, 0),
+    ],
+    4,
+);
+pub const o_umlaut: Character = Character::new(
+    &[
+        (0, 5),
+        (3, 5),
+        (1, 3),
+        (2, 3),
+        (0, 2),
+        (3, 2),
+        (0, 1),
+        (3, 1),
+        (1, 0),
+        (2, 0),
+    ],
+    4,
+);
+pub const u_umlaut: Character = Character::new(
+    &[
+        (0, 5),
+        (3, 5),
+        (0, 3),
+        (3, 3),
+        (0, 2),
+        (3, 2),
+        (0, 1),
+        (3, 1),
+        (1, 0),
+        (2, 0),
+        (3, 0),
+    ],
+    4,
+);
+pub const a_grave: Character = Character::new(
+    &[
+        (1, 5),
+        (2, 4),
+        (1, 3),
+        (2, 3),
+        (3, 3),
+        (0, 2),
+        (3, 2),
+        (0, 1),
+        (3, 1),
+        (1, 0),
+        (2, 0),
+        (3, 0),
+    ],
+    4,
+);
+pub const e_acute: Character = Character::new(
+    &[
+        (2, 5),
+        (1, 4),
+        (1, 3),
+        (2, 3),
+        (0, 2),
+        (1, 2),
+        (2, 2),
+        (3, 2),
+        (0, 1),
+        (1, 0),
+        (2, 0),
+        (3, 0),
+    ],
+    4,
+);
+pub const e_grave: Character = Character::new(
+    &[
+        (1, 5),
+        (2, 4),
+        (1, 3),
+        (2, 3),
+        (0, 2),
+        (1, 2),
+        (2, 2),
+        (3, 2),
+        (0, 1),
+        (1, 0),
+        (2, 0),
+        (3, 0),
+    ],
+    4,
+);
+pub const c_cedil: Character = Character::new(
+    &[
+        (1, 4),
+        (2, 4),
+        (3, 4),
+        (0, 3),
+        (0, 2),
+        (1, 1),
+        (2, 1),
+        (3, 1),
+        (2, 0),
+    ],
+    4,
+);
+pub const sharp_s: Character = Character::new(
+    &[
+        (1, 5),
+        (2, 5),
+        (0, 4),
+        (3, 4),
+        (0, 3),
+        (2, 3),
+        (0, 2),
+        (3, 2),
+        (0, 1),
+        (3, 1),
+        (0, 0),
+        (2, 0),
+    ],
+    4,
+);
+pub const SPACE: Character = Character::new(&[], 2);
+pub const EXCLAM: Character = Character::new(&[(0, 5), (0, 4), (0, 3), (0, 2), (0, 0)], 1);
+pub const D_QUOTE: Character = Character::new(&[(0, 5), (2, 5), (0, 4), (2, 4)], 3);
+pub const HASH: Character = Character::new(
+    &[
+        (1, 5),
+        (3, 5),
+        (0, 4),
+        (1, 4),
+        (2, 4),
+        (3, 4),
+        (4, 4),
+        (1, 3),
+        (3, 3),
+        (0, 2),
+        (1, 2),
+        (2, 2),
+        (3, 2),
+        (4, 2),
+        (1, 1),
+        (3, 1),
+    ],
+    5,
+);
+pub const DOLLAR: Character = Character::new(
+    &[
+        (2, 5),
+        (1, 4),
+        (2, 4),
+        (3, 4),
+        (0, 3),
+        (2, 3),
+        (1, 2),
+        (2, 2),
+        (2, 1),
+        (3, 1),
+        (0, 0),
+        (1, 0),
+        (2, 0),
+    ],
+    4,
+);
+pub const PERCENT: Character = Character::new(
+    &[
+        (0, 5),
+        (1, 5),
+        (3, 5),
+        (0, 4),
+        (1, 4),
+        (3, 4),
+        (2, 3),
+        (1, 2),
+        (0, 1),
+        (2, 1),
+        (3, 1),
+        (0, 0),
+        (2, 0),
+        (3, 0),
+    ],
+    4,
+);
+pub const AMPERSAND: Character = Character::new(
+    &[
+        (1, 5),
+        (0, 4),
+        (2, 4),
+        (1, 3),
+        (0, 2),
+        (2, 2),
+        (0, 1),
+        (2, 1),
+        (1, 0),
+        (3, 0),
+    ],
+    4,
+);
+pub const SIN_QUOTE: Character = Character::new(&[(0, 5), (0, 4)], 1);
+pub const L_PARENTH: Character =
+    Character::new(&[(1, 5), (0, 4), (0, 3), (0, 2), (0, 1), (1, 0)], 2);
+pub const R_PARENTH: Character =
+    Character::new(&[(0, 5), (1, 4), (1, 3), (1, 2), (1, 1), (0, 0)], 2);
+pub const ASTERISK: Character = Character::new(
+    &[
+        (0, 4),
+        (2, 4),
+        (1, 3),
+        (0, 2),
+        (1, 2),
+        (2, 2),
+        (1, 1),
+        (0, 0),
+        (2, 0),
+    ],
+    3,
+);
+pub const PLUS: Character = Character::new(&[(1, 3), (0, 2), (1, 2), (2, 2), (1, 1)], 3);
+pub const COMMA: Character = Character::new(&[(1, 1), (0, 0)], 2);
+pub const MINUS: Character = Character::new(&[(0, 2), (1, 2), (2, 2)], 3);
+pub const F_STOP: Character = Character::new(&[(0, 0)], 1);
+pub const SLASH: Character = Character::new(&[(3, 5), (3, 4), (2, 3), (1, 2), (0, 1), (0, 0)], 4);
+pub const COLON: Character = Character::new(&[(0, 3), (0, 1)], 1);
+pub const S_COLON: Character = Character::new(&[(1, 3), (1, 1), (0, 0)], 2);
+pub const LESS: Character = Character::new(&[(2, 5), (1, 4), (0, 3), (1, 2), (2, 1)], 3);
+pub const EQUAL: Character = Character::new(&[(0, 3), (1, 3), (2, 3), (0, 1), (1, 1), (2, 1)], 3);
+pub const MORE: Character = Character::new(&[(0, 5), (1, 4), (2, 3), (1, 2), (0, 1)], 3);
+pub const QUESTION: Character =
+    Character::new(&[(1, 5), (2, 5), (0, 4), (3, 4), (2, 3), (1, 2), (1, 0)], 4);
+pub const AT: Character = Character::new(
+    &[
+        (1, 5),
+        (2, 5),
+        (0, 4),
+        (3, 4),
+        (0, 3),
+        (2, 3),
+        (3, 3),
+        (0, 2),
+        (2, 2),
+        (3, 2),
+        (0, 1),
+        (1, 0),
+        (2, 0),
+        (3, 0),
+    ],
+    4,
+);
+pub const L_BRACKET: Character = Character::new(
+    &[
+        (0, 5),
+        (1, 5),
+        (0, 4),
+        (0, 3),
+        (0, 2),
+        (0, 1),
+        (0, 0),
+        (1, 0),
+    ],
+    2,
+);
+pub const B_SLASH: Character = Character::new(&[(0, 5), (0, 4), (1, 3), (2, 2), (3, 1), (3, 0)], 4);
+pub const R_BRACKET: Character = Character::new(
+    &[
+        (0, 5),
+        (1, 5),
+        (1, 4),
+        (1, 3),
+        (1, 2),
+        (1, 1),
+        (0, 0),
+        (1, 0),
+    ],
+    2,
+);
+pub const CARET: Character = Character::new(&[(1, 5), (0, 4), (2, 4)], 3);
+pub const U_SCORE: Character = Character::new(&[(0, 0), (1, 0), (2, 0), (3, 0)], 4);
+pub const L_BRACE: Character =
+    Character::new(&[(2, 5), (1, 4), (0, 3), (1, 3), (1, 2), (1, 1), (2, 0)], 3);
+pub const V_BAR: Character = Character::new(&[(0, 5), (0, 4), (0, 3), (0, 2), (0, 1), (0, 0)], 1);
+pub const R_BRACE: Character =
+    Character::new(&[(0, 5), (1, 4), (1, 3), (2, 3), (1, 2), (1, 1), (0, 0)], 3);
+pub const TILDE: Character = Character::new(&[(1, 3), (3, 3), (0, 2), (2, 2)], 4);
+pub const DEGREE: Character = Character::new(&[(1, 5), (0, 4), (2, 4), (1, 3)], 3);
+pub const EURO: Character = Character::new(
+    &[
+        (2, 5),
+        (3, 5),
+        (1, 4),
+        (0, 3),
+        (1, 3),
+        (2, 3),
+        (1, 2),
+        (0, 1),
+        (1, 1),
+        (2, 1),
+        (2, 0),
+        (3, 0),
+    ],
+    4,
+);
+pub const POUND: Character = Character::new(
+    &[
+        (1, 5),
+        (2, 5),
+        (0, 4),
+        (3, 4),
+        (1, 3),
+        (0, 2),
+        (1, 2),
+        (2, 2),
+        (1, 1),
+        (0, 0),
+        (1, 0),
+        (2, 0),
+        (3, 0),
+    ],
+    4,
+);
+pub const HEART: Character = Character::new(
+    &[
+        (1, 5),
+        (3, 5),
+        (0, 4),
+        (1, 4),
+        (2, 4),
+        (3, 4),
+        (4, 4),
+        (0, 3),
+        (1, 3),
+        (2, 3),
+        (3, 3),
+        (4, 3),
+        (1, 2),
+        (2, 2),
+        (3, 2),
+        (2, 1),
+    ],
+    5,
+);
+pub const L_ARROW: Character = Character::new(
+    &[
+        (2, 4),
+        (1, 3),
+        (0, 2),
+        (1, 2),
+        (2, 2),
+        (3, 2),
+        (4, 2),
+        (1, 1),
+        (2, 0),
+    ],
+    5,
+);
+pub const R_ARROW: Character = Character::new(
+    &[
+        (2, 4),
+        (3, 3),
+        (0, 2),
+        (1, 2),
+        (2, 2),
+        (3, 2),
+        (4, 2),
+        (3, 1),
+        (2, 0),
+    ],
+    5,
+);
+pub const U_ARROW: Character = Character::new(
+    &[
+        (2, 5),
+        (1, 4),
+        (2, 4),
+        (3, 4),
+        (0, 3),
+        (2, 3),
+        (4, 3),
+        (2, 2),
+        (2, 1),
+        (2, 0),
+    ],
+    5,
+);
+pub const D_ARROW: Character = Character::new(
+    &[
+        (2, 5),
+        (2, 4),
+        (2, 3),
+        (0, 2),
+        (2, 2),
+        (4, 2),
+        (1, 1),
+        (2, 1),
+        (3, 1),
+        (2, 0),
+    ],
+    5,
+);
+/// Drawn in place of characters which aren't supported.
+pub const REPLACEMENT: Character = Character::new(
+    &[
+        (0, 5),
+        (1, 5),
+        (2, 5),
+        (3, 5),
+        (0, 4),
+        (3, 4),
+        (0, 3),
+        (3, 3),
+        (0, 2),
+        (3, 2),
+        (0, 1),
+        (3, 1),
+        (0, 0),
+        (1, 0),
+        (2, 0),
+        (3, 0),
+    ],
+    4,
+);
+
+/// Look up the glyph of a `char`, or `None` if it isn't supported.
+pub const fn lookup(value: char) -> Option<Character> {
+    let character = match value {
+        '0' => ZERO,
+        '1' => ONE,
+        '2' => TWO,
+        '3' => THREE,
+        '4' => FOUR,
+        '5' => FIVE,
+        '6' => SIX,
+        '7' => SEVEN,
+        '8' => EIGHT,
+        '9' => NINE,
+        'A' => A,
+        'B' => B,
+        'C' => C,
+        'D' => D,
+        'E' => E,
+        'F' => F,
+        'G' => G,
+        'H' => H,
+        'I' => I,
+        'J' => J,
+        'K' => K,
+        'L' => L,
+        'M' => M,
+        'N' => N,
+        'O' => O,
+        'P' => P,
+        'Q' => Q,
+        'R' => R,
+        'S' => S,
+        'T' => T,
+        'U' => U,
+        'V' => V,
+        'W' => W,
+        'X' => X,
+        'Y' => Y,
+        'Z' => Z,
+        'a' => a,
+        'b' => b,
+        'c' => c,
+        'd' => d,
+        'e' => e,
+        'f' => f,
+        'g' => g,
+        'h' => h,
+        'i' => i,
+        'j' => j,
+        'k' => k,
+        'l' => l,
+        'm' => m,
+        'n' => n,
+        'o' => o,
+        'p' => p,
+        'q' => q,
+        'r' => r,
+        's' => s,
+        't' => t,
+        'u' => u,
+        'v' => v,
+        'w' => w,
+        'x' => x,
+        'y' => y,
+        'z' => z,
+        'Ä' => A_UMLAUT,
+        'Ö' => O_UMLAUT,
+        'Ü' => U_UMLAUT,
+        'ä' => a_umlaut,
+        'ö' => o_umlaut,
+        'ü' => u_umlaut,
+        'à' => a_grave,
+        'é' => e_acute,
+        'è' => e_grave,
+        'ç' => c_cedil,
+        'ß' => sharp_s,
+        ' ' => SPACE,
+        '!' => EXCLAM,
+        '"' => D_QUOTE,
+        '#' => HASH,
+        '$' => DOLLAR,
+        '%' => PERCENT,
+        '&' => AMPERSAND,
+        '\'' => SIN_QUOTE,
+        '(' => L_PARENTH,
+        ')' => R_PARENTH,
+        '*' => ASTERISK,
+        '+' => PLUS,
+        ',' => COMMA,
+        '-' => MINUS,
+        '.' => F_STOP,
+        '/' => SLASH,
+        ':' => COLON,
+        ';' => S_COLON,
+        '<' => LESS,
+        '=' => EQUAL,
+        '>' => MORE,
+        '?' => QUESTION,
+        '@' => AT,
+        '[' => L_BRACKET,
+        '\\' => B_SLASH,
+        ']' => R_BRACKET,
+        '^' => CARET,
+        '_' => U_SCORE,
+        '{' => L_BRACE,
+        '|' => V_BAR,
+        '}' => R_BRACE,
+        '~' => TILDE,
+        '°' => DEGREE,
+        '€' => EURO,
+        '£' => POUND,
+        '♥' => HEART,
+        '←' => L_ARROW,
+        '→' => R_ARROW,
+        '↑' => U_ARROW,
+        '↓' => D_ARROW,
+        _ => return None,
+    };
+    Some(character)
+}
@@ -0,0 +1,462 @@
+//! Colors and a number of predefined color values.
+//!
+//! The LED-matrix stores colors as plain tuples `(red, green, blue)`. You can
+//! set an LED to one of the predefined colors like this:
+//!
+//! ```
+//! # use led_matrix::color;
+//! # fn app(matrix: &mut dyn led_matrix::LedMatrix) {
+//! matrix[(2, 6)] = color::PURPLE;
+//! # }
+//! ```
+//!
+//! To compute colors, use the type [Color]. It converts to and from tuples,
+//! so it works everywhere a tuple is expected:
+//!
+//! ```
+//! use led_matrix::color::{self, Color};
+//!
+//! # fn app(matrix: &mut dyn led_matrix::LedMatrix) {
+//! let sky = Color::from_hsv(200.0, 0.8, 1.0);
+//! let dusk = sky.lerp(color::ORANGE.into(), 128).scale(100);
+//! matrix[(2, 6)] = dusk.into();
+//! # }
+//! ```
+
+pub const YELLOW: (u8, u8, u8) = (255, 255, 0);
+pub const ORANGE: (u8, u8, u8) = (255, 165, 0);
+pub const RED: (u8, u8, u8) = (255, 0, 0);
+pub const PURPLE: (u8, u8, u8) = (128, 0, 128);
+pub const PINK: (u8, u8, u8) = (255, 0, 255);
+pub const BLUE: (u8, u8, u8) = (0, 0, 255);
+pub const TEAL: (u8, u8, u8) = (0, 128, 128);
+pub const AQUA: (u8, u8, u8) = (0, 255, 255);
+pub const LIME: (u8, u8, u8) = (0, 255, 0);
+pub const GREEN: (u8, u8, u8) = (0, 128, 0);
+pub const LIGHT_GREY: (u8, u8, u8) = (119, 136, 153);
+pub const GREY: (u8, u8, u8) = (100, 100, 100);
+pub const BROWN: (u8, u8, u8) = (139, 69, 19);
+pub const LIGHT_BROWN: (u8, u8, u8) = (205, 133, 63);
+pub const WHITE: (u8, u8, u8) = (255, 255, 255);
+pub const BLACK: (u8, u8, u8) = (0, 0, 0);
+
+/// An RGB color with 8 bits per channel.
+///
+/// All operations work on the 8 bit channel values directly, without any
+/// gamma correction.
+///
+/// The operators `+` and `-` saturate instead of overflowing, just like
+/// [saturating_add](Self::saturating_add) and
+/// [saturating_sub](Self::saturating_sub).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+/// How two colors are combined by [Color::blend].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlendMode {
+    /// The other color replaces this one.
+    #[default]
+    Replace,
+    /// The channels are added, see [Color::saturating_add].
+    Add,
+    /// The channels are multiplied, see [Color::multiply].
+    Multiply,
+    /// The inverted channels are multiplied, see [Color::screen].
+    Screen,
+}
+
+impl Color {
+    pub const YELLOW: Self = Self::from_tuple(YELLOW);
+    pub const ORANGE: Self = Self::from_tuple(ORANGE);
+    pub const RED: Self = Self::from_tuple(RED);
+    pub const PURPLE: Self = Self::from_tuple(PURPLE);
+    pub const PINK: Self = Self::from_tuple(PINK);
+    pub const BLUE: Self = Self::from_tuple(BLUE);
+    pub const TEAL: Self = Self::from_tuple(TEAL);
+    pub const AQUA: Self = Self::from_tuple(AQUA);
+    pub const LIME: Self = Self::from_tuple(LIME);
+    pub const GREEN: Self = Self::from_tuple(GREEN);
+    pub const LIGHT_GREY: Self = Self::from_tuple(LIGHT_GREY);
+    pub const GREY: Self = Self::from_tuple(GREY);
+    pub const BROWN: Self = Self::from_tuple(BROWN);
+    pub const LIGHT_BROWN: Self = Self::from_tuple(LIGHT_BROWN);
+    pub const WHITE: Self = Self::from_tuple(WHITE);
+    pub const BLACK: Self = Self::from_tuple(BLACK);
+
+    pub const fn new(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b }
+    }
+
+    /// The same as `Color::from`, but usable in constants.
+    pub const fn from_tuple((r, g, b): (u8, u8, u8)) -> Self {
+        Self { r, g, b }
+    }
+
+    /// The same as `<(u8, u8, u8)>::from`, but usable in constants.
+    pub const fn to_tuple(self) -> (u8, u8, u8) {
+        (self.r, self.g, self.b)
+    }
+
+    /// Convert from the HSV color space.
+    ///
+    /// The `hue` is an angle in degrees, it wraps around at 360. The
+    /// `saturation` and `value` range from 0 to 1 and are clamped to that
+    /// range.
+    ///
+    /// ```
+    /// use led_matrix::color::Color;
+    ///
+    /// assert_eq!(Color::from_hsv(120.0, 1.0, 1.0), Color::LIME);
+    /// assert_eq!(Color::from_hsv(-60.0, 1.0, 1.0), Color::PINK);
+    /// ```
+    pub const fn from_hsv(hue: f32, saturation: f32, value: f32) -> Self {
+        let value = clamp_unit(value);
+        let chroma = value * clamp_unit(saturation);
+        Self::from_hue_chroma(hue, chroma, value - chroma)
+    }
+
+    /// Convert to the HSV color space, returning `(hue, saturation, value)`
+    /// as described in [from_hsv](Self::from_hsv). The hue is in the range
+    /// `0.0..360.0`, it is zero for shades of grey.
+    pub const fn to_hsv(self) -> (f32, f32, f32) {
+        let (min, max) = self.min_max();
+        let saturation = if max == 0 {
+            0.0
+        } else {
+            (max - min) as f32 / max as f32
+        };
+        (self.hue(), saturation, max as f32 / 255.0)
+    }
+
+    /// Convert from the HSL color space.
+    ///
+    /// The `hue` is an angle in degrees, it wraps around at 360. The
+    /// `saturation` and `lightness` range from 0 to 1 and are clamped to that
+    /// range.
+    pub const fn from_hsl(hue: f32, saturation: f32, lightness: f32) -> Self {
+        let lightness = clamp_unit(lightness);
+        let chroma = (1.0 - abs(2.0 * lightness - 1.0)) * clamp_unit(saturation);
+        Self::from_hue_chroma(hue, chroma, lightness - chroma / 2.0)
+    }
+
+    /// Convert to the HSL color space, returning
+    /// `(hue, saturation, lightness)` as described in
+    /// [from_hsl](Self::from_hsl).
+    pub const fn to_hsl(self) -> (f32, f32, f32) {
+        let (min, max) = self.min_max();
+        let lightness = (max as f32 + min as f32) / 510.0;
+        let saturation = if max == min {
+            0.0
+        } else {
+            (max - min) as f32 / 255.0 / (1.0 - abs(2.0 * lightness - 1.0))
+        };
+        (self.hue(), saturation, lightness)
+    }
+
+    /// Linear interpolation between two colors. At `t = 0` the result is
+    /// this color, at `t = 255` it is the other color.
+    pub const fn lerp(self, other: Self, t: u8) -> Self {
+        const fn lerp(a: u8, b: u8, t: u8) -> u8 {
+            let delta = (b as i32 - a as i32) * t as i32;
+            // round to the nearest value, away from `a` on a tie
+            (a as i32 + (delta + delta.signum() * 127) / 255) as u8
+        }
+        Self::new(
+            lerp(self.r, other.r, t),
+            lerp(self.g, other.g, t),
+            lerp(self.b, other.b, t),
+        )
+    }
+
+    /// Scale every channel by `factor / 255`, so `255` keeps the color as it
+    /// is and `0` turns it black.
+    pub const fn scale(self, factor: u8) -> Self {
+        self.multiply(Self::new(factor, factor, factor))
+    }
+
+    /// Add the channels of two colors, saturating at 255. This is how light
+    /// of several sources adds up.
+    pub const fn saturating_add(self, other: Self) -> Self {
+        Self::new(
+            self.r.saturating_add(other.r),
+            self.g.saturating_add(other.g),
+            self.b.saturating_add(other.b),
+        )
+    }
+
+    /// Subtract the channels of another color, saturating at 0.
+    pub const fn saturating_sub(self, other: Self) -> Self {
+        Self::new(
+            self.r.saturating_sub(other.r),
+            self.g.saturating_sub(other.g),
+            self.b.saturating_sub(other.b),
+        )
+    }
+
+    /// Multiply the channels of two colors as if they were in the range
+    /// `0.0..=1.0`. The result is never brighter than either color.
+    pub const fn multiply(self, other: Self) -> Self {
+        Self::new(
+            mul(self.r, other.r),
+            mul(self.g, other.g),
+            mul(self.b, other.b),
+        )
+    }
+
+    /// The inverse of [multiply](Self::multiply): the inverted channels are
+    /// multiplied and the result is inverted again. The result is never
+    /// darker than either color.
+    pub const fn screen(self, other: Self) -> Self {
+        self.invert().multiply(other.invert()).invert()
+    }
+
+    /// Combine this color with another one according to `mode`.
+    ///
+    /// ```
+    /// use led_matrix::color::{BlendMode, Color};
+    ///
+    /// let red = Color::new(200, 0, 0);
+    /// let blue = Color::new(0, 0, 200);
+    /// assert_eq!(red.blend(blue, BlendMode::Add), Color::new(200, 0, 200));
+    /// assert_eq!(red.blend(blue, BlendMode::Multiply), Color::BLACK);
+    /// ```
+    pub const fn blend(self, other: Self, mode: BlendMode) -> Self {
+        match mode {
+            BlendMode::Replace => other,
+            BlendMode::Add => self.saturating_add(other),
+            BlendMode::Multiply => self.multiply(other),
+            BlendMode::Screen => self.screen(other),
+        }
+    }
+
+    /// The complementary color, every channel `c` becomes `255 - c`.
+    pub const fn invert(self) -> Self {
+        Self::new(255 - self.r, 255 - self.g, 255 - self.b)
+    }
+
+    /// Common part of the conversions from HSV and HSL. `m` is added to
+    /// every channel to match the value or lightness.
+    const fn from_hue_chroma(hue: f32, chroma: f32, m: f32) -> Self {
+        let mut hue = hue % 360.0;
+        if hue < 0.0 {
+            hue += 360.0;
+        }
+        let sector = hue / 60.0;
+        let x = chroma * (1.0 - abs(sector % 2.0 - 1.0));
+        let (r, g, b) = match sector as u8 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        Self::new(unit_to_u8(r + m), unit_to_u8(g + m), unit_to_u8(b + m))
+    }
+
+    /// The hue in degrees, shared by HSV and HSL.
+    const fn hue(self) -> f32 {
+        let (min, max) = self.min_max();
+        if max == min {
+            return 0.0;
+        }
+        let delta = (max - min) as f32;
+        let (r, g, b) = (self.r as f32, self.g as f32, self.b as f32);
+        let hue = if max == self.r {
+            (g - b) / delta
+        } else if max == self.g {
+            (b - r) / delta + 2.0
+        } else {
+            (r - g) / delta + 4.0
+        };
+        if hue < 0.0 {
+            hue * 60.0 + 360.0
+        } else {
+            hue * 60.0
+        }
+    }
+
+    const fn min_max(self) -> (u8, u8) {
+        let (mut min, mut max) = (self.r, self.r);
+        let others = [self.g, self.b];
+        let mut i = 0;
+        while i < others.len() {
+            if others[i] < min {
+                min = others[i];
+            }
+            if others[i] > max {
+                max = others[i];
+            }
+            i += 1;
+        }
+        (min, max)
+    }
+}
+
+const fn abs(x: f32) -> f32 {
+    if x < 0.0 {
+        -x
+    } else {
+        x
+    }
+}
+
+const fn clamp_unit(x: f32) -> f32 {
+    if x < 0.0 {
+        0.0
+    } else if x > 1.0 {
+        1.0
+    } else {
+        x
+    }
+}
+
+/// Map `0.0..=1.0` to `0..=255`, rounding to the nearest value.
+const fn unit_to_u8(x: f32) -> u8 {
+    // the cast saturates, so slightly out of range values are fine
+    (x * 255.0 + 0.5) as u8
+}
+
+/// Multiply two channel values as if they were in the range `0.0..=1.0`.
+const fn mul(a: u8, b: u8) -> u8 {
+    ((a as u16 * b as u16 + 127) / 255) as u8
+}
+
+impl From<(u8, u8, u8)> for Color {
+    fn from(color: (u8, u8, u8)) -> Self {
+        Self::from_tuple(color)
+    }
+}
+
+impl From<Color> for (u8, u8, u8) {
+    fn from(color: Color) -> Self {
+        color.to_tuple()
+    }
+}
+
+impl core::ops::Add for Color {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        self.saturating_add(other)
+    }
+}
+
+impl core::ops::AddAssign for Color {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl core::ops::Sub for Color {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        self.saturating_sub(other)
+    }
+}
+
+impl core::ops::SubAssign for Color {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRIMARIES: [(Color, f32); 6] = [
+        (Color::RED, 0.0),
+        (Color::YELLOW, 60.0),
+        (Color::LIME, 120.0),
+        (Color::AQUA, 180.0),
+        (Color::BLUE, 240.0),
+        (Color::PINK, 300.0),
+    ];
+
+    #[test]
+    fn hsv_round_trip() {
+        for (color, hue) in PRIMARIES {
+            assert_eq!(color.to_hsv(), (hue, 1.0, 1.0));
+            assert_eq!(Color::from_hsv(hue, 1.0, 1.0), color);
+            assert_eq!(Color::from_hsv(hue + 360.0, 1.0, 1.0), color);
+        }
+        // shades of grey have no hue and no saturation
+        assert_eq!(Color::BLACK.to_hsv(), (0.0, 0.0, 0.0));
+        assert_eq!(Color::WHITE.to_hsv(), (0.0, 0.0, 1.0));
+        assert_eq!(Color::GREY.to_hsv(), (0.0, 0.0, 100.0 / 255.0));
+        assert_eq!(Color::from_hsv(0.0, 0.0, 100.0 / 255.0), Color::GREY);
+        assert_eq!(Color::from_hsv(77.0, 1.0, 0.0), Color::BLACK);
+        // out of range values are clamped
+        assert_eq!(Color::from_hsv(0.0, 2.0, 2.0), Color::RED);
+        assert_eq!(Color::from_hsv(0.0, -1.0, 1.0), Color::WHITE);
+    }
+
+    #[test]
+    fn hsl_round_trip() {
+        for (color, hue) in PRIMARIES {
+            assert_eq!(color.to_hsl(), (hue, 1.0, 0.5));
+            assert_eq!(Color::from_hsl(hue, 1.0, 0.5), color);
+        }
+        assert_eq!(Color::BLACK.to_hsl(), (0.0, 0.0, 0.0));
+        assert_eq!(Color::WHITE.to_hsl(), (0.0, 0.0, 1.0));
+        assert_eq!(Color::GREY.to_hsl(), (0.0, 0.0, 100.0 / 255.0));
+        assert_eq!(Color::from_hsl(0.0, 0.0, 100.0 / 255.0), Color::GREY);
+        // no matter the saturation, lightness 0 is black and 1 is white
+        assert_eq!(Color::from_hsl(200.0, 1.0, 0.0), Color::BLACK);
+        assert_eq!(Color::from_hsl(200.0, 1.0, 1.0), Color::WHITE);
+    }
+
+    #[test]
+    fn lerp() {
+        let (a, b) = (Color::new(10, 200, 0), Color::new(250, 0, 1));
+        assert_eq!(a.lerp(b, 0), a);
+        assert_eq!(a.lerp(b, 255), b);
+        assert_eq!(a.lerp(b, 128), Color::new(130, 100, 1));
+        assert_eq!(b.lerp(a, 128), Color::new(130, 100, 0));
+    }
+
+    #[test]
+    fn scale_multiply_and_screen() {
+        let color = Color::new(255, 100, 1);
+        assert_eq!(color.scale(255), color);
+        assert_eq!(color.scale(0), Color::BLACK);
+        assert_eq!(color.scale(128), Color::new(128, 50, 1));
+
+        assert_eq!(color.multiply(Color::WHITE), color);
+        assert_eq!(color.screen(Color::BLACK), color);
+        assert_eq!(color.screen(Color::WHITE), Color::WHITE);
+        assert_eq!(
+            Color::new(128, 0, 255).screen(Color::new(128, 64, 0)),
+            Color::new(192, 64, 255)
+        );
+        assert_eq!(
+            color.blend(Color::new(0, 200, 0), BlendMode::Screen),
+            Color::new(255, 222, 1)
+        );
+    }
+
+    #[test]
+    fn add_and_sub_saturate() {
+        let mut color = Color::new(200, 10, 0);
+        color += Color::new(100, 10, 0);
+        assert_eq!(color, Color::new(255, 20, 0));
+        color -= Color::new(0, 30, 1);
+        assert_eq!(color, Color::new(255, 0, 0));
+        assert_eq!(Color::WHITE.invert(), Color::BLACK);
+    }
+
+    #[test]
+    fn tuples() {
+        assert_eq!(Color::from(ORANGE), Color::new(255, 165, 0));
+        assert_eq!(<(u8, u8, u8)>::from(Color::ORANGE), ORANGE);
+        assert_eq!(Color::from_tuple(BROWN).to_tuple(), BROWN);
+        let color: Color = (1, 2, 3).into();
+        assert_eq!((color.r, color.g, color.b), (1, 2, 3));
+    }
+}
@@ -0,0 +1,199 @@
+//! Integration with [embedded-graphics](https://docs.rs/embedded-graphics).
+//!
+//! This module is only available with the feature `embedded-graphics`.
+//!
+//! Wrap the LED-matrix in a [Canvas] to use it as a `DrawTarget`. Then you
+//! can use everything from the embedded-graphics ecosystem, like its fonts,
+//! primitives, text layout and image formats, both on hardware and in the
+//! emulators.
+//!
+//! embedded-graphics puts the origin in the top left corner with the y-axis
+//! pointing down, whereas the LED-matrix puts it in the bottom left corner
+//! with the y-axis pointing up. The canvas translates between the two, so
+//! anything drawn with embedded-graphics shows up the right way around. Keep
+//! in mind that a point `(x, y)` of embedded-graphics therefore corresponds
+//! to the LED `(x, H - y - 1)`.
+//!
+//! ```
+//! use embedded_graphics::{
+//!     mono_font::{ascii::FONT_4X6, MonoTextStyle},
+//!     pixelcolor::Rgb888,
+//!     prelude::*,
+//!     primitives::{Circle, PrimitiveStyle},
+//!     text::Text,
+//! };
+//! use led_matrix::graphics::Canvas;
+//!
+//! fn app(matrix: &mut dyn led_matrix::LedMatrix) {
+//!     let mut canvas = Canvas::new(matrix);
+//!
+//!     Circle::new(Point::new(1, 1), 6)
+//!         .into_styled(PrimitiveStyle::with_stroke(Rgb888::GREEN, 1))
+//!         .draw(&mut canvas)
+//!         .unwrap();
+//!
+//!     let style = MonoTextStyle::new(&FONT_4X6, Rgb888::RED);
+//!     Text::new("Hi", Point::new(0, 6), style)
+//!         .draw(&mut canvas)
+//!         .unwrap();
+//!
+//!     matrix.apply();
+//! }
+//! ```
+
+use core::convert::Infallible;
+
+use embedded_graphics_core::{
+    draw_target::DrawTarget,
+    geometry::{OriginDimensions, Point, Size},
+    pixelcolor::{Rgb888, RgbColor},
+    Pixel,
+};
+
+use crate::LedMatrix;
+
+/// An adapter that makes an LED-matrix usable as an embedded-graphics
+/// `DrawTarget`.
+///
+/// Pixels outside of the matrix are ignored. Like all other drawing methods,
+/// drawing on the canvas only stores the colors, you still need to call
+/// [apply](LedMatrix::apply) on the matrix afterwards.
+pub struct Canvas<'a, const W: usize = 8, const H: usize = 8> {
+    matrix: &'a mut dyn LedMatrix<W, H>,
+}
+
+impl<'a, const W: usize, const H: usize> Canvas<'a, W, H> {
+    pub fn new(matrix: &'a mut dyn LedMatrix<W, H>) -> Self {
+        Self { matrix }
+    }
+
+    /// Get the wrapped matrix back, e.g. to call [apply](LedMatrix::apply)
+    /// while the canvas is still in use.
+    pub fn matrix(&mut self) -> &mut dyn LedMatrix<W, H> {
+        self.matrix
+    }
+
+    /// The LED at a point of embedded-graphics, if it's on the matrix.
+    fn led(Point { x, y }: Point) -> Option<(usize, usize)> {
+        let x = usize::try_from(x).ok().filter(|&x| x < W)?;
+        let y = usize::try_from(y).ok().filter(|&y| y < H)?;
+        Some((x, H - y - 1))
+    }
+}
+
+impl<const W: usize, const H: usize> OriginDimensions for Canvas<'_, W, H> {
+    fn size(&self) -> Size {
+        Size::new(W as u32, H as u32)
+    }
+}
+
+impl<const W: usize, const H: usize> DrawTarget for Canvas<'_, W, H> {
+    type Color = Rgb888;
+    type Error = Infallible;
+
+    fn draw_iter<I>(&mut self, pixels: I) -> Result<(), Self::Error>
+    where
+        I: IntoIterator<Item = Pixel<Self::Color>>,
+    {
+        for Pixel(point, color) in pixels {
+            if let Some(led) = Self::led(point) {
+                self.matrix[led] = (color.r(), color.g(), color.b());
+            }
+        }
+        Ok(())
+    }
+
+    fn clear(&mut self, color: Self::Color) -> Result<(), Self::Error> {
+        self.matrix.fill((color.r(), color.g(), color.b()));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use embedded_graphics::{
+        prelude::*,
+        primitives::{PrimitiveStyle, Rectangle},
+    };
+    use std::{string::String, vec::Vec};
+
+    /// Render the current LEDs of a matrix as text, from the top row down.
+    fn render<const W: usize, const H: usize>(
+        matrix: &mut led_matrix_headless::LedMatrix<W, H>,
+    ) -> Vec<String> {
+        matrix.apply();
+        let leds = matrix.last_frame().unwrap().leds;
+        leds.iter()
+            .map(|row| {
+                row.iter()
+                    .map(|&led| match led {
+                        (0, 0, 0) => '.',
+                        (255, 255, 255) => '#',
+                        (255, 0, 0) => 'r',
+                        (0, 0, 255) => 'b',
+                        _ => '?',
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn pixels_are_flipped_vertically() {
+        let mut matrix = led_matrix_headless::LedMatrix::<4, 3>::new();
+        let mut canvas = Canvas::new(&mut matrix);
+        assert_eq!(canvas.size(), Size::new(4, 3));
+        let pixels = [
+            Pixel(Point::new(0, 0), Rgb888::RED),
+            Pixel(Point::new(3, 2), Rgb888::BLUE),
+            Pixel(Point::new(2, 1), Rgb888::WHITE),
+            // outside of the matrix
+            Pixel(Point::new(-1, 0), Rgb888::WHITE),
+            Pixel(Point::new(0, -1), Rgb888::WHITE),
+            Pixel(Point::new(4, 0), Rgb888::WHITE),
+            Pixel(Point::new(0, 3), Rgb888::WHITE),
+            Pixel(Point::new(i32::MIN, i32::MAX), Rgb888::WHITE),
+        ];
+        canvas.draw_iter(pixels).unwrap();
+        assert_eq!(render(&mut matrix), ["r...", "..#.", "...b"]);
+        // (x, y) of embedded-graphics is the LED (x, H - y - 1)
+        assert_eq!(matrix[(0, 2)], (255, 0, 0));
+        assert_eq!(matrix[(3, 0)], (0, 0, 255));
+    }
+
+    #[test]
+    fn primitives() {
+        let mut matrix = led_matrix_headless::LedMatrix::<8, 8>::new();
+        let mut canvas = Canvas::new(&mut matrix);
+        canvas.clear(Rgb888::BLUE).unwrap();
+        Rectangle::new(Point::new(1, 2), Size::new(3, 2))
+            .into_styled(PrimitiveStyle::with_fill(Rgb888::WHITE))
+            .draw(&mut canvas)
+            .unwrap();
+        // partially outside of the matrix
+        Rectangle::new(Point::new(6, 6), Size::new(4, 4))
+            .into_styled(PrimitiveStyle::with_stroke(Rgb888::RED, 1))
+            .draw(&mut canvas)
+            .unwrap();
+        assert_eq!(
+            render(&mut matrix),
+            [
+                "bbbbbbbb", //
+                "bbbbbbbb", "b###bbbb", "b###bbbb", "bbbbbbbb", "bbbbbbbb", "bbbbbbrr", "bbbbbbrb",
+            ]
+        );
+    }
+
+    #[test]
+    fn clear_fills_the_matrix() {
+        let mut matrix = led_matrix_headless::LedMatrix::<2, 2>::new();
+        matrix[(0, 0)] = (255, 255, 255);
+        Canvas::new(&mut matrix).clear(Rgb888::RED).unwrap();
+        assert_eq!(render(&mut matrix), ["rr", "rr"]);
+        Canvas::new(&mut matrix).clear(Rgb888::BLACK).unwrap();
+        assert_eq!(render(&mut matrix), ["..", ".."]);
+    }
+}
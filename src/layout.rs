@@ -0,0 +1,481 @@
+//! Text which doesn't fit on a single line.
+//!
+//! A [TextBox] wraps text at spaces into lines, and the lines into pages
+//! which fill the box. This shows short messages without scrolling:
+//!
+//! ```
+//! use led_matrix::{
+//!     character::{Style, FONT_3X5},
+//!     color,
+//!     layout::{Alignment, TextBox},
+//! };
+//!
+//! # fn app(matrix: &mut dyn led_matrix::LedMatrix<16, 16>) {
+//! let text_box = TextBox::new(&FONT_3X5, (16, 16)).with_alignment(Alignment::Center);
+//! text_box.show(matrix, "NO WIFI, CHECK CABLE", &Style::color(color::RED), 2_000);
+//! # }
+//! ```
+//!
+//! [VerticalText] runs from top to bottom instead, either with its
+//! characters stacked on top of each other or rotated, and can be scrolled
+//! with a [Marquee](crate::marquee::Marquee).
+
+use crate::{
+    character::{Font, Pixel, Style},
+    color, LedMatrix,
+};
+
+/// How lines are aligned within a [TextBox].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Alignment {
+    #[default]
+    Left,
+    Center,
+    Right,
+}
+
+/// A rectangular area, which text is laid out in.
+///
+/// Lines are wrapped at spaces, or within a word if it's wider than the
+/// box. A newline always starts a new line.
+#[derive(Debug, Clone, Copy)]
+pub struct TextBox<'a> {
+    font: &'a Font,
+    width: usize,
+    height: usize,
+    alignment: Alignment,
+}
+
+impl<'a> TextBox<'a> {
+    /// A box of the given size, in which lines are aligned to the left.
+    pub const fn new(font: &'a Font, (width, height): (usize, usize)) -> Self {
+        Self {
+            font,
+            width,
+            height,
+            alignment: Alignment::Left,
+        }
+    }
+
+    pub const fn with_alignment(mut self, alignment: Alignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+
+    pub const fn font(&self) -> &'a Font {
+        self.font
+    }
+
+    pub const fn alignment(&self) -> Alignment {
+        self.alignment
+    }
+
+    /// The number of lines on a page. A page has at least one line, even if
+    /// the box isn't high enough for it.
+    pub const fn lines_per_page(&self) -> usize {
+        // the empty row below the last line isn't needed
+        let lines = (self.height + 1) / self.font.line_height();
+        if lines == 0 {
+            1
+        } else {
+            lines
+        }
+    }
+
+    /// The lines of a text, without the spaces at which they were wrapped.
+    ///
+    /// ```
+    /// use led_matrix::{character::FONT_3X5, layout::TextBox};
+    ///
+    /// let text_box = TextBox::new(&FONT_3X5, (16, 16));
+    /// let mut lines = text_box.lines("HOT TEA\nIS READY");
+    /// assert_eq!(lines.next(), Some("HOT"));
+    /// assert_eq!(lines.next(), Some("TEA"));
+    /// assert_eq!(lines.next(), Some("IS"));
+    /// assert_eq!(lines.next(), Some("READ"));
+    /// assert_eq!(lines.next(), Some("Y"));
+    /// assert_eq!(lines.next(), None);
+    /// ```
+    pub fn lines<'t>(&self, text: &'t str) -> Lines<'a, 't> {
+        Lines {
+            rest: text,
+            font: self.font,
+            width: self.width,
+        }
+    }
+
+    /// The pages of a text. Every page is the part of the text which is
+    /// shown at once, and can be drawn with [draw](Self::draw).
+    pub fn pages<'t>(&self, text: &'t str) -> Pages<'a, 't> {
+        Pages {
+            lines: self.lines(text),
+            lines_per_page: self.lines_per_page(),
+        }
+    }
+
+    /// Draw a page with the lower left corner of the box at the given
+    /// position. Lines which don't fit on the page are not drawn.
+    ///
+    /// The page is drawn on top of the current content of the matrix, like
+    /// [draw_styled_text](LedMatrix::draw_styled_text).
+    pub fn draw<const W: usize, const H: usize>(
+        &self,
+        matrix: &mut dyn LedMatrix<W, H>,
+        (x, y): (i32, i32),
+        page: &str,
+        style: &Style,
+    ) {
+        let top = y.saturating_add(self.height as i32);
+        let lines = self.lines(page).take(self.lines_per_page());
+        for (i, line) in lines.enumerate() {
+            let width = self.font.text_width(line.chars());
+            let space = self.width.saturating_sub(width) as i32;
+            let line_x = match self.alignment {
+                Alignment::Left => 0,
+                Alignment::Center => space / 2,
+                Alignment::Right => space,
+            };
+            let line_y = top
+                .saturating_sub((i * self.font.line_height()) as i32)
+                .saturating_sub(self.font.height() as i32);
+            let position = (x.saturating_add(line_x), line_y);
+            matrix.draw_styled_text(position, format_args!("{line}"), self.font, style);
+        }
+    }
+
+    /// Show all pages of a text one after another, each for `page_ms`
+    /// milliseconds, in the upper left corner of the matrix.
+    ///
+    /// The matrix is filled with the background color of the style before
+    /// every page, or cleared if there is none.
+    pub fn show<const W: usize, const H: usize>(
+        &self,
+        matrix: &mut dyn LedMatrix<W, H>,
+        text: &str,
+        style: &Style,
+        page_ms: u32,
+    ) {
+        let y = H as i32 - self.height as i32;
+        for page in self.pages(text) {
+            matrix.fill(style.background().unwrap_or(color::BLACK));
+            self.draw(matrix, (0, y), page, style);
+            matrix.apply();
+            matrix.sleep_ms(page_ms);
+        }
+    }
+}
+
+/// The word-wrapped lines of a text, see [TextBox::lines].
+#[derive(Debug, Clone)]
+pub struct Lines<'a, 't> {
+    rest: &'t str,
+    font: &'a Font,
+    width: usize,
+}
+
+impl<'t> Iterator for Lines<'_, 't> {
+    type Item = &'t str;
+
+    fn next(&mut self) -> Option<&'t str> {
+        if self.rest.is_empty() {
+            return None;
+        }
+        let text = self.rest;
+        // width of the line so far, including the empty column after it
+        let mut width = 0;
+        // the end of the line if it's wrapped at the last space
+        let mut space = None;
+        let mut previous = None;
+        // whether the line has a character other than a space, leading spaces
+        // aren't a place to wrap the line at
+        let mut has_word = false;
+        for (i, c) in text.char_indices() {
+            if c == '\n' {
+                self.rest = &text[i + 1..];
+                return Some(text[..i].trim_end_matches(' '));
+            }
+            width += self.font.advance(c);
+            if c == ' ' {
+                if has_word && previous != Some(' ') {
+                    space = Some(i);
+                }
+                previous = Some(c);
+                continue;
+            }
+            previous = Some(c);
+            if width - 1 <= self.width {
+                has_word = true;
+                continue;
+            }
+            // the character doesn't fit anymore
+            let (line, rest) = match space {
+                Some(space) => (&text[..space], text[space..].trim_start_matches(' ')),
+                // a line has at least one character which isn't a space
+                None if !has_word => text.split_at(i + c.len_utf8()),
+                None => text.split_at(i),
+            };
+            self.rest = rest;
+            return Some(line.trim_end_matches(' '));
+        }
+        self.rest = "";
+        Some(text.trim_end_matches(' '))
+    }
+}
+
+/// The pages of a text, see [TextBox::pages].
+#[derive(Debug, Clone)]
+pub struct Pages<'a, 't> {
+    lines: Lines<'a, 't>,
+    lines_per_page: usize,
+}
+
+impl<'t> Iterator for Pages<'_, 't> {
+    type Item = &'t str;
+
+    fn next(&mut self) -> Option<&'t str> {
+        let text = self.lines.rest;
+        let mut end = None;
+        for line in self.lines.by_ref().take(self.lines_per_page) {
+            // lines are slices of the text
+            end = Some(line.as_ptr() as usize - text.as_ptr() as usize + line.len());
+        }
+        end.map(|end| &text[..end])
+    }
+}
+
+/// How the characters of [VerticalText] are arranged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Orientation {
+    /// Every character stands upright below the previous one, centered on
+    /// the matrix.
+    #[default]
+    Stacked,
+    /// The text is turned clockwise, so that it's read from top to bottom
+    /// with the head tilted to the right.
+    Rotated,
+}
+
+/// Text which runs from the top of the matrix to the bottom.
+///
+/// Like a [vertical billboard](crate::billboard::vertical), it is drawn at
+/// an offset, the row of the text which is shown at the top edge of the
+/// matrix. For a [shader](Style::shader), the `x` of a [Pixel] is the row
+/// within the text, counted from the top.
+///
+/// ```
+/// use led_matrix::{
+///     character::{Style, FONT_5X7},
+///     color,
+///     layout::{Orientation, VerticalText},
+///     marquee::{Direction, Marquee, Repeat},
+/// };
+///
+/// # fn app(matrix: &mut dyn led_matrix::LedMatrix) {
+/// let text = VerticalText::new("HOTEL", &FONT_5X7).with_orientation(Orientation::Stacked);
+/// let style = Style::color(color::AQUA);
+/// let mut marquee = Marquee::new(text.height(), 8)
+///     .with_repeat(Repeat::Once)
+///     .with_direction(Direction::Up);
+/// marquee.run(matrix, |marquee, matrix| {
+///     marquee.draw_vertical_text(matrix, &text, &style)
+/// });
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct VerticalText<'a> {
+    text: &'a str,
+    font: &'a Font,
+    orientation: Orientation,
+}
+
+impl<'a> VerticalText<'a> {
+    /// Text with stacked characters.
+    pub const fn new(text: &'a str, font: &'a Font) -> Self {
+        Self {
+            text,
+            font,
+            orientation: Orientation::Stacked,
+        }
+    }
+
+    pub const fn with_orientation(mut self, orientation: Orientation) -> Self {
+        self.orientation = orientation;
+        self
+    }
+
+    pub const fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    /// The height of the text in pixels.
+    pub fn height(&self) -> usize {
+        match self.orientation {
+            Orientation::Stacked => {
+                let count = self.text.chars().count();
+                (count * self.font.line_height()).saturating_sub(1)
+            }
+            Orientation::Rotated => self.font.text_width(self.text.chars()),
+        }
+    }
+
+    /// Draw the text with the given row at the top edge of the matrix. The
+    /// offset may be negative, when the text starts further down.
+    ///
+    /// LEDs which aren't part of a character are set to the background color
+    /// of the style, or turned off if there is none.
+    pub fn draw<const W: usize, const H: usize>(
+        &self,
+        matrix: &mut dyn LedMatrix<W, H>,
+        offset: i32,
+        style: &Style,
+    ) {
+        matrix.fill(style.background().unwrap_or(color::BLACK));
+        for row in 0..H {
+            let text_row = usize::try_from(offset as i64 + row as i64).ok();
+            if let Some(text_row) = text_row {
+                self.draw_row(matrix, row, text_row, style);
+            }
+        }
+    }
+
+    /// Draw a row of the text onto a row of the matrix, both counted from the
+    /// top.
+    pub(crate) fn draw_row<const W: usize, const H: usize>(
+        &self,
+        matrix: &mut dyn LedMatrix<W, H>,
+        row: usize,
+        text_row: usize,
+        style: &Style,
+    ) {
+        let font = self.font;
+        let y = H - row - 1;
+        match self.orientation {
+            Orientation::Stacked => {
+                let index = text_row / font.line_height();
+                // the empty row between two characters
+                let Some(glyph_y) = (font.height() - 1).checked_sub(text_row % font.line_height())
+                else {
+                    return;
+                };
+                let Some(c) = self.text.chars().nth(index) else {
+                    return;
+                };
+                let character = font.glyph_or_replacement(c);
+                let left = W.saturating_sub(character.width) / 2;
+                for &(x, _) in character.coordinates.iter().filter(|&&(_, y)| y == glyph_y) {
+                    let pixel = Pixel {
+                        x: text_row,
+                        y: glyph_y,
+                        index,
+                    };
+                    if left + x < W {
+                        matrix[(left + x, y)] = style.foreground(pixel);
+                    }
+                }
+            }
+            Orientation::Rotated => {
+                // find the character and its column in this row
+                let mut start = 0;
+                for (index, c) in self.text.chars().enumerate() {
+                    let character = font.glyph_or_replacement(c);
+                    if text_row >= start + character.width {
+                        start += character.width + 1;
+                        continue;
+                    } else if text_row < start {
+                        // the empty column between two characters
+                        return;
+                    }
+                    // the bottom of the characters is on the left
+                    let left = W.saturating_sub(font.height()) / 2;
+                    let column = text_row - start;
+                    for &(_, glyph_y) in character.coordinates.iter().filter(|&&(x, _)| x == column)
+                    {
+                        let pixel = Pixel {
+                            x: text_row,
+                            y: glyph_y,
+                            index,
+                        };
+                        if left + glyph_y < W {
+                            matrix[(left + glyph_y, y)] = style.foreground(pixel);
+                        }
+                    }
+                    return;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::character::FONT_3X5;
+    use std::vec::Vec;
+
+    fn lines(text: &str, width: usize) -> Vec<&str> {
+        TextBox::new(&FONT_3X5, (width, 5)).lines(text).collect()
+    }
+
+    #[test]
+    fn wrap_at_spaces() {
+        assert_eq!(lines("AB CD EF", 8), ["AB", "CD", "EF"]);
+        assert_eq!(lines("A B CD", 10), ["A B", "CD"]);
+        assert_eq!(lines("AB ", 8), ["AB"]);
+        assert_eq!(lines("", 8), [""; 0]);
+    }
+
+    #[test]
+    fn split_long_words() {
+        assert_eq!(lines("ABCDE", 8), ["AB", "CD", "E"]);
+        assert_eq!(lines("A BCDEF", 8), ["A", "BC", "DE", "F"]);
+        // a line has at least one character, even if it doesn't fit
+        assert_eq!(lines("ABC", 2), ["A", "B", "C"]);
+    }
+
+    #[test]
+    fn newlines() {
+        assert_eq!(lines("A\nB", 8), ["A", "B"]);
+        assert_eq!(lines("A\n\nB\n", 8), ["A", "", "B"]);
+        assert_eq!(lines("A \nB", 8), ["A", "B"]);
+    }
+
+    #[test]
+    fn runs_of_spaces() {
+        assert_eq!(lines("AB   CD", 8), ["AB", "CD"]);
+        assert_eq!(lines("A  B", 16), ["A  B"]);
+        assert_eq!(lines("AB   \n  CD", 8), ["AB", "  C", "D"]);
+    }
+
+    #[test]
+    fn leading_spaces() {
+        // leading spaces are kept, but the line isn't wrapped at them
+        assert_eq!(lines(" ABCD", 8), [" A", "BC", "D"]);
+        assert_eq!(lines("AB\n ABCD", 8), ["AB", " A", "BC", "D"]);
+        assert_eq!(lines("   AB", 8), ["   A", "B"]);
+    }
+
+    #[test]
+    fn pages() {
+        // two lines of the 3x5 font need 11 rows
+        let text_box = TextBox::new(&FONT_3X5, (8, 11));
+        assert_eq!(text_box.lines_per_page(), 2);
+        let pages: Vec<_> = text_box.pages("AB CD EF\nGH").collect();
+        assert_eq!(pages, ["AB CD", "EF\nGH"]);
+
+        // a page has at least one line
+        let text_box = TextBox::new(&FONT_3X5, (8, 3));
+        assert_eq!(text_box.lines_per_page(), 1);
+        let pages: Vec<_> = text_box.pages("AB CD").collect();
+        assert_eq!(pages, ["AB", "CD"]);
+    }
+
+    #[test]
+    fn vertical_text_height() {
+        let text = VerticalText::new("AB", &FONT_3X5);
+        assert_eq!(text.height(), 11);
+        let text = text.with_orientation(Orientation::Rotated);
+        assert_eq!(text.height(), 7);
+    }
+}
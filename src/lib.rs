@@ -7,24 +7,86 @@
 
 #![no_std]
 
-use core::cmp::Ordering;
+pub use led_matrix_core::{correction, mapping, power, JoystickPosition};
 
-pub use led_matrix_core::JoystickPosition;
+/// Decode an image file while compiling and embed it as a
+/// [Bitmap](bitmap::Bitmap).
+///
+/// Supported formats are PNG, GIF (only the first frame) and BMP. The path
+/// is relative to the directory containing your `Cargo.toml`. Only the
+/// pixels end up in the firmware, three bytes per pixel, or four if the image
+/// is transparent. An image which can't be decoded is a compile error.
+///
+/// ```
+/// use led_matrix::{bitmap::Bitmap, include_image};
+///
+/// static CRAB: Bitmap = include_image!("bitmaps/crab.bmp");
+///
+/// # fn app(matrix: &mut dyn led_matrix::LedMatrix) {
+/// matrix.draw_bitmap(&CRAB);
+/// matrix.apply();
+/// # }
+/// ```
+pub use led_matrix_macros::include_image;
+
+/// Import a bitmap font while compiling and embed it as a
+/// [Font](character::Font).
+///
+/// The font must be in the Glyph Bitmap Distribution Format (BDF), which
+/// most pixel fonts are available in, e.g. Tom Thumb or Spleen. Fonts in
+/// the binary PCF format can be converted with `pcf2bdf`. The path is
+/// relative to the directory containing your `Cargo.toml`.
+///
+/// Glyphs are looked up by their Unicode code point. Unsupported characters
+/// are drawn as the `DEFAULT_CHAR` of the font, or as a box if it has none.
+/// Fonts may be at most 32 pixels high.
+///
+/// ```
+/// use led_matrix::{character::Font, color, include_font};
+///
+/// static DIGITS: Font = include_font!("fonts/digits-3x8.bdf");
+///
+/// # fn app(matrix: &mut dyn led_matrix::LedMatrix) {
+/// matrix.draw_text((0, 0), "42", &DIGITS, color::ORANGE);
+/// matrix.apply();
+/// # }
+/// ```
+pub use led_matrix_macros::include_font;
 
-use led_matrix_core::{LedMatrixCore, HEIGHT, WIDTH};
+// lets macros refer to this crate as `led_matrix`, even within it
+extern crate self as led_matrix;
 
+use led_matrix_core::{
+    mapping::{Mapping, Wiring},
+    LedMatrixCore,
+};
+
+pub mod animation;
 pub mod billboard;
+pub mod bitmap;
 pub mod character;
+pub mod color;
+#[cfg(feature = "embedded-graphics")]
+pub mod graphics;
+pub mod layout;
+pub mod marquee;
+mod shape;
+pub mod sprite;
+pub mod tiled;
 
 /// A high-level interface for programming the LED-matrix.
 ///
 /// To update the color of an LED, you can index the `LedMatrix` with a tuple
-/// of integers (x, y) in the range `0..8` each.
+/// of integers (x, y) in the range `0..W` and `0..H` respectively.
+///
+/// The size of the matrix is specified with the const generic parameters `W`
+/// (width) and `H` (height). They default to the 8x8 LED-matrix, so
+/// `dyn LedMatrix` is the same as `dyn LedMatrix<8, 8>`.
 ///
 /// After changing the values of one or several LEDs, don't forget to call
 /// [`apply`](LedMatrix::apply) to actually apply these changes in a batch.
 ///
-/// Here is the coordinate system visualized:
+/// Here is the coordinate system of the 8x8 LED-matrix visualized:
 ///
 /// ```txt
 /// ╭─────┬─────┬─────┬─────┬─────┬─────┬─────┬─────╮
@@ -46,7 +108,7 @@ pub mod character;
 /// ╰─────┴─────┴─────┴─────┴─────┴─────┴─────┴─────╯
 /// ```
 ///
-pub trait LedMatrix:
+pub trait LedMatrix<const W: usize = 8, const H: usize = 8>:
     core::ops::Index<(usize, usize), Output = (u8, u8, u8)> + core::ops::IndexMut<(usize, usize)>
 {
     /// Tell the LED-matrix to display the currently stored color values for
@@ -59,12 +121,62 @@ pub trait LedMatrix:
 
     /// Set the brightness of the display.
     ///
-    /// The display is set at a default brightness of `50` (about 20%).
+    /// The display is set at a default brightness of `255`. Frames that would
+    /// draw too much current are dimmed further by the power limit, see
+    /// [set_power_limit](Self::set_power_limit).
     ///
-    /// This method is a no-op for the GUI emulator.
+    /// The emulators simulate the brightness, so the LEDs look about as
+    /// bright as on the real LED-matrix. Press B in the emulator to show the
+    /// current brightness.
+    ///
+    /// The brightness scales the emitted light linearly. To fade the display
+    /// in a way that looks even, map the levels with
+    /// [perceptual_brightness](correction::perceptual_brightness).
     ///
     fn set_brightness(&mut self, brightness: u8);
 
+    /// Set the gamma correction applied to every color before it's sent to
+    /// the LEDs.
+    ///
+    /// By default, colors are sent to the LEDs as they are
+    /// ([Gamma::LINEAR](correction::Gamma::LINEAR)). Low values then look
+    /// much brighter than on a computer screen and fades look uneven. With
+    /// [Gamma::GAMMA_2_2](correction::Gamma::GAMMA_2_2), colors look about
+    /// the same as on a screen.
+    ///
+    /// The emulators simulate the correction, so colors look the same as on
+    /// the real LED-matrix.
+    ///
+    /// ```
+    /// use led_matrix::correction::Gamma;
+    ///
+    /// # fn app(matrix: &mut dyn led_matrix::LedMatrix) {
+    /// matrix.set_gamma(Gamma::GAMMA_2_8);
+    /// # }
+    /// ```
+    ///
+    fn set_gamma(&mut self, gamma: correction::Gamma);
+
+    /// Set the budget for the current drawn by the LEDs.
+    ///
+    /// Every frame is dimmed just enough for the estimated current to stay
+    /// within the budget, which defaults to the 500mA of a USB port
+    /// ([PowerLimit::USB](power::PowerLimit::USB)). Frames which draw little
+    /// current, e.g. a few dim LEDs, are displayed at full brightness.
+    ///
+    /// The emulators simulate the limit as well.
+    ///
+    /// ```
+    /// use led_matrix::power::PowerLimit;
+    ///
+    /// # fn app(matrix: &mut dyn led_matrix::LedMatrix) {
+    /// // powered by a 2A supply
+    /// matrix.set_power_limit(PowerLimit::new(2_000));
+    /// # }
+    /// ```
+    ///
+    fn set_power_limit(&mut self, limit: power::PowerLimit);
+
     /// Sleep for the specified amount of milliseconds.
     ///
     fn sleep_ms(&mut self, duration: u32);
@@ -92,8 +204,8 @@ pub trait LedMatrix:
     /// You still need to call [apply](Self::apply) afterwards.
     ///
     fn fill(&mut self, color: (u8, u8, u8)) {
-        for x in 0..WIDTH as usize {
-            for y in 0..HEIGHT as usize {
+        for x in 0..W {
+            for y in 0..H {
                 self[(x, y)] = color;
             }
         }
@@ -119,61 +231,271 @@ pub trait LedMatrix:
         }
     }
 
-    // TODO: draw_line
+    /// Set a single LED, given as signed coordinates (x, y).
+    ///
+    /// Unlike indexing the matrix, coordinates outside of it are allowed and
+    /// silently ignored. All the shape drawing methods below clip their
+    /// shapes the same way, so shapes may extend past the edges.
+    ///
+    fn draw_point(&mut self, (x, y): (i32, i32), color: (u8, u8, u8)) {
+        if let (Ok(x), Ok(y)) = (usize::try_from(x), usize::try_from(y)) {
+            if x < W && y < H {
+                self[(x, y)] = color;
+            }
+        }
+    }
 
-    /// Draw a bitmap file with a color depth of 24 bit.
+    /// Draw a straight line between two points, both of which are included.
     ///
-    /// bitmap format: <https://en.wikipedia.org/wiki/BMP_file_format>
+    fn draw_line(&mut self, from: (i32, i32), to: (i32, i32), color: (u8, u8, u8)) {
+        shape::line(from, to, (W, H), &mut |x, y| self.draw_point((x, y), color));
+    }
+
+    /// Draw the outline of the rectangle spanned by two opposite corners.
     ///
-    fn draw_bitmap(&mut self, bitmap: &[u8]) {
-        let color_depth = u16::from_le_bytes(bitmap[28..30].try_into().unwrap());
-        let bitmap_size = u32::from_le_bytes(bitmap[2..6].try_into().unwrap());
-        let bitmap_offset = u32::from_le_bytes(bitmap[10..14].try_into().unwrap());
-        let image_size = u32::from_le_bytes(bitmap[34..38].try_into().unwrap());
-        let bitmap_height = u16::from_be_bytes(bitmap[22..24].try_into().unwrap()) as u32;
-        // let upper_left_origin = bitmap_height < 0;
-        let pic = &bitmap[bitmap_offset as usize..];
-        let bitmap_width = (image_size / 3) / bitmap_height;
+    fn draw_rect(&mut self, (x0, y0): (i32, i32), (x1, y1): (i32, i32), color: (u8, u8, u8)) {
+        self.draw_line((x0, y0), (x1, y0), color);
+        self.draw_line((x1, y0), (x1, y1), color);
+        self.draw_line((x1, y1), (x0, y1), color);
+        self.draw_line((x0, y1), (x0, y0), color);
+    }
 
-        if bitmap_height > HEIGHT as u32 || bitmap_width > WIDTH as u32 {
-            // TODO: How to debug? println not available on no_std.
-            // println!(format!(
-            //     "bitmap is larger than matrix: {bitmap_width}x{bitmap_height}"
-            // ));
-        }
-        if (color_depth) != 24 {
-            panic!("Wrong color-depth ({color_depth}) detected. Use bitmaps with a color-depth of 24 bits.");
+    /// Fill the rectangle spanned by two opposite corners.
+    ///
+    fn fill_rect(&mut self, (x0, y0): (i32, i32), (x1, y1): (i32, i32), color: (u8, u8, u8)) {
+        let clip_x = |x: i32| x.clamp(0, W as i32);
+        let clip_y = |y: i32| y.clamp(0, H as i32);
+        for x in clip_x(x0.min(x1))..clip_x(x0.max(x1).saturating_add(1)) {
+            for y in clip_y(y0.min(y1))..clip_y(y0.max(y1).saturating_add(1)) {
+                self[(x as usize, y as usize)] = color;
+            }
         }
-        if (bitmap_size) != 246 {
-            // TODO: How to debug? println not available on no_std.
-            // println!("The bitmap size is different than expected. The image may be defective.");
+    }
+
+    /// Draw the outline of a circle.
+    ///
+    fn draw_circle(&mut self, (cx, cy): (i32, i32), radius: u16, color: (u8, u8, u8)) {
+        shape::circle_octant(radius.into(), &mut |x, y| {
+            for (x, y) in [(x, y), (y, x)] {
+                self.draw_point((cx + x, cy + y), color);
+                self.draw_point((cx - x, cy + y), color);
+                self.draw_point((cx + x, cy - y), color);
+                self.draw_point((cx - x, cy - y), color);
+            }
+        });
+    }
+
+    /// Draw a filled circle.
+    ///
+    fn fill_circle(&mut self, (cx, cy): (i32, i32), radius: u16, color: (u8, u8, u8)) {
+        shape::circle_octant(radius.into(), &mut |x, y| {
+            for (x, y) in [(x, y), (y, x)] {
+                self.fill_rect((cx - x, cy + y), (cx + x, cy + y), color);
+                self.fill_rect((cx - x, cy - y), (cx + x, cy - y), color);
+            }
+        });
+    }
+
+    /// Draw the outline of an axis-aligned ellipse with the horizontal and
+    /// vertical radii `(rx, ry)`.
+    ///
+    fn draw_ellipse(&mut self, (cx, cy): (i32, i32), (rx, ry): (u16, u16), color: (u8, u8, u8)) {
+        let (rx, ry) = (i32::from(rx), i32::from(ry));
+        if rx == 0 || ry == 0 {
+            // degenerate ellipses are just lines
+            self.draw_line((cx - rx, cy - ry), (cx + rx, cy + ry), color);
+            return;
         }
-        for x in 0..WIDTH as usize {
-            for y in 0..HEIGHT as usize {
-                let i = (y * WIDTH as usize + x) * 3;
-                let y = HEIGHT as usize - y - 1;
-                self[(x, y)] = (pic[i + 2], pic[i + 1], pic[i]);
+        shape::ellipse_quadrant((rx, ry), &mut |x, y| {
+            self.draw_point((cx + x, cy + y), color);
+            self.draw_point((cx - x, cy + y), color);
+            self.draw_point((cx + x, cy - y), color);
+            self.draw_point((cx - x, cy - y), color);
+        });
+    }
+
+    /// Draw the outline of a triangle.
+    ///
+    fn draw_triangle(&mut self, a: (i32, i32), b: (i32, i32), c: (i32, i32), color: (u8, u8, u8)) {
+        self.draw_polygon(&[a, b, c], color);
+    }
+
+    /// Draw a filled triangle. It covers exactly the same LEDs as its
+    /// outline drawn with [draw_triangle](Self::draw_triangle) plus the
+    /// interior.
+    ///
+    fn fill_triangle(&mut self, a: (i32, i32), b: (i32, i32), c: (i32, i32), color: (u8, u8, u8)) {
+        for (y, span) in shape::triangle_spans::<H>(a, b, c).into_iter().enumerate() {
+            if let Some((x0, x1)) = span {
+                self.fill_rect((x0, y as i32), (x1, y as i32), color);
             }
         }
     }
 
+    /// Draw the outline of a polygon. The last point is connected to the
+    /// first one to close the shape.
+    ///
+    /// ```
+    /// # fn app(matrix: &mut dyn led_matrix::LedMatrix) {
+    /// // a diamond reaching out of the matrix on every side
+    /// matrix.draw_polygon(&[(3, -2), (9, 3), (3, 9), (-2, 3)], (0, 255, 0));
+    /// matrix.apply();
+    /// # }
+    /// ```
+    ///
+    fn draw_polygon(&mut self, points: &[(i32, i32)], color: (u8, u8, u8)) {
+        for (i, &from) in points.iter().enumerate() {
+            let to = points[(i + 1) % points.len()];
+            self.draw_line(from, to, color);
+        }
+    }
+
+    /// Draw a bitmap with its upper left corner in the upper left corner of
+    /// the matrix. Parts of the bitmap which don't fit are clipped.
+    ///
+    /// Every pixel of the bitmap is drawn as it is, its alpha channel is
+    /// ignored. Pixels skipped by a compressed bitmap are left untouched.
+    ///
+    /// Parse a bitmap file with [Bitmap::parse](bitmap::Bitmap::parse), or
+    /// use one of the predefined bitmaps in the module [bitmap].
+    ///
+    /// ```
+    /// # fn app(matrix: &mut dyn led_matrix::LedMatrix) {
+    /// matrix.draw_bitmap(led_matrix::bitmap::CRAB);
+    /// matrix.apply();
+    /// # }
+    /// ```
+    ///
+    fn draw_bitmap(&mut self, bitmap: &bitmap::Bitmap) {
+        let top = H as i32 - bitmap.height() as i32;
+        self.draw_bitmap_at(bitmap, (0, top), bitmap::Transparency::Opaque);
+    }
+
+    /// Draw a bitmap with its lower left corner at the given position, which
+    /// may be outside of the matrix. Parts of the bitmap which don't fit are
+    /// clipped, so a bitmap can be moved around or panned across.
+    ///
+    /// ```
+    /// use led_matrix::bitmap::{Transparency, BIG_IMG};
+    ///
+    /// # fn app(matrix: &mut dyn led_matrix::LedMatrix) {
+    /// // pan from the bottom left to the top right corner of the image
+    /// let (width, height) = (BIG_IMG.width() as i32, BIG_IMG.height() as i32);
+    /// for offset in 0..=(width - 8).min(height - 8) {
+    ///     matrix.draw_bitmap_at(BIG_IMG, (-offset, -offset), Transparency::Opaque);
+    ///     matrix.apply();
+    ///     matrix.sleep_ms(100);
+    /// }
+    /// # }
+    /// ```
+    ///
+    fn draw_bitmap_at(
+        &mut self,
+        bitmap: &bitmap::Bitmap,
+        position: (i32, i32),
+        transparency: bitmap::Transparency,
+    ) {
+        let region = sprite::Region::new((0, 0), (bitmap.width(), bitmap.height()));
+        draw_region(self, bitmap, region, position, transparency);
+    }
+
+    /// Draw a sprite of a sprite sheet with its lower left corner at the
+    /// given position, which may be outside of the matrix.
+    ///
+    /// The sprite is drawn with the transparency of the sprite sheet. See
+    /// the module [sprite] for an example.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sprite sheet has no sprite with this index.
+    ///
+    fn draw_sprite(&mut self, sheet: &sprite::SpriteSheet, index: usize, position: (i32, i32)) {
+        let region = sheet.region(index).expect("sprite index out of range");
+        draw_region(self, sheet.bitmap(), region, position, sheet.transparency());
+    }
+
+    /// Draw text with the lower left corner of its first character at the
+    /// given position, which may be outside of the matrix. Returns the width
+    /// of the text in pixels.
+    ///
+    /// The text is drawn on top of the current content of the matrix. Use
+    /// [draw_text_fmt](Self::draw_text_fmt) to draw formatted text.
+    ///
+    /// Unsupported characters are drawn as the replacement glyph of the
+    /// font. Check string literals while compiling with [text!].
+    ///
+    fn draw_text(
+        &mut self,
+        position: (i32, i32),
+        text: &str,
+        font: &character::Font,
+        color: (u8, u8, u8),
+    ) -> usize {
+        self.draw_text_fmt(position, format_args!("{text}"), font, color)
+    }
+
+    /// Draw formatted text like [draw_text](Self::draw_text), without
+    /// allocating a string for it.
+    ///
+    /// ```
+    /// use led_matrix::{character::FONT_5X7, color};
+    ///
+    /// # fn app(matrix: &mut dyn led_matrix::LedMatrix) {
+    /// let score = 42;
+    /// let width = matrix.draw_text_fmt((0, 0), format_args!("{score}"), &FONT_5X7, color::GREEN);
+    /// assert_eq!(width, 11);
+    /// # }
+    /// ```
+    ///
+    fn draw_text_fmt(
+        &mut self,
+        position: (i32, i32),
+        text: core::fmt::Arguments,
+        font: &character::Font,
+        color: (u8, u8, u8),
+    ) -> usize {
+        self.draw_styled_text(position, text, font, &character::Style::color(color))
+    }
+
+    /// Draw formatted text like [draw_text_fmt](Self::draw_text_fmt), but
+    /// colored according to a [Style](character::Style), e.g. with a
+    /// background or in the colors of the rainbow.
+    ///
+    /// ```
+    /// use led_matrix::character::{rainbow, Style, FONT_5X7};
+    ///
+    /// # fn app(matrix: &mut dyn led_matrix::LedMatrix) {
+    /// matrix.draw_styled_text((0, 0), format_args!("Hi"), &FONT_5X7, &Style::shader(&rainbow));
+    /// # }
+    /// ```
+    ///
+    fn draw_styled_text(
+        &mut self,
+        position: (i32, i32),
+        text: core::fmt::Arguments,
+        font: &character::Font,
+        style: &character::Style,
+    ) -> usize {
+        let mut writer = character::TextWriter::new(self, position, font, style);
+        // the writer itself never fails
+        let _ = core::fmt::write(&mut writer, text);
+        writer.width()
+    }
+
     /// Draw a frame of a horizontal billboard at a specified offset.
     ///
     /// Construct such a billboard with [`billboard::horizontal`].
     ///
     /// This function only draws a single frame, you probably want to
     /// loob over offsets and draw each frame with a desired delay using
-    /// [sleep_ms](Self::sleep_ms).
+    /// [sleep_ms](Self::sleep_ms), or let a [Marquee](marquee::Marquee) do it.
     ///
     /// See the module documentation of [billboard] for more information.
     ///
-    fn draw_horizontal_billboard_frame(&mut self, billboard: billboard::Billboard, offset: usize) {
-        for (x, column) in (offset..offset + WIDTH as usize).enumerate() {
-            for y in 0..HEIGHT as usize {
-                self[(x, y)] = match billboard
-                    .get(column)
-                    .map(|col| col[HEIGHT as usize - y - 1])
-                {
+    fn draw_horizontal_billboard_frame(&mut self, billboard: &[[bool; H]], offset: usize) {
+        for (x, column) in (offset..offset + W).enumerate() {
+            for y in 0..H {
+                self[(x, y)] = match billboard.get(column).map(|col| col[H - y - 1]) {
                     Some(true) => color::WHITE,
                     _ => color::BLACK,
                 }
@@ -187,10 +509,10 @@ pub trait LedMatrix:
     ///
     /// This function is analogous to [draw_horizontal_billboard_frame](Self::draw_horizontal_billboard_frame).
     ///
-    fn draw_vertical_billboard_frame(&mut self, billboard: billboard::Billboard, offset: usize) {
-        for (y, row) in (offset..offset + HEIGHT as usize).enumerate() {
-            let y = HEIGHT as usize - y - 1;
-            for x in 0..WIDTH as usize {
+    fn draw_vertical_billboard_frame(&mut self, billboard: &[[bool; W]], offset: usize) {
+        for (y, row) in (offset..offset + H).enumerate() {
+            let y = H - y - 1;
+            for x in 0..W {
                 self[(x, y)] = match billboard.get(row).map(|row| row[x]) {
                     Some(true) => color::WHITE,
                     _ => color::BLACK,
@@ -201,64 +523,71 @@ pub trait LedMatrix:
 
     /// Draw a frame of a strip of text at a specified offset.
     ///
-    /// Construct such a strip of text with [`character::convert_str`].
+    /// Construct such a strip of text with [convert_str!].
     ///
     /// Like [draw_horizontal_billboard_frame](Self::draw_horizontal_billboard_frame),
     /// this function only draws a single frame. You probably want to loob
     /// over offsets and draw each frame with a desired delay using [sleep_ms](Self::sleep_ms).
     ///
-    fn draw_text_billboard_frame(
+    fn draw_text_billboard_frame(&mut self, text: &[character::Character], frame_offset: usize) {
+        let style = character::Style::color(color::WHITE);
+        self.draw_styled_text_billboard_frame(text, frame_offset, &style);
+    }
+
+    /// Draw a frame of a strip of text at a specified offset, colored
+    /// according to a [Style](character::Style).
+    ///
+    /// LEDs which aren't part of a character are set to the background color
+    /// of the style, or turned off if there is none.
+    ///
+    /// ```
+    /// use led_matrix::{character::Style, color, convert_str};
+    ///
+    /// # fn app(matrix: &mut dyn led_matrix::LedMatrix) {
+    /// let (text, length) = &convert_str!("Hopp!");
+    /// let style = Style::colors(&[color::RED, color::WHITE]).with_background(color::BLUE);
+    /// for offset in 0..*length {
+    ///     matrix.draw_styled_text_billboard_frame(text, offset, &style);
+    ///     matrix.apply();
+    ///     matrix.sleep_ms(50);
+    /// }
+    /// # }
+    /// ```
+    ///
+    fn draw_styled_text_billboard_frame(
         &mut self,
         text: &[character::Character],
-        frame_offset: usize, // colors=ColorTable.WHITE, delay_ms=50, direction="left"
+        frame_offset: usize,
+        style: &character::Style,
     ) {
-        // let length: usize = text.iter().map(|c| c.width).sum::<usize>() - 1;
+        self.fill(style.background().unwrap_or(color::BLACK));
 
-        // TODO: custom color support
-        // // use same color for all bitmaps if only one color is supplied
-        // if not isinstance(colors, list):
-        //     colors = [colors] * len(bitmaps)
-
-        self.clear();
-
-        for &c in text.iter() {
+        for (index, c) in text.iter().enumerate() {
             if c.offset + c.width < frame_offset {
                 continue;
-            } else if frame_offset + WIDTH as usize - 1 < c.offset {
+            } else if frame_offset + W <= c.offset {
                 break;
             }
-            let mut coords = c.coordinates.iter().copied().filter_map(|(mut x, y)| {
-                // remove out-of-bounds coordinates and apply offset
-                match frame_offset.cmp(&c.offset) {
-                    Ordering::Less => {
-                        x += c.offset - frame_offset;
-                        if x >= WIDTH as usize {
-                            // Character is partially in frame, but this
-                            // specific pixel is beyond the right border of
-                            // the frame.
-                            return None;
-                        }
-                    }
-                    Ordering::Equal => {}
-                    Ordering::Greater => {
-                        let offset_diff = frame_offset - c.offset;
-                        if offset_diff > x {
-                            // Character is partially in frame, but this
-                            // specific pixel is beyond the left border of
-                            // the frame.
-                            return None;
-                        }
-                        x -= offset_diff;
-                    }
+            for &(x, y) in c.coordinates {
+                // the column within the text, and on the matrix
+                let column = c.offset + x;
+                let Some(screen_x) = column.checked_sub(frame_offset) else {
+                    continue;
+                };
+                // glyphs may not fit on the matrix
+                if screen_x < W && y < H {
+                    let pixel = character::Pixel {
+                        x: column,
+                        y,
+                        index,
+                    };
+                    self[(screen_x, y)] = style.foreground(pixel);
                 }
-                Some((x, y))
-            });
-
-            self.draw_coordinates(&mut coords, color::WHITE);
+            }
         }
     }
 }
-impl<T: LedMatrixCore> LedMatrix for T {
+impl<const W: usize, const H: usize, T: LedMatrixCore<W, H>> LedMatrix<W, H> for T {
     fn apply(&mut self) {
         self.apply()
     }
@@ -267,6 +596,14 @@ impl<T: LedMatrixCore> LedMatrix for T {
         self.set_brightness(brightness)
     }
 
+    fn set_gamma(&mut self, gamma: correction::Gamma) {
+        self.set_gamma(gamma)
+    }
+
+    fn set_power_limit(&mut self, limit: power::PowerLimit) {
+        self.set_power_limit(limit)
+    }
+
     fn sleep_ms(&mut self, duration: u32) {
         self.sleep_ms(duration)
     }
@@ -288,87 +625,117 @@ impl<T: LedMatrixCore> LedMatrix for T {
     }
 }
 
+/// Draw a region of a bitmap with its lower left corner at the given
+/// position, shared by [draw_bitmap_at](LedMatrix::draw_bitmap_at) and
+/// [draw_sprite](LedMatrix::draw_sprite).
+fn draw_region<const W: usize, const H: usize, M: LedMatrix<W, H> + ?Sized>(
+    matrix: &mut M,
+    bitmap: &bitmap::Bitmap,
+    region: sprite::Region,
+    (x, y): (i32, i32),
+    transparency: bitmap::Transparency,
+) {
+    bitmap.for_each_pixel_in(region, |(bx, by), (r, g, b, alpha)| {
+        let position = (x.saturating_add(bx as i32), y.saturating_add(by as i32));
+        let (Ok(px), Ok(py)) = (usize::try_from(position.0), usize::try_from(position.1)) else {
+            return;
+        };
+        if px >= W || py >= H {
+            return;
+        }
+        let color = (r, g, b);
+        matrix[(px, py)] = match transparency {
+            bitmap::Transparency::Opaque => color,
+            bitmap::Transparency::Alpha => color::Color::from(matrix[(px, py)])
+                .lerp(color.into(), alpha)
+                .into(),
+            bitmap::Transparency::ColorKey(key) if key == color => return,
+            bitmap::Transparency::ColorKey(_) => color,
+        };
+    });
+}
+
 /// Runs your program with an initialized [LedMatrix].
 ///
 /// The implementation (hardware or emulator) is automatically chosen based on
-/// the compilation target.
+/// the compilation target. On the host, the feature `tui` selects the TUI
+/// emulator instead of the GUI one and the feature `headless` selects an
+/// in-memory LED-matrix without any display, which is useful for CI.
 ///
 /// This run function is a slightly leaky abstraction, unfortunately. The
 /// emulator is built with egui, which must be run on the main thread. However,
 /// the library API want to give the user control over the rendering loop. This
 /// means the users code must be moved to a separate thread.
 ///
-pub fn run<F: FnOnce(&mut dyn LedMatrix) + Send + 'static>(f: F) -> ! {
+/// The size of the matrix is inferred from the signature of your function.
+/// For example, a function taking `&mut dyn LedMatrix<16, 16>` runs on a
+/// 16x16 matrix, whereas `&mut dyn LedMatrix` is the regular 8x8 one.
+///
+pub fn run<const W: usize, const H: usize, F: FnOnce(&mut dyn LedMatrix<W, H>) + Send + 'static>(
+    f: F,
+) -> ! {
+    run_mapped(Wiring::DEFAULT, f)
+}
+
+/// Like [run], but with a custom mapping of the LED chain to positions on
+/// the matrix.
+///
+/// Use this if your panel is wired differently than the regular LED-matrix
+/// or mounted rotated or mirrored. See the module [mapping] for details.
+/// The emulators always display the matrix as intended, so the mapping only
+/// affects the hardware.
+///
+/// ```no_run
+/// use led_matrix::mapping::Wiring;
+///
+/// fn app(matrix: &mut dyn led_matrix::LedMatrix<16, 16>) {
+///     // ...
+/// }
+///
+/// led_matrix::run_mapped(Wiring::DEFAULT.serpentine(), app);
+/// ```
+///
+pub fn run_mapped<
+    const W: usize,
+    const H: usize,
+    M: Mapping<W, H> + Send + 'static,
+    F: FnOnce(&mut dyn LedMatrix<W, H>) + Send + 'static,
+>(
+    mapping: M,
+    f: F,
+) -> ! {
     #[cfg(target_os = "none")]
     {
-        led_matrix_bsp::run(|mut matrix| f(&mut matrix))
+        led_matrix_bsp::run_mapped::<W, H, M, _>(mapping, |mut matrix| f(&mut matrix))
     }
     #[cfg(not(target_os = "none"))]
     {
-        #[cfg(not(feature = "tui"))]
+        let _ = mapping;
+        #[cfg(feature = "headless")]
         {
-            led_matrix_gui::run(|mut matrix| f(&mut matrix))
+            led_matrix_headless::run::<W, H, _>(|mut matrix| f(&mut matrix))
         }
-        #[cfg(feature = "tui")]
+        #[cfg(all(not(feature = "headless"), not(feature = "tui")))]
         {
-            led_matrix_tui::run(|mut matrix| f(&mut matrix))
+            led_matrix_gui::run::<W, H, _>(|mut matrix| f(&mut matrix))
+        }
+        #[cfg(all(not(feature = "headless"), feature = "tui"))]
+        {
+            led_matrix_tui::run::<W, H, _>(|mut matrix| f(&mut matrix))
         }
     }
 }
 
-/// Contains a number of predefined color values.
-///
-/// You can set an LED to one of these colors like this:
+/// Returns an iterator over the coordinates of all LEDs of the 8x8
+/// LED-matrix. Useful for avoiding nested loops.
 ///
-/// ```
-/// *matrix.get_mut(2, 6) = color::PURPLE;
-/// ```
-pub mod color {
-    pub const YELLOW: (u8, u8, u8) = (255, 255, 0);
-    pub const ORANGE: (u8, u8, u8) = (255, 165, 0);
-    pub const RED: (u8, u8, u8) = (255, 0, 0);
-    pub const PURPLE: (u8, u8, u8) = (128, 0, 128);
-    pub const PINK: (u8, u8, u8) = (255, 0, 255);
-    pub const BLUE: (u8, u8, u8) = (0, 0, 255);
-    pub const TEAL: (u8, u8, u8) = (0, 128, 128);
-    pub const AQUA: (u8, u8, u8) = (0, 255, 255);
-    pub const LIME: (u8, u8, u8) = (0, 255, 0);
-    pub const GREEN: (u8, u8, u8) = (0, 128, 0);
-    pub const LIGHT_GREY: (u8, u8, u8) = (119, 136, 153);
-    pub const GREY: (u8, u8, u8) = (100, 100, 100);
-    pub const BROWN: (u8, u8, u8) = (139, 69, 19);
-    pub const LIGHT_BROWN: (u8, u8, u8) = (205, 133, 63);
-    pub const WHITE: (u8, u8, u8) = (255, 255, 255);
-    pub const BLACK: (u8, u8, u8) = (0, 0, 0);
-}
-
-/// Contains a number of predefined bitmaps.
-pub mod bitmap {
-    pub static APPLE: &[u8] = include_bytes!("../bitmaps/apple.bmp");
-    pub static BAT: &[u8] = include_bytes!("../bitmaps/bat.bmp");
-    pub static BIG_IMG: &[u8] = include_bytes!("../bitmaps/big_img.bmp");
-    pub static BIRD: &[u8] = include_bytes!("../bitmaps/bird.bmp");
-    pub static CHICKEN: &[u8] = include_bytes!("../bitmaps/chicken.bmp");
-    pub static CRAB: &[u8] = include_bytes!("../bitmaps/crab.bmp");
-    pub static DINO: &[u8] = include_bytes!("../bitmaps/dino.bmp");
-    pub static DRINK: &[u8] = include_bytes!("../bitmaps/drink.bmp");
-    pub static DUCK: &[u8] = include_bytes!("../bitmaps/duck.bmp");
-    pub static ERLENMEYER: &[u8] = include_bytes!("../bitmaps/erlenmeyer.bmp");
-    pub static FOX: &[u8] = include_bytes!("../bitmaps/fox.bmp");
-    pub static MC_CREEPER: &[u8] = include_bytes!("../bitmaps/mc_creeper.bmp");
-    pub static MC_PIG: &[u8] = include_bytes!("../bitmaps/mc_pig.bmp");
-    pub static MOB_1: &[u8] = include_bytes!("../bitmaps/mob_1.bmp");
-    pub static MOUSE: &[u8] = include_bytes!("../bitmaps/mouse.bmp");
-    pub static MUSHROOM: &[u8] = include_bytes!("../bitmaps/mushroom.bmp");
-    pub static PIKACHU: &[u8] = include_bytes!("../bitmaps/pikachu.bmp");
-    pub static RABBIT: &[u8] = include_bytes!("../bitmaps/rabbit.bmp");
-    pub static SKULL: &[u8] = include_bytes!("../bitmaps/skull.bmp");
-    pub static TEST_GRID: &[u8] = include_bytes!("../bitmaps/test_grid.bmp");
-    pub static WINE: &[u8] = include_bytes!("../bitmaps/wine.bmp");
+/// For matrices of other sizes, see [led_coordinates].
+pub fn all_led_coordinates() -> impl Iterator<Item = (usize, usize)> {
+    led_coordinates::<8, 8>()
 }
 
-/// Returns an iterator over the coordinates of all LEDs. Useful for avoiding
-/// nested loops.
-pub fn all_led_coordinates() -> impl Iterator<Item = (usize, usize)> {
-    (0..HEIGHT as usize).flat_map(|y| (0..WIDTH as usize).map(move |x| (x, y)))
+/// Returns an iterator over the coordinates of all LEDs of a matrix with
+/// width `W` and height `H`.
+pub fn led_coordinates<const W: usize, const H: usize>() -> impl Iterator<Item = (usize, usize)> {
+    (0..H).flat_map(|y| (0..W).map(move |x| (x, y)))
 }
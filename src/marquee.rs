@@ -0,0 +1,630 @@
+//! Scrolling text and billboards across the matrix.
+//!
+//! A [Marquee] keeps track of how far its content has moved. It knows the
+//! length of the content in pixels, in which [Direction] and how fast it
+//! moves, whether it pauses at the start or end, and what happens when the
+//! end is reached ([Repeat]).
+//!
+//! It can run on its own, sleeping between frames with
+//! [sleep_ms](crate::LedMatrix::sleep_ms):
+//!
+//! ```
+//! use led_matrix::{
+//!     character::Style,
+//!     color, convert_str,
+//!     marquee::{Direction, Marquee, Repeat},
+//! };
+//!
+//! # fn app(matrix: &mut dyn led_matrix::LedMatrix) {
+//! let (text, length) = &convert_str!("Hello World");
+//! let style = Style::color(color::ORANGE);
+//! let mut marquee = Marquee::new(*length, 8)
+//!     .with_speed(40)
+//!     .with_pauses(500, 500)
+//!     .with_repeat(Repeat::Bounce)
+//!     .with_direction(Direction::Left);
+//! marquee.run(matrix, |marquee, matrix| {
+//!     marquee.draw_text(matrix, text, &style)
+//! });
+//! # }
+//! ```
+//!
+//! Or it can be advanced by the time that has passed from a game loop,
+//! which draws other things as well:
+//!
+//! ```
+//! use led_matrix::{
+//!     billboard::{horizontal, Billboard},
+//!     marquee::{Direction, Marquee, Repeat},
+//! };
+//!
+//! static WAVE: Billboard = &horizontal([
+//!     *b"  ##      ##    ",
+//!     *b" #  #    #  #   ",
+//!     *b"#    #  #    #  ",
+//!     *b"      ##      ##",
+//!     *b"                ",
+//!     *b"                ",
+//!     *b"                ",
+//!     *b"                ",
+//! ]);
+//!
+//! # fn app(matrix: &mut dyn led_matrix::LedMatrix) {
+//! // the wave is repeated without a gap
+//! let mut marquee = Marquee::new(WAVE.len(), 8)
+//!     .with_repeat(Repeat::Loop)
+//!     .with_direction(Direction::Right);
+//! for _ in 0..100 {
+//!     marquee.draw_horizontal_billboard(matrix, WAVE);
+//!     matrix.draw_point((3, 6), (255, 255, 0));
+//!     matrix.apply();
+//!     matrix.sleep_ms(20);
+//!     marquee.advance(20);
+//! }
+//! # }
+//! ```
+//!
+//! The offset of a marquee is the position in the content which is shown at
+//! the left edge of the matrix for horizontal content, or at the top edge for
+//! [vertical billboards](crate::billboard::vertical), like the offset of
+//! [draw_horizontal_billboard_frame](crate::LedMatrix::draw_horizontal_billboard_frame).
+//! Unlike there, it may be negative, when the content has only partially
+//! entered the matrix.
+
+use crate::{
+    billboard::ColorColumn,
+    character::{Character, Pixel, Style},
+    color,
+    layout::VerticalText,
+    LedMatrix,
+};
+
+/// The direction in which the content moves across the matrix.
+///
+/// Text and horizontal billboards move along the x-axis, vertical billboards
+/// along the y-axis. Moving up is treated like moving left and moving down
+/// like moving right, so that a marquee can be used for either.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Direction {
+    /// The content enters on the right edge.
+    #[default]
+    Left,
+    /// The content enters on the left edge.
+    Right,
+    /// The content enters on the bottom edge.
+    Up,
+    /// The content enters on the top edge.
+    Down,
+}
+
+impl Direction {
+    /// Whether the offset grows as time passes.
+    const fn is_forward(self) -> bool {
+        matches!(self, Direction::Left | Direction::Up)
+    }
+}
+
+/// What a [Marquee] does when its content has passed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Repeat {
+    /// The content enters the matrix, moves across it until it has left
+    /// completely, and then the marquee is [finished](Marquee::is_finished).
+    #[default]
+    Once,
+    /// The content is repeated without a gap, so that its start follows
+    /// right after its end. Add blank columns to the content for some
+    /// space in between.
+    Loop,
+    /// The content moves back and forth. Content which is longer than the
+    /// matrix moves until its end is shown, shorter content moves until it
+    /// touches the other edge.
+    Bounce,
+}
+
+/// The state of content moving across the matrix.
+///
+/// See the [module documentation](self) for examples.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Marquee {
+    length: usize,
+    view: usize,
+    direction: Direction,
+    repeat: Repeat,
+    step_ms: u32,
+    pause_start_ms: u32,
+    pause_end_ms: u32,
+    time_ms: u32,
+}
+
+impl Marquee {
+    /// A marquee for content which is `length` pixels long, on a matrix
+    /// which is `view` pixels wide, or high for vertical billboards.
+    ///
+    /// The content moves left once, by one pixel every 50 milliseconds.
+    pub const fn new(length: usize, view: usize) -> Self {
+        Self {
+            length,
+            view,
+            direction: Direction::Left,
+            repeat: Repeat::Once,
+            step_ms: 50,
+            pause_start_ms: 0,
+            pause_end_ms: 0,
+            time_ms: 0,
+        }
+    }
+
+    pub const fn with_direction(mut self, direction: Direction) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    pub const fn with_repeat(mut self, repeat: Repeat) -> Self {
+        self.repeat = repeat;
+        self
+    }
+
+    /// Move the content by one pixel every `step_ms` milliseconds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step_ms` is zero.
+    pub const fn with_speed(mut self, step_ms: u32) -> Self {
+        assert!(step_ms > 0, "the speed must not be zero");
+        self.step_ms = step_ms;
+        self
+    }
+
+    /// Hold the first and last position for some extra time.
+    ///
+    /// When the marquee [bounces](Repeat::Bounce), it pauses at both ends
+    /// each time. When it [loops](Repeat::Loop), it pauses at the start of
+    /// the content before and at its end after every pass.
+    pub const fn with_pauses(mut self, start_ms: u32, end_ms: u32) -> Self {
+        self.pause_start_ms = start_ms;
+        self.pause_end_ms = end_ms;
+        self
+    }
+
+    pub const fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    pub const fn repeat(&self) -> Repeat {
+        self.repeat
+    }
+
+    /// The number of positions in a single pass, and which of them is the
+    /// end position.
+    const fn positions(&self) -> (u32, u32) {
+        let count = match self.repeat {
+            Repeat::Once => (self.length + self.view).saturating_sub(1),
+            Repeat::Loop => self.length,
+            Repeat::Bounce => 2 * self.length.abs_diff(self.view),
+        };
+        let count = if count > u32::MAX as usize {
+            u32::MAX
+        } else {
+            count as u32
+        };
+        match self.repeat {
+            // empty content, or content as long as the matrix doesn't move
+            Repeat::Loop | Repeat::Bounce if count == 0 => (1, 0),
+            Repeat::Once | Repeat::Loop => (count, count.saturating_sub(1)),
+            Repeat::Bounce => (count, count / 2),
+        }
+    }
+
+    /// The time at which the content reaches a position.
+    const fn start_of(&self, position: u32) -> u32 {
+        let (_, end) = self.positions();
+        let mut time = position.saturating_mul(self.step_ms);
+        if position > 0 {
+            time = time.saturating_add(self.pause_start_ms);
+        }
+        if position > end {
+            time = time.saturating_add(self.pause_end_ms);
+        }
+        time
+    }
+
+    /// The duration of a single pass, in milliseconds.
+    pub const fn duration_ms(&self) -> u32 {
+        let (count, _) = self.positions();
+        match count {
+            0 => 0,
+            _ => self.start_of(count),
+        }
+    }
+
+    /// The position at the current time.
+    const fn position(&self) -> u32 {
+        let (count, end) = self.positions();
+        if count == 0 {
+            return 0;
+        }
+        let time = self.time_ms;
+        if time < self.start_of(1) {
+            return 0;
+        }
+        let time = time - self.pause_start_ms;
+        let mut position = time / self.step_ms;
+        if position > end {
+            position = time.saturating_sub(self.pause_end_ms) / self.step_ms;
+            if position < end {
+                position = end;
+            }
+        }
+        if position >= count {
+            count - 1
+        } else {
+            position
+        }
+    }
+
+    /// The position in the content at the left or top edge of the matrix.
+    pub const fn offset(&self) -> i32 {
+        let position = self.position() as i64;
+        let (length, view) = (self.length as i64, self.view as i64);
+        let offset = match (self.repeat, self.direction.is_forward()) {
+            (Repeat::Once, true) => position - (view - 1),
+            (Repeat::Once, false) => length - 1 - position,
+            (Repeat::Loop, true) => position,
+            (Repeat::Loop, false) if position == 0 => 0,
+            (Repeat::Loop, false) => length - position,
+            (Repeat::Bounce, forward) => {
+                let span = (length - view).abs();
+                let distance = if position <= span {
+                    position
+                } else {
+                    2 * span - position
+                };
+                // move between the start of the content and its end, or the
+                // other edge of the matrix
+                let (first, last) = if length < view {
+                    (length - view, 0)
+                } else {
+                    (0, length - view)
+                };
+                if forward {
+                    first + distance
+                } else {
+                    last - distance
+                }
+            }
+        };
+        offset as i32
+    }
+
+    /// Advance the marquee by the given time, usually the time since the
+    /// last call.
+    pub fn advance(&mut self, elapsed_ms: u32) {
+        let duration = self.duration_ms();
+        self.time_ms = match (duration, self.repeat) {
+            (0, _) => 0,
+            (_, Repeat::Once) => self.time_ms.saturating_add(elapsed_ms).min(duration),
+            // the sum of two u32 can't overflow in a u64, and the remainder
+            // fits into a u32 again
+            _ => ((u64::from(self.time_ms) + u64::from(elapsed_ms)) % u64::from(duration)) as u32,
+        };
+    }
+
+    /// Start over at the first position.
+    pub fn restart(&mut self) {
+        self.time_ms = 0;
+    }
+
+    /// Whether a marquee which runs [once](Repeat::Once) has moved its
+    /// content across the whole matrix. Other marquees never finish.
+    pub const fn is_finished(&self) -> bool {
+        matches!(self.repeat, Repeat::Once) && self.time_ms >= self.duration_ms()
+    }
+
+    /// The time until the content moves again, in milliseconds.
+    pub const fn remaining_ms(&self) -> u32 {
+        let (count, _) = self.positions();
+        if count == 0 || self.is_finished() {
+            return 0;
+        }
+        self.start_of(self.position() + 1) - self.time_ms
+    }
+
+    /// Draw a frame with `draw`, show it, and wait until the content moves,
+    /// until the marquee is [finished](Self::is_finished).
+    ///
+    /// Marquees which [loop](Repeat::Loop) or [bounce](Repeat::Bounce) run
+    /// forever.
+    pub fn run<const W: usize, const H: usize>(
+        &mut self,
+        matrix: &mut dyn LedMatrix<W, H>,
+        mut draw: impl FnMut(&Self, &mut dyn LedMatrix<W, H>),
+    ) {
+        while !self.is_finished() {
+            draw(self, matrix);
+            matrix.apply();
+            let remaining = self.remaining_ms();
+            matrix.sleep_ms(remaining);
+            self.advance(remaining);
+        }
+    }
+
+    /// The position in the content which is shown at a position on the
+    /// matrix, counted from the left or top edge.
+    fn content_position(&self, screen: usize) -> Option<usize> {
+        let position = self.offset() as i64 + screen as i64;
+        if matches!(self.repeat, Repeat::Loop) && self.length > 0 {
+            return Some(position.rem_euclid(self.length as i64) as usize);
+        }
+        usize::try_from(position)
+            .ok()
+            .filter(|&position| position < self.length)
+    }
+
+    /// Draw the current frame of a horizontal billboard, which is
+    /// constructed with [`billboard::horizontal`](crate::billboard::horizontal).
+    pub fn draw_horizontal_billboard<const W: usize, const H: usize>(
+        &self,
+        matrix: &mut dyn LedMatrix<W, H>,
+        billboard: &[[bool; H]],
+    ) {
+        for x in 0..W {
+            let column = self.content_position(x).and_then(|x| billboard.get(x));
+            for y in 0..H {
+                matrix[(x, y)] = match column {
+                    Some(column) if column[H - y - 1] => color::WHITE,
+                    _ => color::BLACK,
+                };
+            }
+        }
+    }
+
+    /// Draw the current frame of a vertical billboard, which is constructed
+    /// with [`billboard::vertical`](crate::billboard::vertical).
+    pub fn draw_vertical_billboard<const W: usize, const H: usize>(
+        &self,
+        matrix: &mut dyn LedMatrix<W, H>,
+        billboard: &[[bool; W]],
+    ) {
+        for y in 0..H {
+            let row = self.content_position(y).and_then(|y| billboard.get(y));
+            for x in 0..W {
+                matrix[(x, H - y - 1)] = match row {
+                    Some(row) if row[x] => color::WHITE,
+                    _ => color::BLACK,
+                };
+            }
+        }
+    }
+
+    /// Draw the current frame of a strip of text, which is constructed with
+    /// [convert_str!](crate::convert_str!).
+    ///
+    /// Like [draw_styled_text_billboard_frame](crate::LedMatrix::draw_styled_text_billboard_frame),
+    /// LEDs which aren't part of a character are set to the background color
+    /// of the style, or turned off if there is none.
+    pub fn draw_text<const W: usize, const H: usize>(
+        &self,
+        matrix: &mut dyn LedMatrix<W, H>,
+        text: &[Character],
+        style: &Style,
+    ) {
+        matrix.fill(style.background().unwrap_or(color::BLACK));
+        for screen_x in 0..W {
+            let Some(column) = self.content_position(screen_x) else {
+                continue;
+            };
+            // characters are sorted by their offset
+            let index = text.partition_point(|c| c.offset + c.width <= column);
+            let Some(c) = text.get(index).filter(|c| c.offset <= column) else {
+                continue;
+            };
+            for &(x, y) in c.coordinates {
+                if c.offset + x == column && y < H {
+                    let pixel = Pixel {
+                        x: column,
+                        y,
+                        index,
+                    };
+                    matrix[(screen_x, y)] = style.foreground(pixel);
+                }
+            }
+        }
+    }
+
+    /// Draw the current frame of [VerticalText].
+    ///
+    /// LEDs which aren't part of a character are set to the background color
+    /// of the style, or turned off if there is none.
+    pub fn draw_vertical_text<const W: usize, const H: usize>(
+        &self,
+        matrix: &mut dyn LedMatrix<W, H>,
+        text: &VerticalText,
+        style: &Style,
+    ) {
+        matrix.fill(style.background().unwrap_or(color::BLACK));
+        for row in 0..H {
+            if let Some(text_row) = self.content_position(row) {
+                text.draw_row(matrix, row, text_row, style);
+            }
+        }
+    }
+
+    /// Draw the current frame of the columns of a
+    /// [ColorBillboard](crate::billboard::ColorBillboard).
+    pub fn draw_color_billboard<const W: usize, const H: usize>(
+        &self,
+        matrix: &mut dyn LedMatrix<W, H>,
+        columns: &[ColorColumn<H>],
+    ) {
+        for x in 0..W {
+            let column = self.content_position(x).and_then(|x| columns.get(x));
+            for y in 0..H {
+                matrix[(x, y)] = column.map_or(color::BLACK, |column| column[H - y - 1]);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use std::vec::Vec;
+
+    /// The offsets of the first `count` positions, and how long each one is
+    /// shown.
+    fn steps(mut marquee: Marquee, count: usize) -> Vec<(i32, u32)> {
+        (0..count)
+            .map(|_| {
+                let step = (marquee.offset(), marquee.remaining_ms());
+                marquee.advance(step.1);
+                step
+            })
+            .collect()
+    }
+
+    fn marquee(length: usize, repeat: Repeat, direction: Direction) -> Marquee {
+        Marquee::new(length, 4)
+            .with_speed(10)
+            .with_repeat(repeat)
+            .with_direction(direction)
+    }
+
+    fn offsets(marquee: Marquee, count: usize) -> Vec<i32> {
+        steps(marquee, count)
+            .into_iter()
+            .map(|(offset, _)| offset)
+            .collect()
+    }
+
+    #[test]
+    fn once() {
+        use Direction::*;
+        let once = |length, direction| marquee(length, Repeat::Once, direction);
+        // the content enters on one side and leaves on the other
+        assert_eq!(offsets(once(3, Left), 8), [-3, -2, -1, 0, 1, 2, 2, 2]);
+        assert_eq!(offsets(once(3, Right), 8), [2, 1, 0, -1, -2, -3, -3, -3]);
+        assert_eq!(
+            offsets(once(6, Left), 10),
+            [-3, -2, -1, 0, 1, 2, 3, 4, 5, 5]
+        );
+        assert_eq!(
+            offsets(once(6, Right), 10),
+            [5, 4, 3, 2, 1, 0, -1, -2, -3, -3]
+        );
+
+        let mut marquee = once(3, Left);
+        assert_eq!(marquee.duration_ms(), 60);
+        marquee.advance(59);
+        assert!(!marquee.is_finished());
+        marquee.advance(1);
+        assert!(marquee.is_finished());
+        assert_eq!(marquee.remaining_ms(), 0);
+        marquee.restart();
+        assert_eq!(marquee.offset(), -3);
+    }
+
+    #[test]
+    fn once_with_pauses() {
+        let marquee = marquee(3, Repeat::Once, Direction::Left).with_pauses(100, 200);
+        assert_eq!(marquee.duration_ms(), 360);
+        assert_eq!(
+            steps(marquee, 7),
+            [
+                (-3, 110),
+                (-2, 10),
+                (-1, 10),
+                (0, 10),
+                (1, 10),
+                (2, 210),
+                (2, 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn loops() {
+        use Direction::*;
+        let looping = |length, direction| marquee(length, Repeat::Loop, direction);
+        // the content is repeated without a gap
+        assert_eq!(offsets(looping(3, Left), 7), [0, 1, 2, 0, 1, 2, 0]);
+        assert_eq!(offsets(looping(3, Right), 7), [0, 2, 1, 0, 2, 1, 0]);
+        assert_eq!(offsets(looping(6, Left), 7), [0, 1, 2, 3, 4, 5, 0]);
+        assert_eq!(offsets(looping(6, Right), 7), [0, 5, 4, 3, 2, 1, 0]);
+
+        let mut marquee = looping(3, Left);
+        assert_eq!(marquee.duration_ms(), 30);
+        marquee.advance(1_000_000);
+        assert!(!marquee.is_finished());
+        assert_eq!(marquee.offset(), 1);
+    }
+
+    #[test]
+    fn loops_with_pauses() {
+        let marquee = marquee(3, Repeat::Loop, Direction::Right).with_pauses(100, 200);
+        assert_eq!(marquee.duration_ms(), 330);
+        assert_eq!(steps(marquee, 4), [(0, 110), (2, 10), (1, 210), (0, 110)]);
+    }
+
+    #[test]
+    fn bounces() {
+        use Direction::*;
+        let bouncing = |length, direction| marquee(length, Repeat::Bounce, direction);
+        // long content moves between its start and its end
+        assert_eq!(offsets(bouncing(6, Left), 6), [0, 1, 2, 1, 0, 1]);
+        assert_eq!(offsets(bouncing(6, Right), 6), [2, 1, 0, 1, 2, 1]);
+        // short content moves between the edges of the matrix
+        assert_eq!(offsets(bouncing(3, Left), 4), [-1, 0, -1, 0]);
+        assert_eq!(offsets(bouncing(3, Right), 4), [0, -1, 0, -1]);
+        // content as long as the matrix doesn't move
+        assert_eq!(offsets(bouncing(4, Left), 3), [0, 0, 0]);
+    }
+
+    #[test]
+    fn bounces_with_pauses() {
+        let marquee = marquee(6, Repeat::Bounce, Direction::Left).with_pauses(100, 200);
+        assert_eq!(marquee.duration_ms(), 340);
+        assert_eq!(
+            steps(marquee, 5),
+            [(0, 110), (1, 10), (2, 210), (1, 10), (0, 110)]
+        );
+    }
+
+    #[test]
+    fn vertical_directions_match_horizontal_ones() {
+        for repeat in [Repeat::Once, Repeat::Loop, Repeat::Bounce] {
+            for length in [3, 6] {
+                for (vertical, horizontal) in [
+                    (Direction::Up, Direction::Left),
+                    (Direction::Down, Direction::Right),
+                ] {
+                    assert_eq!(
+                        steps(marquee(length, repeat, vertical).with_pauses(100, 200), 12),
+                        steps(
+                            marquee(length, repeat, horizontal).with_pauses(100, 200),
+                            12
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn long_durations_dont_overflow() {
+        let mut marquee = Marquee::new(5, 3)
+            .with_pauses(u32::MAX, 0)
+            .with_repeat(Repeat::Loop);
+        assert_eq!(marquee.duration_ms(), u32::MAX);
+        marquee.advance(u32::MAX - 1);
+        marquee.advance(u32::MAX - 1);
+        assert_eq!(marquee.offset(), 0);
+        assert_eq!(marquee.remaining_ms(), 2);
+
+        let mut marquee = Marquee::new(5, 3).with_pauses(u32::MAX, u32::MAX);
+        marquee.advance(u32::MAX);
+        marquee.advance(u32::MAX);
+        assert!(marquee.is_finished());
+    }
+}
@@ -0,0 +1,421 @@
+//! Rasterization of geometric shapes.
+//!
+//! The functions in this module don't know anything about the matrix, they
+//! report every pixel of the shape to a callback, which is responsible for
+//! clipping. Only lines are clipped up front, because they can be arbitrarily
+//! long.
+
+/// A straight line, rasterized like Bresenham's line algorithm, but every
+/// pixel can be computed directly from its index. This makes it possible to
+/// clip the line without walking the parts outside the clipping area, which
+/// could take billions of steps for far away end points.
+struct Line {
+    from: (i64, i64),
+    // the number of steps along the major axis, the line has one pixel more
+    steps: i64,
+    // the distance along the minor axis
+    minor: i64,
+    x_major: bool,
+    // the direction of every step, 1 or -1 along each axis
+    direction: (i64, i64),
+}
+
+impl Line {
+    fn new(from: (i32, i32), to: (i32, i32)) -> Self {
+        let from = (i64::from(from.0), i64::from(from.1));
+        let (dx, dy) = (i64::from(to.0) - from.0, i64::from(to.1) - from.1);
+        let x_major = dx.abs() >= dy.abs();
+        let (steps, minor) = match x_major {
+            true => (dx.abs(), dy.abs()),
+            false => (dy.abs(), dx.abs()),
+        };
+        let direction = (if dx < 0 { -1 } else { 1 }, if dy < 0 { -1 } else { 1 });
+        Self {
+            from,
+            steps,
+            minor,
+            x_major,
+            direction,
+        }
+    }
+
+    /// How far along the minor axis the pixel after `step` steps is, rounded
+    /// half away from the start like Bresenham's algorithm does.
+    fn minor_offset(&self, step: i64) -> i64 {
+        if self.steps == 0 {
+            return 0;
+        }
+        let (step, minor, steps) = (
+            i128::from(step),
+            i128::from(self.minor),
+            i128::from(self.steps),
+        );
+        ((2 * step * minor + steps) / (2 * steps)) as i64
+    }
+
+    /// The pixel after `step` steps, which must be in `0..=steps`.
+    fn pixel(&self, step: i64) -> (i32, i32) {
+        let (major, minor) = (step, self.minor_offset(step));
+        let (x, y) = match self.x_major {
+            true => (major, minor),
+            false => (minor, major),
+        };
+        // every pixel is between the end points, so it fits into an i32
+        (
+            (self.from.0 + self.direction.0 * x) as i32,
+            (self.from.1 + self.direction.1 * y) as i32,
+        )
+    }
+
+    /// The first and last step whose pixels are within the inclusive bounds
+    /// `x` and `y`, or `None` if the line doesn't touch them.
+    fn clip(&self, x: (i64, i64), y: (i64, i64)) -> Option<(i64, i64)> {
+        // the range of offsets from the start along an axis, which stay in
+        // bounds
+        let offsets = |from: i64, direction: i64, (min, max): (i64, i64)| match direction {
+            1 => (min - from, max - from),
+            _ => (from - max, from - min),
+        };
+        let (x, y) = (
+            offsets(self.from.0, self.direction.0, x),
+            offsets(self.from.1, self.direction.1, y),
+        );
+        let (major, minor) = match self.x_major {
+            true => (x, y),
+            false => (y, x),
+        };
+
+        let (mut first, mut last) = (major.0.max(0), major.1.min(self.steps));
+        if self.minor == 0 {
+            if minor.0 > 0 || minor.1 < 0 {
+                return None;
+            }
+        } else {
+            // invert the rounding of minor_offset, which grows monotonically
+            let (steps, distance) = (i128::from(self.steps), i128::from(self.minor));
+            let (min, max) = (i128::from(minor.0), i128::from(minor.1));
+            let lowest = -(steps - 2 * steps * min).div_euclid(2 * distance);
+            let highest = (2 * steps * (max + 1) - steps - 1).div_euclid(2 * distance);
+            first = first.max(lowest.clamp(0, steps + 1) as i64);
+            last = last.min(highest.clamp(-1, steps) as i64);
+        }
+        (first <= last).then_some((first, last))
+    }
+}
+
+/// Draw a straight line, both end points are included. Only the pixels
+/// within a matrix of the given size are reported.
+pub(crate) fn line(
+    from: (i32, i32),
+    to: (i32, i32),
+    (width, height): (usize, usize),
+    plot: &mut dyn FnMut(i32, i32),
+) {
+    let line = Line::new(from, to);
+    let bounds = |size: usize| (0, size as i64 - 1);
+    if let Some((first, last)) = line.clip(bounds(width), bounds(height)) {
+        for step in first..=last {
+            let (x, y) = line.pixel(step);
+            plot(x, y);
+        }
+    }
+}
+
+/// Midpoint circle algorithm. Every point `(x, y)` with `x >= y` of one
+/// octant is reported, the caller mirrors it to the other octants.
+pub(crate) fn circle_octant(radius: i32, plot: &mut dyn FnMut(i32, i32)) {
+    let (mut x, mut y) = (radius, 0);
+    let mut err = 1 - radius;
+    while x >= y {
+        plot(x, y);
+        y += 1;
+        if err < 0 {
+            err += 2 * y + 1;
+        } else {
+            x -= 1;
+            err += 2 * (y - x) + 1;
+        }
+    }
+}
+
+/// Midpoint ellipse algorithm. Every point `(x, y)` of one quadrant is
+/// reported, the caller mirrors it to the other quadrants.
+pub(crate) fn ellipse_quadrant((rx, ry): (i32, i32), plot: &mut dyn FnMut(i32, i32)) {
+    let (rx2, ry2) = (i64::from(rx) * i64::from(rx), i64::from(ry) * i64::from(ry));
+    let (mut x, mut y) = (0, ry);
+    let (mut px, mut py) = (0, 2 * rx2 * i64::from(y));
+
+    // region 1: the slope is less than 1
+    let mut p = ry2 - rx2 * i64::from(ry) + rx2 / 4;
+    while px < py {
+        plot(x, y);
+        x += 1;
+        px += 2 * ry2;
+        if p < 0 {
+            p += ry2 + px;
+        } else {
+            y -= 1;
+            py -= 2 * rx2;
+            p += ry2 + px - py;
+        }
+    }
+
+    // region 2: the slope is greater than 1
+    let (x2, y2) = (2 * i64::from(x) + 1, i64::from(y) - 1);
+    let mut p = ry2 * x2 * x2 / 4 + rx2 * y2 * y2 - rx2 * ry2;
+    while y >= 0 {
+        plot(x, y);
+        y -= 1;
+        py -= 2 * rx2;
+        if p > 0 {
+            p += rx2 - py;
+        } else {
+            x += 1;
+            px += 2 * ry2;
+            p += rx2 - py + px;
+        }
+    }
+}
+
+/// The horizontal extent of a filled triangle in every row `0..H`, `None`
+/// for rows the triangle doesn't touch.
+///
+/// The spans are computed from the pixels of the edges, rasterized the same
+/// way as lines, so filled and outlined triangles cover the same pixels. The
+/// spans may extend past the sides of the matrix.
+pub(crate) fn triangle_spans<const H: usize>(
+    a: (i32, i32),
+    b: (i32, i32),
+    c: (i32, i32),
+) -> [Option<(i32, i32)>; H] {
+    let mut spans = [None; H];
+    for (from, to) in [(a, b), (b, c), (c, a)] {
+        let line = Line::new(from, to);
+        for (y, span) in spans.iter_mut().enumerate() {
+            let row = (y as i64, y as i64);
+            let Some((first, last)) = line.clip((i32::MIN.into(), i32::MAX.into()), row) else {
+                continue;
+            };
+            // the pixels of a row are consecutive
+            let (x0, x1) = (line.pixel(first).0, line.pixel(last).0);
+            let (min, max) = (x0.min(x1), x0.max(x1));
+            *span = match *span {
+                None => Some((min, max)),
+                Some((other_min, other_max)) => Some((min.min(other_min), max.max(other_max))),
+            };
+        }
+    }
+    spans
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::LedMatrix as _;
+    use led_matrix_headless::LedMatrix;
+    use std::{string::String, vec::Vec};
+
+    const ON: (u8, u8, u8) = (255, 255, 255);
+
+    /// Draw on an 8x8 matrix and render it as text, from the top row down.
+    fn render(draw: impl FnOnce(&mut LedMatrix)) -> Vec<String> {
+        let mut matrix = LedMatrix::new();
+        draw(&mut matrix);
+        matrix.apply();
+        let leds = matrix.last_frame().unwrap().leds;
+        leds.iter()
+            .map(|row| {
+                row.iter()
+                    .map(|&led| if led == ON { '#' } else { '.' })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// The unclipped Bresenham algorithm, which `line` must agree with.
+    fn bresenham(from: (i32, i32), to: (i32, i32)) -> Vec<(i32, i32)> {
+        let mut pixels = Vec::new();
+        let (mut x, mut y) = from;
+        let dx = (to.0 - from.0).abs();
+        let dy = -(to.1 - from.1).abs();
+        let step_x = if from.0 < to.0 { 1 } else { -1 };
+        let step_y = if from.1 < to.1 { 1 } else { -1 };
+        let mut err = dx + dy;
+        loop {
+            pixels.push((x, y));
+            if (x, y) == to {
+                return pixels;
+            }
+            let err2 = 2 * err;
+            if err2 >= dy {
+                err += dy;
+                x += step_x;
+            }
+            if err2 <= dx {
+                err += dx;
+                y += step_y;
+            }
+        }
+    }
+
+    #[test]
+    fn clipped_lines_match_bresenham() {
+        let coordinates = -5..13;
+        for from in coordinates
+            .clone()
+            .flat_map(|x| coordinates.clone().map(move |y| (x, y)))
+        {
+            for to in coordinates
+                .clone()
+                .flat_map(|x| coordinates.clone().map(move |y| (x, y)))
+            {
+                let mut pixels = Vec::new();
+                line(from, to, (8, 6), &mut |x, y| pixels.push((x, y)));
+                let mut expected = bresenham(from, to);
+                expected.retain(|&(x, y)| (0..8).contains(&x) && (0..6).contains(&y));
+                assert_eq!(pixels, expected, "{from:?} -> {to:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn far_away_lines() {
+        let bottom = ["........"; 7].into_iter().chain(["########"]);
+        assert!(render(|m| m.draw_line((i32::MIN, 0), (i32::MAX, 0), ON))
+            .into_iter()
+            .eq(bottom));
+        assert_eq!(
+            render(|m| m.draw_line((i32::MAX, i32::MAX), (i32::MIN, i32::MIN), ON)),
+            [
+                ".......#", "......#.", ".....#..", "....#...", "...#....", "..#.....", ".#......",
+                "#.......",
+            ]
+        );
+        // a steep line still has exactly one pixel in every row
+        for row in render(|m| m.draw_line((0, i32::MIN), (7, i32::MAX), ON)) {
+            assert_eq!(row.matches('#').count(), 1, "{row}");
+        }
+        // lines which don't touch the matrix at all
+        let empty = ["........"; 8];
+        assert_eq!(
+            render(|m| m.draw_line((i32::MIN, 8), (i32::MAX, 8), ON)),
+            empty
+        );
+        assert_eq!(
+            render(|m| m.draw_line((-1, i32::MIN), (-1, i32::MAX), ON)),
+            empty
+        );
+        assert_eq!(render(|m| m.draw_line((-20, 0), (0, -20), ON)), empty);
+    }
+
+    #[test]
+    fn lines() {
+        assert_eq!(
+            render(|m| m.draw_line((-3, -1), (10, 5), ON)),
+            [
+                "........", "........", "........", ".......#", ".....##.", "...##...", ".##.....",
+                "#.......",
+            ]
+        );
+        assert_eq!(
+            render(|m| m.draw_line((2, 9), (5, -4), ON)),
+            [
+                "..#.....", "...#....", "...#....", "...#....", "...#....", "....#...", "....#...",
+                "....#...",
+            ]
+        );
+    }
+
+    #[test]
+    fn rects() {
+        assert_eq!(
+            render(|m| m.draw_rect((5, 2), (10, 6), ON)),
+            [
+                "........", ".....###", ".....#..", ".....#..", ".....#..", ".....###", "........",
+                "........",
+            ]
+        );
+        assert_eq!(
+            render(|m| m.fill_rect((2, 20), (-4, 5), ON)),
+            [
+                "###.....", "###.....", "###.....", "........", "........", "........", "........",
+                "........",
+            ]
+        );
+    }
+
+    #[test]
+    fn circles() {
+        assert_eq!(
+            render(|m| m.draw_circle((1, 3), 3, ON)),
+            [
+                "........", "###.....", "...#....", "....#...", "....#...", "....#...", "...#....",
+                "###.....",
+            ]
+        );
+        assert_eq!(
+            render(|m| m.fill_circle((6, 6), 2, ON)),
+            [
+                "....####", "....####", "....####", ".....###", "........", "........", "........",
+                "........",
+            ]
+        );
+    }
+
+    #[test]
+    fn ellipses() {
+        assert_eq!(
+            render(|m| m.draw_ellipse((3, 3), (4, 2), ON)),
+            [
+                "........", "........", ".#####..", "#.....#.", ".......#", "#.....#.", ".#####..",
+                "........",
+            ]
+        );
+        assert_eq!(
+            render(|m| m.draw_ellipse((3, 3), (0, 2), ON)),
+            [
+                "........", "........", "...#....", "...#....", "...#....", "...#....", "...#....",
+                "........",
+            ]
+        );
+    }
+
+    #[test]
+    fn triangles() {
+        let (a, b, c) = ((-2, 0), (6, 1), (3, 9));
+        assert_eq!(
+            render(|m| m.draw_triangle(a, b, c, ON)),
+            [
+                "..#.#...", ".#..#...", ".#..#...", "#....#..", "#....#..", "......#.", "..#####.",
+                "##......",
+            ]
+        );
+        assert_eq!(
+            render(|m| m.fill_triangle(a, b, c, ON)),
+            [
+                "..###...", ".####...", ".####...", "######..", "######..", "#######.", "#######.",
+                "##......",
+            ]
+        );
+        assert_eq!(
+            render(|m| m.fill_triangle((-1_000_000_000, 1), (1_000_000_000, 1), (3, 6), ON)),
+            [
+                "........", "########", "########", "########", "########", "########", "########",
+                "........",
+            ]
+        );
+    }
+
+    #[test]
+    fn polygons() {
+        assert_eq!(
+            render(|m| m.draw_polygon(&[(3, -2), (9, 3), (3, 9), (-2, 3)], ON)),
+            [
+                ".#...#..", "#.....#.", "#......#", "........", "........", "........", "#.....##",
+                ".#...#..",
+            ]
+        );
+    }
+}
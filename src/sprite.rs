@@ -0,0 +1,493 @@
+//! Sprite sheets and frame-based animations.
+//!
+//! A [SpriteSheet] slices a single image into many sprites, either along a
+//! grid of equally sized frames or into named regions. Draw a sprite with
+//! [draw_sprite](crate::LedMatrix::draw_sprite).
+//!
+//! An [Animation] describes a sequence of sprites and how long each of them
+//! is shown. To step it from a game loop, [start](Animation::start) a
+//! [Playback] and advance it by the time that has passed:
+//!
+//! ```
+//! use led_matrix::{
+//!     bitmap::{Bitmap, Transparency},
+//!     include_image,
+//!     sprite::{Animation, AnimationFrame, SpriteSheet},
+//! };
+//!
+//! // four frames of 8x8 pixels next to each other
+//! static SHEET: SpriteSheet = SpriteSheet::grid(include_image!("bitmaps/walker.png"), (8, 8))
+//!     .with_transparency(Transparency::Alpha);
+//!
+//! static WALK: Animation = Animation::looping(&[
+//!     AnimationFrame::new(0, 150),
+//!     AnimationFrame::new(1, 100),
+//!     AnimationFrame::new(2, 150),
+//!     AnimationFrame::new(3, 100),
+//! ]);
+//!
+//! # fn app(matrix: &mut dyn led_matrix::LedMatrix) {
+//! let mut walk = WALK.start();
+//! for x in -8..8 {
+//!     matrix.fill((0, 0, 40));
+//!     matrix.draw_sprite(&SHEET, walk.sprite(), (x, 0));
+//!     matrix.apply();
+//!     matrix.sleep_ms(50);
+//!     walk.advance(50);
+//! }
+//! # }
+//! ```
+
+use crate::bitmap::{Bitmap, Transparency};
+
+/// A rectangular part of an image.
+///
+/// Like the coordinates of a [Bitmap], `x` and `y` are the position of the
+/// lower left corner of the region.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Region {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Region {
+    pub const fn new((x, y): (usize, usize), (width, height): (usize, usize)) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+}
+
+/// An image which contains many sprites.
+///
+/// Sprites are identified by their index. In a [grid](Self::grid), sprites
+/// are numbered row by row, starting in the upper left corner like text is
+/// read. With [regions](Self::with_regions), the index is the position in the
+/// list of regions and [find](Self::find) looks up a sprite by its name.
+#[derive(Debug, Clone, Copy)]
+pub struct SpriteSheet<'a> {
+    bitmap: Bitmap<'a>,
+    layout: Layout<'a>,
+    transparency: Transparency,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Layout<'a> {
+    Grid { width: usize, height: usize },
+    Regions(&'a [(&'a str, Region)]),
+}
+
+impl<'a> SpriteSheet<'a> {
+    /// Slice the image into a grid of sprites of the given size. Pixels on
+    /// the right and bottom edge which don't fill a whole sprite are ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the width or height of the sprites is zero.
+    pub const fn grid(bitmap: Bitmap<'a>, (width, height): (usize, usize)) -> Self {
+        assert!(width > 0 && height > 0, "sprites must not be empty");
+        Self {
+            bitmap,
+            layout: Layout::Grid { width, height },
+            transparency: Transparency::Opaque,
+        }
+    }
+
+    /// Slice the image into named regions, which may have different sizes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a region doesn't fit into the image.
+    ///
+    /// ```
+    /// use led_matrix::{
+    ///     include_image,
+    ///     sprite::{Region, SpriteSheet},
+    /// };
+    ///
+    /// static SHEET: SpriteSheet = SpriteSheet::with_regions(
+    ///     include_image!("bitmaps/big_img.bmp"),
+    ///     &[
+    ///         ("corner", Region::new((0, 0), (8, 8))),
+    ///         ("strip", Region::new((0, 8), (20, 4))),
+    ///     ],
+    /// );
+    ///
+    /// assert_eq!(SHEET.find("strip"), Some(1));
+    /// assert_eq!(SHEET.region(1), Some(Region::new((0, 8), (20, 4))));
+    /// ```
+    pub const fn with_regions(bitmap: Bitmap<'a>, regions: &'a [(&'a str, Region)]) -> Self {
+        let mut i = 0;
+        while i < regions.len() {
+            let region = regions[i].1;
+            assert!(
+                region.x + region.width <= bitmap.width()
+                    && region.y + region.height <= bitmap.height(),
+                "region doesn't fit into the image"
+            );
+            i += 1;
+        }
+        Self {
+            bitmap,
+            layout: Layout::Regions(regions),
+            transparency: Transparency::Opaque,
+        }
+    }
+
+    /// Draw the sprites with the given transparency, instead of drawing every
+    /// pixel as it is.
+    pub const fn with_transparency(mut self, transparency: Transparency) -> Self {
+        self.transparency = transparency;
+        self
+    }
+
+    /// The image containing all sprites.
+    pub const fn bitmap(&self) -> &Bitmap<'a> {
+        &self.bitmap
+    }
+
+    pub const fn transparency(&self) -> Transparency {
+        self.transparency
+    }
+
+    /// The number of sprites.
+    pub const fn len(&self) -> usize {
+        match self.layout {
+            Layout::Grid { width, height } => {
+                (self.bitmap.width() / width) * (self.bitmap.height() / height)
+            }
+            Layout::Regions(regions) => regions.len(),
+        }
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The region of the image containing a sprite, or `None` if there is no
+    /// sprite with this index.
+    pub const fn region(&self, index: usize) -> Option<Region> {
+        if index >= self.len() {
+            return None;
+        }
+        match self.layout {
+            Layout::Grid { width, height } => {
+                let columns = self.bitmap.width() / width;
+                let (column, row) = (index % columns, index / columns);
+                // rows are counted from the top, but y from the bottom
+                let y = self.bitmap.height() - (row + 1) * height;
+                Some(Region::new((column * width, y), (width, height)))
+            }
+            Layout::Regions(regions) => Some(regions[index].1),
+        }
+    }
+
+    /// The index of the sprite with the given name. Sprites in a grid don't
+    /// have names.
+    pub fn find(&self, name: &str) -> Option<usize> {
+        match self.layout {
+            Layout::Grid { .. } => None,
+            Layout::Regions(regions) => regions.iter().position(|&(n, _)| n == name),
+        }
+    }
+}
+
+/// A single frame of an [Animation].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AnimationFrame {
+    /// The index of the sprite in its [SpriteSheet].
+    pub sprite: usize,
+    /// How long the sprite is shown, in milliseconds.
+    pub duration_ms: u32,
+}
+
+impl AnimationFrame {
+    pub const fn new(sprite: usize, duration_ms: u32) -> Self {
+        Self {
+            sprite,
+            duration_ms,
+        }
+    }
+}
+
+/// A sequence of sprites, each shown for its own duration.
+///
+/// The animation only describes the sequence, which makes it possible to
+/// store it in a `static`. The progress of a running animation is tracked by
+/// a [Playback].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Animation<'a> {
+    frames: &'a [AnimationFrame],
+    looping: bool,
+}
+
+impl<'a> Animation<'a> {
+    /// An animation which starts over after its last frame.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no frames.
+    pub const fn looping(frames: &'a [AnimationFrame]) -> Self {
+        assert!(!frames.is_empty(), "animation without frames");
+        Self {
+            frames,
+            looping: true,
+        }
+    }
+
+    /// An animation which stops at its last frame.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no frames.
+    pub const fn once(frames: &'a [AnimationFrame]) -> Self {
+        assert!(!frames.is_empty(), "animation without frames");
+        Self {
+            frames,
+            looping: false,
+        }
+    }
+
+    pub const fn frames(&self) -> &'a [AnimationFrame] {
+        self.frames
+    }
+
+    pub const fn is_looping(&self) -> bool {
+        self.looping
+    }
+
+    /// The duration of a single pass through all frames, in milliseconds.
+    pub const fn duration_ms(&self) -> u32 {
+        let mut total = 0u32;
+        let mut i = 0;
+        while i < self.frames.len() {
+            total = total.saturating_add(self.frames[i].duration_ms);
+            i += 1;
+        }
+        total
+    }
+
+    /// The sprite shown at the given time since the start of the animation.
+    pub const fn sprite_at(&self, time_ms: u32) -> usize {
+        let duration = self.duration_ms();
+        let mut time = match duration {
+            0 => 0,
+            _ if self.looping => time_ms % duration,
+            _ if time_ms >= duration => return self.frames[self.frames.len() - 1].sprite,
+            _ => time_ms,
+        };
+        let mut i = 0;
+        while i < self.frames.len() - 1 && time >= self.frames[i].duration_ms {
+            time -= self.frames[i].duration_ms;
+            i += 1;
+        }
+        self.frames[i].sprite
+    }
+
+    /// Start playing the animation from its first frame.
+    pub const fn start(&self) -> Playback<'a> {
+        Playback {
+            animation: *self,
+            time_ms: 0,
+        }
+    }
+}
+
+/// A running [Animation], which is advanced by the time that has passed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Playback<'a> {
+    animation: Animation<'a>,
+    time_ms: u32,
+}
+
+impl<'a> Playback<'a> {
+    pub const fn animation(&self) -> &Animation<'a> {
+        &self.animation
+    }
+
+    /// Advance the animation by the given time, usually the time since the
+    /// last call.
+    pub fn advance(&mut self, elapsed_ms: u32) {
+        let duration = self.animation.duration_ms();
+        self.time_ms = match duration {
+            0 => 0,
+            // the sum of two u32 can't overflow in a u64, and the remainder
+            // fits into a u32 again
+            _ if self.animation.looping => {
+                ((u64::from(self.time_ms) + u64::from(elapsed_ms)) % u64::from(duration)) as u32
+            }
+            _ => self.time_ms.saturating_add(elapsed_ms).min(duration),
+        };
+    }
+
+    /// Start over at the first frame.
+    pub fn restart(&mut self) {
+        self.time_ms = 0;
+    }
+
+    /// The sprite to draw right now.
+    pub const fn sprite(&self) -> usize {
+        self.animation.sprite_at(self.time_ms)
+    }
+
+    /// Whether an animation which is played [once](Animation::once) has
+    /// reached its end. Looping animations never finish.
+    pub const fn is_finished(&self) -> bool {
+        !self.animation.looping && self.time_ms >= self.animation.duration_ms()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LedMatrix as _;
+
+    const K: (u8, u8, u8) = (0, 0, 0);
+    const R: (u8, u8, u8) = (255, 0, 0);
+    const G: (u8, u8, u8) = (0, 255, 0);
+    const W: (u8, u8, u8) = (255, 255, 255);
+
+    /// Two 2x2 sprites next to each other, above a row of three 1x1 pixels
+    /// and a pixel which doesn't fill a whole sprite.
+    #[rustfmt::skip]
+    static PIXELS: [u8; 4 * 3 * 3] = [
+        255, 0, 0,    0, 0, 0,    0, 255, 0,  0, 255, 0,
+        255, 0, 0,    255, 0, 0,  0, 0, 0,    0, 255, 0,
+        0, 0, 255,    255, 255, 255, 0, 0, 255, 255, 255, 255,
+    ];
+
+    const FRAMES: [AnimationFrame; 3] = [
+        AnimationFrame::new(4, 100),
+        AnimationFrame::new(2, 0),
+        AnimationFrame::new(7, 50),
+    ];
+
+    #[test]
+    fn grid_regions() {
+        let sheet = SpriteSheet::grid(Bitmap::from_rgb(4, 3, &PIXELS), (2, 2));
+        // the bottom row doesn't fill a whole sprite
+        assert_eq!(sheet.len(), 2);
+        assert_eq!(sheet.region(0), Some(Region::new((0, 1), (2, 2))));
+        assert_eq!(sheet.region(1), Some(Region::new((2, 1), (2, 2))));
+        assert_eq!(sheet.region(2), None);
+        assert_eq!(sheet.find("anything"), None);
+
+        let sheet = SpriteSheet::grid(Bitmap::from_rgb(4, 3, &PIXELS), (1, 1));
+        assert_eq!(sheet.len(), 12);
+        // numbered row by row from the top
+        assert_eq!(sheet.region(0), Some(Region::new((0, 2), (1, 1))));
+        assert_eq!(sheet.region(5), Some(Region::new((1, 1), (1, 1))));
+        assert_eq!(sheet.region(11), Some(Region::new((3, 0), (1, 1))));
+    }
+
+    #[test]
+    fn named_regions() {
+        static REGIONS: [(&str, Region); 2] = [
+            ("strip", Region::new((0, 0), (4, 1))),
+            ("corner", Region::new((3, 2), (1, 1))),
+        ];
+        let sheet = SpriteSheet::with_regions(Bitmap::from_rgb(4, 3, &PIXELS), &REGIONS);
+        assert_eq!(sheet.len(), 2);
+        assert_eq!(sheet.find("corner"), Some(1));
+        assert_eq!(sheet.find("missing"), None);
+        assert_eq!(sheet.region(0), Some(Region::new((0, 0), (4, 1))));
+        assert_eq!(sheet.region(2), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "region doesn't fit into the image")]
+    fn regions_must_fit_into_the_image() {
+        static REGIONS: [(&str, Region); 1] = [("wide", Region::new((1, 0), (4, 1)))];
+        SpriteSheet::with_regions(Bitmap::from_rgb(4, 3, &PIXELS), &REGIONS);
+    }
+
+    #[test]
+    fn draw_sprites() {
+        let sheet = SpriteSheet::grid(Bitmap::from_rgb(4, 3, &PIXELS), (2, 2));
+        let mut matrix = led_matrix_headless::LedMatrix::<3, 3>::new();
+        matrix.draw_sprite(&sheet, 0, (0, 0));
+        // partly outside of the matrix
+        matrix.draw_sprite(&sheet, 1, (2, 1));
+        matrix.apply();
+        assert_eq!(
+            matrix.last_frame().unwrap().leds,
+            [[K, K, G], [R, K, K], [R, R, K]]
+        );
+
+        let sheet = sheet.with_transparency(Transparency::ColorKey(K));
+        let mut matrix = led_matrix_headless::LedMatrix::<3, 3>::new();
+        matrix.fill(W);
+        matrix.draw_sprite(&sheet, 1, (-1, 0));
+        matrix.apply();
+        assert_eq!(
+            matrix.last_frame().unwrap().leds,
+            [[W, W, W], [G, W, W], [G, W, W]]
+        );
+    }
+
+    #[test]
+    fn sprite_at() {
+        let looping = Animation::looping(&FRAMES);
+        assert_eq!(looping.duration_ms(), 150);
+        // frames without a duration are skipped
+        let sprites = [0, 99, 100, 149, 150, 250, 1000].map(|t| looping.sprite_at(t));
+        assert_eq!(sprites, [4, 4, 7, 7, 4, 7, 7]);
+
+        let once = Animation::once(&FRAMES);
+        let sprites = [0, 99, 100, 149, 150, 1000].map(|t| once.sprite_at(t));
+        assert_eq!(sprites, [4, 4, 7, 7, 7, 7]);
+
+        // if every frame is skipped, the last one stays
+        let frames = [AnimationFrame::new(3, 0), AnimationFrame::new(5, 0)];
+        assert_eq!(Animation::looping(&frames).sprite_at(123), 5);
+    }
+
+    #[test]
+    fn playback() {
+        let mut looping = Animation::looping(&FRAMES).start();
+        let mut once = Animation::once(&FRAMES).start();
+        for (elapsed, looping_sprite, once_sprite) in [
+            (0, 4, 4),
+            (60, 4, 4),
+            (60, 7, 7),
+            (29, 7, 7),
+            (1, 4, 7),
+            (100, 7, 7),
+        ] {
+            looping.advance(elapsed);
+            once.advance(elapsed);
+            assert_eq!(
+                (looping.sprite(), once.sprite()),
+                (looping_sprite, once_sprite)
+            );
+        }
+        assert!(once.is_finished());
+        assert!(!looping.is_finished());
+
+        once.restart();
+        assert_eq!(once.sprite(), 4);
+        assert!(!once.is_finished());
+    }
+
+    #[test]
+    fn long_animations_dont_overflow() {
+        static LONG: [AnimationFrame; 2] =
+            [AnimationFrame::new(0, u32::MAX), AnimationFrame::new(1, 5)];
+        let mut playback = Animation::looping(&LONG).start();
+        assert_eq!(playback.animation().duration_ms(), u32::MAX);
+        playback.advance(u32::MAX - 1);
+        playback.advance(u32::MAX - 1);
+        assert_eq!(playback.sprite(), 0);
+
+        let mut playback = Animation::once(&LONG).start();
+        playback.advance(u32::MAX);
+        playback.advance(u32::MAX);
+        assert!(playback.is_finished());
+        assert_eq!(playback.sprite(), 1);
+    }
+}
@@ -0,0 +1,376 @@
+//! Combine several panels into one large canvas.
+//!
+//! A [TiledMatrix] takes a number of panels and a [Tile] for each of them,
+//! which specifies where and in which orientation the panel is mounted. The
+//! resulting canvas can be used like any other [LedMatrix](crate::LedMatrix),
+//! indexing it routes every LED to the correct panel.
+//!
+//! For common layouts, where panels of the same size are chained together in
+//! a grid, the tiles can be computed with [chain].
+//!
+//! To run your program on several emulated panels at once, use [run].
+
+use led_matrix_core::{correction::Gamma, power::PowerLimit, JoystickPosition, LedMatrixCore};
+
+pub use led_matrix_core::{Rotation, Tile};
+
+/// The order in which panels are chained together in a grid.
+///
+/// The chain always starts in the lower left corner and continues to the
+/// right, filling the grid row by row from the bottom up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ChainOrder {
+    /// Every row of panels runs from left to right.
+    #[default]
+    Progressive,
+    /// Rows of panels alternate between running from left to right and from
+    /// right to left. The panels of every other row are mounted upside down,
+    /// so the cable can run straight from one row to the next.
+    ///
+    /// This is also known as a zig-zag layout.
+    Serpentine,
+}
+
+/// Compute the tiles for `N` panels of size `panel` chained together to
+/// cover a canvas of size `canvas`.
+///
+/// Panics if the panels don't cover the canvas exactly.
+///
+/// # Examples
+///
+/// ```
+/// use led_matrix::tiled::{chain, ChainOrder, Rotation, Tile};
+///
+/// // four 8x8 panels forming a 16x16 canvas
+/// const TILES: [Tile; 4] = chain((16, 16), (8, 8), ChainOrder::Serpentine);
+///
+/// assert_eq!(TILES[1], Tile::new(8, 0, Rotation::Deg0));
+/// assert_eq!(TILES[2], Tile::new(8, 8, Rotation::Deg180));
+/// ```
+// `usize::is_multiple_of` needs Rust 1.87
+#[allow(clippy::manual_is_multiple_of)]
+pub const fn chain<const N: usize>(
+    canvas: (usize, usize),
+    panel: (usize, usize),
+    order: ChainOrder,
+) -> [Tile; N] {
+    assert!(
+        canvas.0 % panel.0 == 0 && canvas.1 % panel.1 == 0,
+        "canvas size must be a multiple of the panel size"
+    );
+    let columns = canvas.0 / panel.0;
+    let rows = canvas.1 / panel.1;
+    assert!(columns * rows == N, "panels don't cover the canvas");
+
+    let mut tiles = [Tile::new(0, 0, Rotation::Deg0); N];
+    let mut i = 0;
+    while i < N {
+        let row = i / columns;
+        let mut column = i % columns;
+        if matches!(order, ChainOrder::Serpentine) && row % 2 == 1 {
+            column = columns - column - 1;
+            tiles[i].rotation = Rotation::Deg180;
+        }
+        tiles[i].x = column * panel.0;
+        tiles[i].y = row * panel.1;
+        i += 1;
+    }
+    tiles
+}
+
+/// A large canvas of size `W` x `H` made of `N` panels of size `PW` x `PH`.
+///
+/// Inputs, sleeping and the sine function are provided by the first panel.
+/// Positions on the canvas which aren't covered by any panel are black and
+/// changes to them are discarded.
+///
+/// The panels share a single power supply, so the power limit applies to all
+/// of them together: the current of the whole canvas is estimated and every
+/// panel is dimmed by the same amount, just like a single large matrix.
+pub struct TiledMatrix<
+    T,
+    const W: usize,
+    const H: usize,
+    const N: usize,
+    const PW: usize = 8,
+    const PH: usize = 8,
+> {
+    panels: [T; N],
+    tiles: [Tile; N],
+    // written to when indexing a position that isn't covered by any panel
+    uncovered: (u8, u8, u8),
+    // the power limit is applied here, the panels get the limited brightness
+    brightness: u8,
+    gamma: Gamma,
+    power_limit: PowerLimit,
+}
+
+impl<T, const W: usize, const H: usize, const N: usize, const PW: usize, const PH: usize>
+    TiledMatrix<T, W, H, N, PW, PH>
+where
+    T: LedMatrixCore<PW, PH>,
+{
+    /// Combine panels into a canvas, placing every panel according to the
+    /// tile at the same index.
+    ///
+    /// Panics if a tile isn't entirely on the canvas.
+    pub fn new(panels: [T; N], tiles: [Tile; N]) -> Self {
+        assert!(N > 0, "a tiled matrix needs at least one panel");
+        for tile in &tiles {
+            let (width, height) = tile.rotation.footprint((PW, PH));
+            assert!(
+                tile.x + width <= W && tile.y + height <= H,
+                "tile is outside the canvas"
+            );
+        }
+        let mut matrix = Self {
+            panels,
+            tiles,
+            uncovered: (0, 0, 0),
+            brightness: 255,
+            gamma: Gamma::LINEAR,
+            power_limit: PowerLimit::USB,
+        };
+        for panel in &mut matrix.panels {
+            panel.set_gamma(Gamma::LINEAR);
+            panel.set_power_limit(PowerLimit::UNLIMITED);
+        }
+        matrix
+    }
+
+    /// Combine panels that are chained together in a grid, see [chain].
+    pub fn chained(panels: [T; N], order: ChainOrder) -> Self {
+        Self::new(panels, chain((W, H), (PW, PH), order))
+    }
+
+    /// Get the individual panels back.
+    pub fn into_panels(self) -> [T; N] {
+        self.panels
+    }
+
+    fn locate(&self, position: (usize, usize)) -> Option<(usize, (usize, usize))> {
+        assert!((0..W).contains(&position.0));
+        assert!((0..H).contains(&position.1));
+        self.tiles
+            .iter()
+            .enumerate()
+            .find_map(|(i, tile)| Some((i, tile.to_panel(position, (PW, PH))?)))
+    }
+}
+
+impl<T, const W: usize, const H: usize, const N: usize, const PW: usize, const PH: usize>
+    LedMatrixCore<W, H> for TiledMatrix<T, W, H, N, PW, PH>
+where
+    T: LedMatrixCore<PW, PH>,
+{
+    fn apply(&mut self) {
+        let gamma = &self.gamma;
+        let leds = self.panels.iter().flat_map(|panel| {
+            (0..PH).flat_map(move |y| (0..PW).map(move |x| gamma.correct(panel[(x, y)])))
+        });
+        let brightness = self.power_limit.limit_brightness(leds, self.brightness);
+        for panel in &mut self.panels {
+            panel.set_brightness(brightness);
+            panel.apply()
+        }
+    }
+
+    fn set_brightness(&mut self, brightness: u8) {
+        self.brightness = brightness
+    }
+
+    fn set_gamma(&mut self, gamma: Gamma) {
+        self.gamma = gamma;
+        for panel in &mut self.panels {
+            panel.set_gamma(gamma)
+        }
+    }
+
+    fn set_power_limit(&mut self, limit: PowerLimit) {
+        self.power_limit = limit
+    }
+
+    fn sleep_ms(&mut self, duration: u32) {
+        self.panels[0].sleep_ms(duration)
+    }
+
+    fn get_sin(&self) -> fn(f32) -> f32 {
+        self.panels[0].get_sin()
+    }
+
+    fn joystick_position(&mut self) -> JoystickPosition {
+        self.panels[0].joystick_position()
+    }
+
+    fn joystick_pressed(&mut self) -> bool {
+        self.panels[0].joystick_pressed()
+    }
+
+    fn switch(&mut self) -> bool {
+        self.panels[0].switch()
+    }
+}
+
+impl<T, const W: usize, const H: usize, const N: usize, const PW: usize, const PH: usize>
+    core::ops::Index<(usize, usize)> for TiledMatrix<T, W, H, N, PW, PH>
+where
+    T: LedMatrixCore<PW, PH>,
+{
+    type Output = (u8, u8, u8);
+
+    fn index(&self, position: (usize, usize)) -> &Self::Output {
+        match self.locate(position) {
+            Some((i, position)) => &self.panels[i][position],
+            None => &(0, 0, 0),
+        }
+    }
+}
+impl<T, const W: usize, const H: usize, const N: usize, const PW: usize, const PH: usize>
+    core::ops::IndexMut<(usize, usize)> for TiledMatrix<T, W, H, N, PW, PH>
+where
+    T: LedMatrixCore<PW, PH>,
+{
+    fn index_mut(&mut self, position: (usize, usize)) -> &mut Self::Output {
+        match self.locate(position) {
+            Some((i, position)) => &mut self.panels[i][position],
+            None => &mut self.uncovered,
+        }
+    }
+}
+
+/// Runs your program on a canvas made of several 8x8 panels.
+///
+/// This is analogous to [run](crate::run), but the emulators display every
+/// panel at the position and in the orientation specified by its tile. The
+/// size of the canvas is inferred from the signature of your function.
+///
+/// On hardware, the panels must be chained on a single data line, in the
+/// same order as the tiles.
+///
+/// Panics unless the tiles cover the canvas exactly, see
+/// [TiledWiring::new](crate::mapping::TiledWiring::new). This is checked in
+/// the emulators as well, even though they could display other layouts.
+///
+/// ```no_run
+/// use led_matrix::tiled::{chain, ChainOrder, Tile};
+///
+/// const TILES: [Tile; 4] = chain((16, 16), (8, 8), ChainOrder::Serpentine);
+///
+/// fn app(matrix: &mut dyn led_matrix::LedMatrix<16, 16>) {
+///     // ...
+/// }
+///
+/// led_matrix::tiled::run(TILES, app);
+/// ```
+pub fn run<
+    const W: usize,
+    const H: usize,
+    const N: usize,
+    F: FnOnce(&mut dyn crate::LedMatrix<W, H>) + Send + 'static,
+>(
+    tiles: [Tile; N],
+    f: F,
+) -> ! {
+    let mapping =
+        crate::mapping::TiledWiring::<N>::new::<W, H>(tiles, crate::mapping::Wiring::DEFAULT);
+    #[cfg(target_os = "none")]
+    {
+        led_matrix_bsp::run_mapped::<W, H, _, _>(mapping, |mut matrix| f(&mut matrix))
+    }
+    #[cfg(all(not(target_os = "none"), feature = "headless"))]
+    {
+        // nothing is displayed, so the layout doesn't matter
+        let _ = mapping;
+        led_matrix_headless::run::<W, H, _>(|mut matrix| f(&mut matrix))
+    }
+    #[cfg(all(
+        not(target_os = "none"),
+        not(feature = "headless"),
+        not(feature = "tui")
+    ))]
+    {
+        led_matrix_gui::run_tiled::<8, 8, N, _>(mapping.tiles, move |panels| {
+            f(&mut TiledMatrix::<_, W, H, N>::new(panels, mapping.tiles))
+        })
+    }
+    #[cfg(all(not(target_os = "none"), not(feature = "headless"), feature = "tui"))]
+    {
+        led_matrix_tui::run_tiled::<8, 8, N, _>(mapping.tiles, move |panels| {
+            f(&mut TiledMatrix::<_, W, H, N>::new(panels, mapping.tiles))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{chain, ChainOrder, TiledMatrix};
+    use crate::LedMatrix as _;
+    use led_matrix_core::{correction::Gamma, power::PowerLimit};
+    use led_matrix_headless::LedMatrix as Headless;
+
+    const WHITE: (u8, u8, u8) = (255, 255, 255);
+
+    fn canvas() -> TiledMatrix<Headless, 16, 8, 2> {
+        TiledMatrix::chained([Headless::new(), Headless::new()], ChainOrder::Progressive)
+    }
+
+    #[test]
+    fn leds_are_routed_to_their_panels() {
+        let tiles = chain((8, 16), (8, 8), ChainOrder::Serpentine);
+        let mut matrix = TiledMatrix::<_, 8, 16, 2>::new([Headless::new(), Headless::new()], tiles);
+        matrix[(1, 2)] = WHITE;
+        // the upper panel is upside down
+        matrix[(1, 10)] = WHITE;
+        matrix.apply();
+        let [lower, upper] = matrix.into_panels();
+        assert_eq!(lower.last_frame().unwrap()[(1, 2)], WHITE);
+        assert_eq!(upper.last_frame().unwrap()[(6, 5)], WHITE);
+    }
+
+    #[test]
+    fn power_limit_is_shared_by_all_panels() {
+        let mut matrix = canvas();
+        // only the left panel is lit
+        matrix.fill_rect((0, 0), (7, 7), WHITE);
+        matrix.apply();
+
+        let leds = [WHITE; 64].into_iter().chain([(0, 0, 0); 64]);
+        let expected = PowerLimit::USB.limit_brightness(leds.clone(), 255);
+        assert_eq!(expected, 23);
+        assert!(PowerLimit::USB.estimate_ma(leds, expected) <= 500);
+        for panel in matrix.into_panels() {
+            assert_eq!(panel.last_frame().unwrap().brightness, expected);
+            assert_eq!(*panel.power_limit(), PowerLimit::UNLIMITED);
+        }
+    }
+
+    #[test]
+    fn power_limit_includes_brightness_and_gamma() {
+        let mut matrix = canvas();
+        matrix.set_brightness(10);
+        matrix.fill((128, 128, 128));
+        matrix.apply();
+        let [left, _] = matrix.into_panels();
+        assert_eq!(left.last_frame().unwrap().brightness, 10);
+
+        // a gamma curve makes the same colors draw less current
+        for (gamma, brightness) in [(Gamma::LINEAR, 23), (Gamma::GAMMA_2_2, 55)] {
+            let mut matrix = canvas();
+            matrix.set_gamma(gamma);
+            matrix.fill((128, 128, 128));
+            matrix.apply();
+            for panel in matrix.into_panels() {
+                assert_eq!(*panel.gamma(), gamma);
+                assert_eq!(panel.last_frame().unwrap().brightness, brightness);
+            }
+        }
+
+        let mut matrix = canvas();
+        matrix.set_power_limit(PowerLimit::UNLIMITED);
+        matrix.fill(WHITE);
+        matrix.apply();
+        let [left, right] = matrix.into_panels();
+        assert_eq!(left.last_frame().unwrap().brightness, 255);
+        assert_eq!(right.last_frame().unwrap().brightness, 255);
+    }
+}
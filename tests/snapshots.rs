@@ -0,0 +1,133 @@
+//! Compare what the drawing functions produce against checked-in snapshots.
+//!
+//! Run with `LED_MATRIX_UPDATE_SNAPSHOTS=1` to update the snapshots after an
+//! intended change, and check the differences before committing them.
+
+#![cfg(not(target_os = "none"))]
+
+use led_matrix::{
+    billboard::{horizontal, vertical, Billboard},
+    bitmap,
+    character::{Style, FONT_3X5, FONT_5X7},
+    color, convert_str,
+    layout::{Alignment, Orientation, TextBox, VerticalText},
+    LedMatrix as _,
+};
+use led_matrix_headless::{assert_frame_eq, LedMatrix};
+
+static ARROW: Billboard = &horizontal([
+    *b"   #      ",
+    *b"    #     ",
+    *b"     #    ",
+    *b"########  ",
+    *b"     #    ",
+    *b"    #     ",
+    *b"   #      ",
+    *b"          ",
+]);
+
+static STAIRS: Billboard = &vertical([
+    *b"##      ",
+    *b"  ##    ",
+    *b"    ##  ",
+    *b"      ##",
+    *b"      ##",
+    *b"    ##  ",
+    *b"  ##    ",
+    *b"##      ",
+    *b"#  ##  #",
+    *b"########",
+]);
+
+#[test]
+fn draw_bitmap() {
+    let mut matrix = LedMatrix::<8, 8>::new();
+    matrix.draw_bitmap(bitmap::CRAB);
+    assert_frame_eq!(matrix, "tests/snapshots/crab.txt");
+    matrix.draw_bitmap(bitmap::MC_CREEPER);
+    assert_frame_eq!(matrix, "tests/snapshots/creeper.txt");
+    matrix.draw_bitmap(bitmap::TEST_GRID);
+    assert_frame_eq!(matrix, "tests/snapshots/test_grid.ppm");
+}
+
+#[test]
+fn draw_horizontal_billboard_frame() {
+    let mut matrix = LedMatrix::<8, 8>::new();
+    for offset in [0, 3, 7, 10] {
+        matrix.draw_horizontal_billboard_frame(ARROW, offset);
+        let path = format!("tests/snapshots/arrow_{offset}.txt");
+        assert_frame_eq!(matrix, path);
+    }
+}
+
+#[test]
+fn draw_vertical_billboard_frame() {
+    let mut matrix = LedMatrix::<8, 8>::new();
+    for offset in [0, 2, 8] {
+        matrix.draw_vertical_billboard_frame(STAIRS, offset);
+        let path = format!("tests/snapshots/stairs_{offset}.txt");
+        assert_frame_eq!(matrix, path);
+    }
+}
+
+#[test]
+fn draw_text_billboard_frame() {
+    let mut matrix = LedMatrix::<8, 8>::new();
+    let (text, length) = &convert_str!("Rgö");
+    assert_eq!(*length, 17);
+    for offset in [0, 4, 12, 16] {
+        matrix.draw_text_billboard_frame(text, offset);
+        let path = format!("tests/snapshots/text_{offset}.txt");
+        assert_frame_eq!(matrix, path);
+    }
+}
+
+#[test]
+fn text_box_pages() {
+    // wrapped at the spaces, the newline and within "READY"
+    let text_box = TextBox::new(&FONT_3X5, (16, 16));
+    let style = Style::color(color::WHITE);
+    let mut matrix = LedMatrix::<16, 16>::new();
+    let pages: Vec<_> = text_box.pages("HOT  TEA\nIS READY").collect();
+    assert_eq!(pages, ["HOT  TEA", "IS READ", "Y"]);
+    for (i, page) in pages.into_iter().enumerate() {
+        matrix.clear();
+        text_box.draw(&mut matrix, (0, 0), page, &style);
+        let path = format!("tests/snapshots/text_box_page_{i}.txt");
+        assert_frame_eq!(matrix, path);
+    }
+}
+
+#[test]
+fn text_box_alignment() {
+    let style = Style::color(color::WHITE);
+    let mut matrix = LedMatrix::<16, 16>::new();
+    for (alignment, name) in [
+        (Alignment::Left, "left"),
+        (Alignment::Center, "center"),
+        (Alignment::Right, "right"),
+    ] {
+        let text_box = TextBox::new(&FONT_3X5, (16, 11)).with_alignment(alignment);
+        matrix.clear();
+        text_box.draw(&mut matrix, (0, 5), "A BC\nD", &style);
+        let path = format!("tests/snapshots/text_box_{name}.txt");
+        assert_frame_eq!(matrix, path);
+    }
+}
+
+#[test]
+fn vertical_text() {
+    let style = Style::color(color::WHITE).with_background(color::BLUE);
+    let mut matrix = LedMatrix::<8, 8>::new();
+    for (orientation, name) in [
+        (Orientation::Stacked, "stacked"),
+        (Orientation::Rotated, "rotated"),
+    ] {
+        let text = VerticalText::new("Hi", &FONT_5X7).with_orientation(orientation);
+        for offset in [-2, 0, 5] {
+            text.draw(&mut matrix, offset, &style);
+            let path = format!("tests/snapshots/vertical_{name}_{offset}.txt");
+            assert_frame_eq!(matrix, path);
+        }
+    }
+}